@@ -27,6 +27,7 @@ use std::{
         Debug,
         Display,
     },
+    path::PathBuf,
     str::FromStr,
 };
 
@@ -35,11 +36,18 @@ use super::{
     display_contract_exec_result,
     display_contract_exec_result_debug,
     display_dry_run_result_warning,
+    display_fee_estimate,
+    display_multisig_details,
+    display_multisig_status,
+    interactive::Prompt,
     parse_account,
     parse_balance,
     print_dry_running_status,
     print_gas_required_success,
     prompt_confirm_tx,
+    renderer,
+    BlockRef,
+    CLIArgsOpts,
     CLIExtrinsicOpts,
     MAX_KEY_COL_WIDTH,
 };
@@ -50,12 +58,18 @@ use anyhow::{
 };
 use contract_build::name_value_println;
 use contract_extrinsics::{
-    pallet_contracts_primitives::StorageDeposit,
+    pallet_contracts_primitives::{
+        ContractExecResult,
+        StorageDeposit,
+    },
+    ArgsFormat,
+    ArtifactRegistry,
     CallCommandBuilder,
     CallExec,
     DisplayEvents,
     ExtrinsicOptsBuilder,
     TokenMetadata,
+    TrySigner,
 };
 use contract_transcode::Value;
 use sp_weights::Weight;
@@ -65,6 +79,7 @@ use subxt::{
         ExtrinsicParams,
     },
     ext::{
+        codec::Decode,
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
@@ -78,11 +93,17 @@ pub struct CallCommand {
     #[clap(name = "contract", long, env = "CONTRACT")]
     contract: String,
     /// The name of the contract message to call.
-    #[clap(long, short)]
-    message: String,
-    /// The arguments of the contract message to call.
-    #[clap(long, num_args = 0..)]
-    args: Vec<String>,
+    #[clap(long, short, required_unless_present = "interactive")]
+    message: Option<String>,
+    #[clap(flatten)]
+    args_opts: CLIArgsOpts,
+    /// Prompt for each argument of the message, validated against its type, and for the
+    /// message itself if `--message` is omitted.
+    #[clap(
+        long,
+        conflicts_with_all = ["args", "args_file", "named_args", "output_json"]
+    )]
+    interactive: bool,
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
     /// Maximum amount of gas (execution time) to be used for this command.
@@ -98,9 +119,21 @@ pub struct CallCommand {
     /// The value to be transferred as part of the call.
     #[clap(name = "value", long, default_value = "0")]
     value: String,
+    /// Directory of contract artifacts used to decode the events emitted by other
+    /// contracts during the call.
+    #[clap(long, value_parser)]
+    artifacts_dir: Option<PathBuf>,
+    /// Dry-run the call against the state at the given block hash or number, instead of
+    /// the best block.
+    #[clap(long, conflicts_with = "execute")]
+    at: Option<BlockRef>,
     /// Export the call output in JSON format.
     #[clap(long, conflicts_with = "verbose")]
     output_json: bool,
+    /// Display the decoded return value and events as plain data, e.g. balances as
+    /// integers and timestamps in milliseconds, instead of in a human-readable form.
+    #[clap(long)]
+    plain: bool,
 }
 
 impl CallCommand {
@@ -121,17 +154,23 @@ impl CallCommand {
         &self,
     ) -> Result<(), ErrorVariant>
     where
-        <C as Config>::AccountId: IntoVisitor + FromStr + EncodeAsType,
+        <C as Config>::AccountId:
+            IntoVisitor + FromStr + EncodeAsType + Decode + Ord + Display + Serialize,
         <<C as Config>::AccountId as FromStr>::Err: Display,
+        <C as Config>::AssetId: FromStr,
+        <<C as Config>::AssetId as FromStr>::Err: Display,
         C::Balance:
             From<u128> + Display + Default + FromStr + Serialize + Debug + EncodeAsType,
         <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
             From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
+        <C as Config>::Hash: From<[u8; 32]>,
     {
         let contract = parse_account(&self.contract)
             .map_err(|e| anyhow::anyhow!("Failed to parse contract option: {}", e))?;
-        let signer = C::Signer::from_str(&self.extrinsic_cli_opts.suri)
-            .map_err(|_| anyhow::anyhow!("Failed to parse suri option"))?;
+        let signer: C::Signer = self.extrinsic_cli_opts.signer()?;
+        self.extrinsic_cli_opts
+            .ensure_multisig_weight(self.gas_limit, self.proof_size)?;
+        let multisig = self.extrinsic_cli_opts.multisig(&signer.account_id())?;
         let chain = self.extrinsic_cli_opts.chain_cli_opts.chain();
         let token_metadata = TokenMetadata::query::<C>(&chain.url()).await?;
         let storage_deposit_limit = self
@@ -145,16 +184,51 @@ impl CallCommand {
             })?;
         let value = parse_balance(&self.value, &token_metadata)
             .map_err(|e| anyhow::anyhow!("Failed to parse value option: {}", e))?;
+        let renderer =
+            (!self.plain && !self.output_json()).then(|| renderer(Some(&token_metadata)));
+        let custom_types = self.extrinsic_cli_opts.custom_types()?;
+        let ss58_prefix = self
+            .extrinsic_cli_opts
+            .chain_cli_opts
+            .ss58_prefix::<C>()
+            .await?;
+        let args_format = self.args_opts.args_format;
+        let transcoder = self.extrinsic_cli_opts.contract_transcoder()?;
+        let (message, args, call_data) = if self.interactive {
+            let prompt = Prompt::new(&transcoder, args_format);
+            let message = match self.message {
+                Some(ref message) => message.clone(),
+                None => prompt.select_message()?,
+            };
+            let args = prompt.message_args(&message)?;
+            let call_data =
+                transcoder.encode_with_format(&message, &args, args_format)?;
+            (message, args, call_data)
+        } else {
+            let message = self.message.clone().expect("required unless interactive");
+            let call_data = self.args_opts.encode(&transcoder, &message)?;
+            (message, self.args_opts.args.clone(), call_data)
+        };
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
             .file(self.extrinsic_cli_opts.file.clone())
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
             .url(chain.url())
             .storage_deposit_limit(storage_deposit_limit)
+            .multisig(multisig)
+            .proxy_for(self.extrinsic_cli_opts.proxy_for()?)
+            .nonce(self.extrinsic_cli_opts.nonce)
+            .tip(self.extrinsic_cli_opts.tip(&token_metadata)?)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .fee_asset(self.extrinsic_cli_opts.fee_asset()?)
             .verbosity(self.extrinsic_cli_opts.verbosity()?)
+            .renderer(renderer.clone())
+            .custom_types(custom_types.clone())
+            .ss58_prefix(Some(ss58_prefix))
             .done();
 
-        let call_exec = CallCommandBuilder::new(contract, &self.message, extrinsic_opts)
-            .args(self.args.clone())
+        let call_exec = CallCommandBuilder::new(contract, &message, extrinsic_opts)
+            .args(args)
+            .call_data(call_data)
             .gas_limit(self.gas_limit)
             .proof_size(self.proof_size)
             .value(value)
@@ -163,35 +237,31 @@ impl CallCommand {
         let metadata = call_exec.client().metadata();
 
         if !self.extrinsic_cli_opts.execute {
-            let result = call_exec.call_dry_run().await?;
+            let block_hash = match self.at {
+                Some(ref at) => Some(at.block_hash::<C>(&chain.url()).await?),
+                None => None,
+            };
+            let result = call_exec.call_dry_run_at(block_hash).await?;
             match result.result {
                 Ok(ref ret_val) => {
-                    let value = call_exec
-                        .transcoder()
-                        .decode_message_return(
-                            call_exec.message(),
-                            &mut &ret_val.data[..],
-                        )
-                        .context(format!(
-                            "Failed to decode return value {:?}",
-                            &ret_val
-                        ))?;
-                    let dry_run_result = CallDryRunResult {
-                        reverted: ret_val.did_revert(),
-                        data: value,
-                        gas_consumed: result.gas_consumed,
-                        gas_required: result.gas_required,
-                        storage_deposit: result.storage_deposit.clone(),
-                    };
-                    if self.output_json() {
-                        println!("{}", dry_run_result.to_json()?);
-                    } else {
-                        dry_run_result.print();
-                        display_contract_exec_result_debug::<_, DEFAULT_KEY_COL_WIDTH, _>(
-                            &result,
-                        )?;
-                        display_dry_run_result_warning("message");
-                    };
+                    let transcoder = call_exec.transcoder();
+                    let data = &mut &ret_val.data[..];
+                    let context =
+                        || format!("Failed to decode return value {:?}", &ret_val);
+                    match args_format {
+                        ArgsFormat::Scon => {
+                            let value = transcoder
+                                .decode_message_return(call_exec.message(), data)
+                                .with_context(context)?;
+                            self.display_dry_run_result(value, &result)?;
+                        }
+                        ArgsFormat::Json => {
+                            let value = transcoder
+                                .decode_message_return_json(call_exec.message(), data)
+                                .with_context(context)?;
+                            self.display_dry_run_result(value, &result)?;
+                        }
+                    }
                 }
                 Err(ref err) => {
                     let object = ErrorVariant::from_dispatch_error(err, &metadata)?;
@@ -204,6 +274,19 @@ impl CallCommand {
                 }
             }
         } else {
+            let registry = match self.artifacts_dir {
+                Some(ref dir) => ArtifactRegistry::load(dir)?,
+                None => ArtifactRegistry::default(),
+            };
+            let registry = match custom_types {
+                Some(ref custom_types) => registry.with_custom_types(custom_types)?,
+                None => registry,
+            };
+            let registry = registry.with_ss58_prefix(ss58_prefix)?;
+            let registry = match renderer {
+                Some(renderer) => registry.with_renderer(renderer),
+                None => registry,
+            };
             let gas_limit = pre_submit_dry_run_gas_estimate_call(
                 &call_exec,
                 self.output_json(),
@@ -211,6 +294,7 @@ impl CallCommand {
             )
             .await?;
             if !self.extrinsic_cli_opts.skip_confirm {
+                let fee = call_exec.estimate_fee(gas_limit).await;
                 prompt_confirm_tx(|| {
                     name_value_println!(
                         "Message",
@@ -227,24 +311,93 @@ impl CallCommand {
                         gas_limit.to_string(),
                         DEFAULT_KEY_COL_WIDTH
                     );
+                    display_fee_estimate(
+                        &fee,
+                        &token_metadata,
+                        call_exec.opts().fee_asset(),
+                    );
+                    if let Some(real) = call_exec.opts().proxy_for() {
+                        name_value_println!(
+                            "Proxy for",
+                            real.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    if let Some(multisig) = call_exec.opts().multisig() {
+                        display_multisig_details(multisig);
+                    }
                 })?;
             }
             let events = call_exec.call(Some(gas_limit)).await?;
-            let display_events =
-                DisplayEvents::from_events::<C, C>(&events, None, &metadata)?;
+            let display_events = DisplayEvents::from_events_with_registry::<C, C>(
+                &events,
+                Some(call_exec.transcoder()),
+                &registry,
+                &metadata,
+            )?;
+            let multisig_status = match call_exec.opts().multisig() {
+                Some(multisig) => {
+                    Some(multisig.status(call_exec.client(), &events).await?)
+                }
+                None => None,
+            };
 
-            let output = if self.output_json() {
-                display_events.to_json()?
+            if self.output_json() {
+                let output = display_events.to_json()?;
+                if let Some(status) = multisig_status {
+                    // Create a JSON object with the events and the multisig status.
+                    let json_object = serde_json::json!({
+                        "events": serde_json::from_str::<serde_json::Value>(&output)?,
+                        "multisig": status,
+                    });
+                    println!("{}", serde_json::to_string_pretty(&json_object)?);
+                } else {
+                    println!("{output}");
+                }
             } else {
-                display_events.display_events::<C>(
+                let output = display_events.display_events::<C>(
                     self.extrinsic_cli_opts.verbosity().unwrap(),
                     &token_metadata,
-                )?
-            };
-            println!("{output}");
+                )?;
+                println!("{output}");
+                if let Some(status) = multisig_status {
+                    display_multisig_status(&status);
+                }
+            }
         }
         Ok(())
     }
+
+    /// Display the result of a dry-run with the decoded return value `data`.
+    fn display_dry_run_result<Data, Balance>(
+        &self,
+        data: Data,
+        result: &ContractExecResult<Balance>,
+    ) -> Result<()>
+    where
+        Data: Display + Serialize,
+        Balance: Clone + Serialize,
+    {
+        let dry_run_result = CallDryRunResult {
+            reverted: result
+                .result
+                .as_ref()
+                .map(|ret_val| ret_val.did_revert())
+                .unwrap_or_default(),
+            data,
+            gas_consumed: result.gas_consumed,
+            gas_required: result.gas_required,
+            storage_deposit: result.storage_deposit.clone(),
+        };
+        if self.output_json() {
+            println!("{}", dry_run_result.to_json()?);
+        } else {
+            dry_run_result.print();
+            display_contract_exec_result_debug::<_, DEFAULT_KEY_COL_WIDTH, _>(result)?;
+            display_dry_run_result_warning("message");
+        };
+        Ok(())
+    }
 }
 
 /// A helper function to estimate the gas required for a contract call.
@@ -254,8 +407,8 @@ async fn pre_submit_dry_run_gas_estimate_call<C: Config + Environment, Signer>(
     skip_dry_run: bool,
 ) -> Result<Weight>
 where
-    Signer: subxt::tx::Signer<C> + Clone,
-    <C as Config>::AccountId: IntoVisitor + EncodeAsType,
+    Signer: TrySigner<C> + Clone,
+    <C as Config>::AccountId: IntoVisitor + EncodeAsType + Serialize,
     C::Balance: Debug + EncodeAsType,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
@@ -305,17 +458,17 @@ where
 
 /// Result of the contract call
 #[derive(serde::Serialize)]
-pub struct CallDryRunResult<Balance> {
+pub struct CallDryRunResult<Balance, Data = Value> {
     /// Was the operation reverted
     pub reverted: bool,
-    pub data: Value,
+    pub data: Data,
     pub gas_consumed: Weight,
     pub gas_required: Weight,
     /// Storage deposit after the operation
     pub storage_deposit: StorageDeposit<Balance>,
 }
 
-impl<Balance: Serialize> CallDryRunResult<Balance> {
+impl<Balance: Serialize, Data: Display + Serialize> CallDryRunResult<Balance, Data> {
     /// Returns a result in json format
     pub fn to_json(&self) -> Result<String> {
         Ok(serde_json::to_string_pretty(self)?)
@@ -0,0 +1,591 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Generates typed clients of a contract from its metadata.
+
+mod rust;
+mod typescript;
+
+use anyhow::{
+    Context,
+    Result,
+};
+use contract_extrinsics::{
+    CellKind,
+    ContractArtifacts,
+    ContractMessageTranscoder,
+    ContractStorageLayout,
+};
+use contract_transcode::ink_metadata::InkProject;
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    path::PathBuf,
+};
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Lang {
+    /// A Rust module built on `contract-transcode` and `contract-extrinsics`.
+    #[clap(name = "rust")]
+    Rust,
+    /// A TypeScript module built on `scale-ts`.
+    #[clap(name = "typescript")]
+    Typescript,
+}
+
+/// Generate a typed client of a contract from its metadata.
+#[derive(Debug, clap::Args)]
+#[clap(name = "codegen")]
+pub struct CodegenCommand {
+    /// The language of the generated client.
+    #[clap(long, value_enum)]
+    lang: Lang,
+    /// Path to the contract metadata: a `.json` metadata file or a `.contract` bundle.
+    #[clap(value_parser)]
+    metadata: PathBuf,
+    /// Write the client to the given file instead of stdout.
+    #[clap(long, short, value_parser)]
+    output: Option<PathBuf>,
+}
+
+impl CodegenCommand {
+    pub fn run(&self) -> Result<()> {
+        let artifacts =
+            ContractArtifacts::from_manifest_or_file(None, Some(&self.metadata))?;
+        let name = artifacts.metadata()?.contract.name;
+        let transcoder = artifacts.contract_transcoder()?;
+        let client = generate(self.lang, &name, &transcoder)?;
+        match self.output {
+            Some(ref path) => {
+                std::fs::write(path, client).with_context(|| {
+                    format!("Failed to write the client to {}", path.display())
+                })?
+            }
+            None => print!("{client}"),
+        }
+        Ok(())
+    }
+}
+
+/// Generate the client of the contract with the given name in the given language.
+pub fn generate(
+    lang: Lang,
+    name: &str,
+    transcoder: &ContractMessageTranscoder,
+) -> Result<String> {
+    match lang {
+        Lang::Rust => {
+            rust::generate(&Contract::new(name, transcoder, rust::RESERVED_NAMES)?)
+        }
+        Lang::Typescript => {
+            typescript::generate(&Contract::new(
+                name,
+                transcoder,
+                typescript::RESERVED_NAMES,
+            )?)
+        }
+    }
+}
+
+/// The contract a client is generated for, with unique names for its custom types,
+/// events and storage cells.
+struct Contract<'a> {
+    name: &'a str,
+    project: &'a InkProject,
+    /// The custom types used by the contract, each after the types it depends on.
+    types: Vec<u32>,
+    /// The position of each custom type in `types`.
+    positions: HashMap<u32, usize>,
+    type_names: HashMap<u32, String>,
+    /// The unique names of the events, in the order of the metadata.
+    event_names: Vec<String>,
+    storage: Vec<StorageCell>,
+}
+
+/// A storage cell of the contract.
+struct StorageCell {
+    /// A unique snake case name of the cell, derived from its path.
+    name: String,
+    /// The path of the cell in the storage layout, e.g. `Erc20::balances`.
+    path: String,
+    root_key: u32,
+    kind: CellKind,
+    /// The type of the values stored in the cell.
+    value_type_id: u32,
+}
+
+impl<'a> Contract<'a> {
+    /// Collect the custom types used by the constructors, messages, events and storage
+    /// of the contract, allocating unique names which don't clash with the `reserved`
+    /// names of the language.
+    fn new(
+        name: &'a str,
+        transcoder: &'a ContractMessageTranscoder,
+        reserved: &[&str],
+    ) -> Result<Self> {
+        let project = transcoder.metadata();
+        let registry = project.registry();
+        let spec = project.spec();
+
+        let storage = ContractStorageLayout::root_key_entries(transcoder)
+            .into_iter()
+            .map(|entry| {
+                let kind = CellKind::new(&entry, transcoder)?;
+                let value_type_id = match kind {
+                    CellKind::Packed => entry.type_id,
+                    _ => {
+                        registry
+                            .resolve(entry.type_id)
+                            .and_then(|ty| {
+                                ty.type_params
+                                    .iter()
+                                    .find(|param| param.name == "V")
+                                    .and_then(|param| param.ty)
+                            })
+                            .map(|ty| ty.id)
+                            .with_context(|| {
+                                format!(
+                                "Failed to find the value type of the storage cell `{}`",
+                                entry.path.join("::")
+                            )
+                            })?
+                    }
+                };
+                Ok((entry, kind, value_type_id))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut roots = Vec::new();
+        for constructor in spec.constructors() {
+            roots.extend(constructor.args().iter().map(|arg| arg.ty().ty().id));
+            roots.push(constructor.return_type().ret_type().ty().id);
+        }
+        for message in spec.messages() {
+            roots.extend(message.args().iter().map(|arg| arg.ty().ty().id));
+            roots.push(message.return_type().ret_type().ty().id);
+        }
+        for event in spec.events() {
+            roots.extend(event.args().iter().map(|arg| arg.ty().ty().id));
+        }
+        for (_, kind, value_type_id) in &storage {
+            if let CellKind::Mapping { key_type_id } = kind {
+                roots.push(*key_type_id);
+            }
+            roots.push(*value_type_id);
+        }
+        let mut visited = HashSet::new();
+        let mut types = Vec::new();
+        for type_id in roots {
+            collect_types(project, type_id, &mut visited, &mut types)?;
+        }
+
+        let mut names = Names::new(reserved);
+        let type_names = types
+            .iter()
+            .map(|type_id| {
+                let ty = registry
+                    .resolve(*type_id)
+                    .expect("type was resolved before");
+                let ident = ty.path.ident().unwrap_or_else(|| format!("Type{type_id}"));
+                (*type_id, names.unique(&ident))
+            })
+            .collect();
+        let event_names = spec
+            .events()
+            .iter()
+            .map(|event| names.unique(&pascal_case(event.label())))
+            .collect();
+        let mut cell_names = Names::new(&[]);
+        let storage = storage
+            .into_iter()
+            .map(|(entry, kind, value_type_id)| {
+                let fields = entry
+                    .path
+                    .iter()
+                    .skip(1)
+                    .filter(|segment| !segment.starts_with(char::is_uppercase))
+                    .map(|segment| segment.as_str())
+                    .collect::<Vec<_>>();
+                let name = if fields.is_empty() {
+                    "root".to_string()
+                } else {
+                    snake_case(&fields.join("_"))
+                };
+                StorageCell {
+                    name: cell_names.unique(&name),
+                    path: entry.path[1..].join("::"),
+                    root_key: entry.root_key,
+                    kind,
+                    value_type_id,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            name,
+            project,
+            positions: types.iter().enumerate().map(|(i, id)| (*id, i)).collect(),
+            types,
+            type_names,
+            event_names,
+            storage,
+        })
+    }
+
+    /// Returns the name of the custom type with the given id, if it is one.
+    fn type_name(&self, type_id: u32) -> Option<&str> {
+        self.type_names.get(&type_id).map(String::as_str)
+    }
+
+    /// Returns `true` if the custom type `type_id` is referenced by the custom type at
+    /// position `from` before it is defined, i.e. the types are recursive.
+    fn is_forward_ref(&self, from: Option<usize>, type_id: u32) -> bool {
+        match (from, self.positions.get(&type_id)) {
+            (Some(from), Some(to)) => *to >= from,
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` for the `Option` and `Result` types, which are generic in both
+/// languages and not generated.
+fn is_builtin(ty: &scale_info::Type<scale_info::form::PortableForm>) -> bool {
+    ty.path.segments == ["Option"] || ty.path.segments == ["Result"]
+}
+
+/// Collect the custom types reachable from `type_id`, each after its dependencies.
+fn collect_types(
+    project: &InkProject,
+    type_id: u32,
+    visited: &mut HashSet<u32>,
+    types: &mut Vec<u32>,
+) -> Result<()> {
+    use scale_info::TypeDef;
+
+    if !visited.insert(type_id) {
+        return Ok(())
+    }
+    let ty = project
+        .registry()
+        .resolve(type_id)
+        .with_context(|| format!("Failed to resolve type with id `{type_id}`"))?;
+    let children: Vec<u32> = match &ty.type_def {
+        TypeDef::Composite(composite) => {
+            composite.fields.iter().map(|field| field.ty.id).collect()
+        }
+        TypeDef::Variant(variant) => {
+            variant
+                .variants
+                .iter()
+                .flat_map(|variant| variant.fields.iter().map(|field| field.ty.id))
+                .collect()
+        }
+        TypeDef::Sequence(sequence) => vec![sequence.type_param.id],
+        TypeDef::Array(array) => vec![array.type_param.id],
+        TypeDef::Tuple(tuple) => tuple.fields.iter().map(|field| field.id).collect(),
+        TypeDef::Compact(compact) => vec![compact.type_param.id],
+        TypeDef::Primitive(_) => Vec::new(),
+        TypeDef::BitSequence(_) => {
+            anyhow::bail!("Bit sequences are not supported by the code generator")
+        }
+    };
+    for child in children {
+        collect_types(project, child, visited, types)?;
+    }
+    if matches!(ty.type_def, TypeDef::Composite(_) | TypeDef::Variant(_))
+        && !is_builtin(ty)
+    {
+        types.push(type_id)
+    }
+    Ok(())
+}
+
+/// Allocates unique names, appending a number to names which are taken.
+struct Names {
+    taken: HashSet<String>,
+}
+
+impl Names {
+    fn new(reserved: &[&str]) -> Self {
+        Self {
+            taken: reserved.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn unique(&mut self, name: &str) -> String {
+        let name = (1..)
+            .map(|n| {
+                if n == 1 {
+                    name.to_string()
+                } else {
+                    format!("{name}{n}")
+                }
+            })
+            .find(|name| !self.taken.contains(name))
+            .expect("there are infinitely many names");
+        self.taken.insert(name.clone());
+        name
+    }
+}
+
+/// Convert a label, e.g. `PSP22::transferFrom`, to snake case: `psp22_transfer_from`.
+fn snake_case(label: &str) -> String {
+    let mut snake = String::new();
+    let mut prev: Option<char> = None;
+    for c in label.chars() {
+        if c.is_alphanumeric() {
+            if c.is_uppercase()
+                && prev.is_some_and(|p| p.is_lowercase() || p.is_numeric())
+            {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if !snake.is_empty() && !snake.ends_with('_') {
+            snake.push('_');
+        }
+        prev = Some(c);
+    }
+    snake.trim_end_matches('_').to_string()
+}
+
+/// Convert a label to camel case, e.g. `PSP22::transfer_from` to `psp22TransferFrom`.
+fn camel_case(label: &str) -> String {
+    let pascal = pascal_case(label);
+    let mut chars = pascal.chars();
+    chars
+        .next()
+        .map(|first| first.to_lowercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Convert a label to pascal case, e.g. `transfer_from` to `TransferFrom`.
+fn pascal_case(label: &str) -> String {
+    snake_case(label)
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[allow(clippy::extra_unused_lifetimes)]
+    #[ink::contract]
+    pub mod codegen {
+        use ink::storage::Mapping;
+
+        #[derive(Debug, Clone, PartialEq)]
+        #[ink::scale_derive(Encode, Decode, TypeInfo)]
+        pub enum Error {
+            InsufficientBalance,
+            Custom(String),
+        }
+
+        #[ink(event)]
+        pub struct Transferred {
+            #[ink(topic)]
+            from: Option<AccountId>,
+            value: Balance,
+        }
+
+        #[ink(event)]
+        pub struct Call {
+            value: Balance,
+        }
+
+        #[ink(storage)]
+        pub struct Token {
+            total_supply: Balance,
+            balances: Mapping<AccountId, Balance>,
+        }
+
+        impl Token {
+            /// Creates a token with the given supply.
+            #[ink(constructor, payable)]
+            pub fn new(total_supply: Balance) -> Self {
+                Self {
+                    total_supply,
+                    balances: Mapping::new(),
+                }
+            }
+
+            /// Transfers `value` to the account `to`.
+            #[ink(message)]
+            pub fn transfer(
+                &mut self,
+                to: AccountId,
+                value: Balance,
+            ) -> Result<(), Error> {
+                let _ = (to, value);
+                Ok(())
+            }
+
+            #[ink(message)]
+            pub fn balance_of(&self, owner: AccountId) -> Balance {
+                self.balances.get(owner).unwrap_or_default()
+            }
+
+            #[ink(message)]
+            pub fn total_supply(&self) -> Balance {
+                self.total_supply
+            }
+        }
+    }
+
+    fn transcoder() -> ContractMessageTranscoder {
+        extern "Rust" {
+            fn __ink_generate_metadata() -> InkProject;
+        }
+
+        ContractMessageTranscoder::new(unsafe { __ink_generate_metadata() })
+    }
+
+    #[test]
+    fn convert_label_case() {
+        assert_eq!(snake_case("PSP22::transferFrom"), "psp22_transfer_from");
+        assert_eq!(snake_case("balance_of"), "balance_of");
+        assert_eq!(camel_case("PSP22::transfer_from"), "psp22TransferFrom");
+        assert_eq!(pascal_case("transfer_from"), "TransferFrom");
+    }
+
+    #[test]
+    fn unique_names() {
+        let mut names = Names::new(&["Call"]);
+
+        assert_eq!(names.unique("Call"), "Call2");
+        assert_eq!(names.unique("Error"), "Error");
+        assert_eq!(names.unique("Error"), "Error2");
+    }
+
+    #[test]
+    fn collect_contract_types_and_storage() -> Result<()> {
+        let transcoder = transcoder();
+        let contract = Contract::new("token", &transcoder, &[])?;
+
+        let type_names = contract
+            .types
+            .iter()
+            .map(|type_id| contract.type_name(*type_id).unwrap())
+            .collect::<Vec<_>>();
+        for name in ["Error", "LangError", "AccountId", "Token"] {
+            assert!(type_names.contains(&name), "{name} in {type_names:?}");
+        }
+        assert!(!type_names.contains(&"Option"));
+        assert_eq!(contract.event_names, ["Call", "Transferred"]);
+        let cells = contract
+            .storage
+            .iter()
+            .map(|cell| (cell.name.as_str(), cell.path.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(cells, [("root", ""), ("balances", "Token::balances")]);
+        Ok(())
+    }
+
+    #[test]
+    fn generate_rust_client() -> Result<()> {
+        let client = generate(Lang::Rust, "token", &transcoder())?;
+
+        for expected in [
+            "pub enum Error {",
+            "Custom(String),",
+            "pub fn transfer(to: types::AccountId, value: u128) -> Call<Result<Result<(), types::Error>, types::LangError>> {",
+            "pub fn new(total_supply: u128) -> Call<",
+            "pub fn balances(key: &types::AccountId) -> StorageKey<u128> {",
+            "Transferred::SIGNATURE_TOPIC => {",
+            "pub struct Call2 {",
+        ] {
+            assert!(client.contains(expected), "{expected} in\n{client}");
+        }
+        Ok(())
+    }
+
+    /// Compiles the generated client in a crate depending on `contract-extrinsics` and
+    /// `contract-transcode`, which takes a while.
+    #[cfg(feature = "test-ci-only")]
+    #[test]
+    fn generated_rust_client_compiles() -> Result<()> {
+        let manifest_dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"));
+        let crates_dir = manifest_dir.parent().expect("crates directory exists");
+        let workspace_dir = crates_dir.parent().expect("workspace directory exists");
+        let client_dir = tempfile::tempdir()?;
+        std::fs::create_dir(client_dir.path().join("src"))?;
+        std::fs::write(
+            client_dir.path().join("Cargo.toml"),
+            format!(
+                "[package]\n\
+                name = \"token-client\"\n\
+                version = \"0.1.0\"\n\
+                edition = \"2021\"\n\
+                \n\
+                [dependencies]\n\
+                contract-extrinsics = {{ path = {:?} }}\n\
+                contract-transcode = {{ path = {:?} }}\n\
+                \n\
+                [workspace]\n",
+                crates_dir.join("extrinsics"),
+                crates_dir.join("transcode"),
+            ),
+        )?;
+        std::fs::write(
+            client_dir.path().join("src").join("lib.rs"),
+            generate(Lang::Rust, "token", &transcoder())?,
+        )?;
+        let lockfile = workspace_dir.join("Cargo.lock");
+        if lockfile.exists() {
+            std::fs::copy(lockfile, client_dir.path().join("Cargo.lock"))?;
+        }
+
+        // Share the artifacts of the dependencies between the test runs.
+        let target_dir = workspace_dir.join("target").join("codegen-client");
+        let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+        let output = std::process::Command::new(cargo)
+            .args(["check", "--quiet"])
+            .env("CARGO_TARGET_DIR", target_dir)
+            .current_dir(client_dir.path())
+            .output()?;
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn generate_typescript_client() -> Result<()> {
+        let client = generate(Lang::Typescript, "token", &transcoder())?;
+
+        for expected in [
+            "export const Error = Enum({ InsufficientBalance: _void, Custom: str }, [0, 1]);",
+            "transfer(to: AccountId, value: bigint): Call<",
+            "balanceOf(owner: AccountId): Call<",
+            "balances(key: AccountId): StorageKey<bigint> {",
+            "return { name: \"Transferred\", value: Transferred.dec(data) };",
+        ] {
+            assert!(client.contains(expected), "{expected} in\n{client}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,657 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A Rust client: the custom types derive `Encode` and `Decode` of
+//! `contract_transcode::scale`, and the storage keys are computed with
+//! `contract_extrinsics`.
+
+use super::{
+    is_builtin,
+    snake_case,
+    CellKind,
+    Contract,
+    Names,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use scale_info::{
+    form::PortableForm,
+    Field,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::fmt::Write;
+
+/// Names of the generated client and the prelude which can't be used for the custom
+/// types and events, because they would shadow the names used by the generated code.
+pub const RESERVED_NAMES: &[&str] = &[
+    "Box",
+    "Call",
+    "Decode",
+    "Encode",
+    "Event",
+    "Option",
+    "PhantomData",
+    "Result",
+    "StorageKey",
+    "String",
+    "Vec",
+];
+
+const DERIVES: &str =
+    "#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]\n\
+    #[codec(crate = ::contract_transcode::scale)]";
+
+const HEADER: &str = r#"#![allow(dead_code)]
+
+use ::contract_transcode::scale::{
+    self,
+    Decode,
+    Encode,
+};
+use ::core::marker::PhantomData;
+
+/// The encoded call of a constructor or message, with the type `R` of its return value.
+pub struct Call<R> {
+    label: &'static str,
+    data: Vec<u8>,
+    _return: PhantomData<fn() -> R>,
+}
+
+impl<R: Decode> Call<R> {
+    fn new(label: &'static str, data: Vec<u8>) -> Self {
+        Self {
+            label,
+            data,
+            _return: PhantomData,
+        }
+    }
+
+    /// The label of the constructor or message.
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// The call data: the selector followed by the SCALE encoded arguments.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Decode the return value of the call.
+    pub fn decode_return(&self, mut data: &[u8]) -> Result<R, scale::Error> {
+        R::decode(&mut data)
+    }
+}
+
+/// The key of a storage entry in the contract child trie, with the type `V` of its
+/// value.
+pub struct StorageKey<V> {
+    key: Vec<u8>,
+    _value: PhantomData<fn() -> V>,
+}
+
+impl<V: Decode> StorageKey<V> {
+    fn new(root_key: u32, key: &[u8]) -> Self {
+        Self {
+            key: ::contract_extrinsics::StorageEntryKey::child_trie_key(root_key, key),
+            _value: PhantomData,
+        }
+    }
+
+    /// The key in the contract child trie.
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Decode the value fetched for the key.
+    pub fn decode_value(&self, mut data: &[u8]) -> Result<V, scale::Error> {
+        V::decode(&mut data)
+    }
+}
+"#;
+
+/// Generate a Rust module with the client of the contract.
+pub fn generate(contract: &Contract) -> Result<String> {
+    let mut out = String::new();
+    writeln!(
+        out,
+        "//! Client of the `{}` contract, generated by `cargo contract codegen`.",
+        contract.name
+    )?;
+    writeln!(
+        out,
+        "//!\n\
+        //! Submit the calls with `contract_extrinsics`, e.g.\n\
+        //!\n\
+        //! ```ignore\n\
+        //! let call = messages::flip();\n\
+        //! let call_exec = CallCommandBuilder::new(contract, call.label(), extrinsic_opts)\n\
+        //!     .call_data(call.data().to_vec())\n\
+        //!     .done()\n\
+        //!     .await?;\n\
+        //! ```\n"
+    )?;
+    out.push_str(HEADER);
+
+    writeln!(out)?;
+    writeln!(
+        out,
+        "/// The types used by the constructors, messages, events and storage of the contract."
+    )?;
+    writeln!(out, "pub mod types {{")?;
+    writeln!(out, "    use super::scale;")?;
+    for (position, type_id) in contract.types.iter().enumerate() {
+        writeln!(out)?;
+        push_indented(&mut out, &type_definition(contract, *type_id, position)?);
+    }
+    writeln!(out, "}}")?;
+
+    let spec = contract.project.spec();
+    writeln!(out)?;
+    writeln!(out, "/// The constructors of the contract.")?;
+    writeln!(out, "pub mod constructors {{")?;
+    writeln!(out, "    use super::*;")?;
+    let mut names = Names::new(&[]);
+    for constructor in spec.constructors() {
+        let mut notes = vec![selector_note(constructor.selector().to_bytes())];
+        if *constructor.payable() {
+            notes.push("payable".to_string())
+        }
+        let call = call_function(
+            contract,
+            &names.unique(&rust_ident(&snake_case(constructor.label()))),
+            constructor.label(),
+            constructor.selector().to_bytes(),
+            constructor.args(),
+            constructor.return_type().ret_type().ty().id,
+            &docs(constructor.docs(), &notes),
+        )?;
+        writeln!(out)?;
+        push_indented(&mut out, &call);
+    }
+    writeln!(out, "}}")?;
+
+    writeln!(out)?;
+    writeln!(out, "/// The messages of the contract.")?;
+    writeln!(out, "pub mod messages {{")?;
+    writeln!(out, "    use super::*;")?;
+    let mut names = Names::new(&[]);
+    for message in spec.messages() {
+        let mut notes = vec![selector_note(message.selector().to_bytes())];
+        notes.push(
+            if message.mutates() {
+                "mutates the contract state".to_string()
+            } else {
+                "read-only".to_string()
+            },
+        );
+        if message.payable() {
+            notes.push("payable".to_string())
+        }
+        let call = call_function(
+            contract,
+            &names.unique(&rust_ident(&snake_case(message.label()))),
+            message.label(),
+            message.selector().to_bytes(),
+            message.args(),
+            message.return_type().ret_type().ty().id,
+            &docs(message.docs(), &notes),
+        )?;
+        writeln!(out)?;
+        push_indented(&mut out, &call);
+    }
+    writeln!(out, "}}")?;
+
+    if !spec.events().is_empty() {
+        writeln!(out)?;
+        push_events(&mut out, contract)?;
+    }
+    writeln!(out)?;
+    push_storage(&mut out, contract)?;
+    Ok(out)
+}
+
+/// The definition of the custom type with the given id at `position` in the types.
+fn type_definition(contract: &Contract, type_id: u32, position: usize) -> Result<String> {
+    let ty = contract
+        .project
+        .registry()
+        .resolve(type_id)
+        .context("type was collected")?;
+    let name = contract.type_name(type_id).context("type was collected")?;
+    let mut out = docs(&ty.docs, &[]);
+    writeln!(out, "{DERIVES}")?;
+    match &ty.type_def {
+        TypeDef::Composite(composite) => {
+            let fields = fields(contract, &composite.fields, "", Some(position), "pub ")?;
+            let semicolon = if fields.starts_with(" {") { "" } else { ";" };
+            writeln!(out, "pub struct {name}{fields}{semicolon}")?;
+        }
+        TypeDef::Variant(variant) => {
+            writeln!(out, "pub enum {name} {{")?;
+            for variant in &variant.variants {
+                out.push_str(&indent(&docs(&variant.docs, &[])));
+                writeln!(out, "    #[codec(index = {})]", variant.index)?;
+                let fields = fields(contract, &variant.fields, "", Some(position), "")?;
+                let fields = indent(&fields);
+                let fields = fields.trim();
+                let separator = if fields.starts_with('{') { " " } else { "" };
+                writeln!(out, "    {}{separator}{fields},", variant.name)?;
+            }
+            writeln!(out, "}}")?;
+        }
+        _ => unreachable!("only composites and variants are custom types"),
+    }
+    Ok(out)
+}
+
+/// The fields of a struct or an enum variant: ` { a: A }`, `(A, B)` or nothing.
+fn fields(
+    contract: &Contract,
+    fields: &[Field<PortableForm>],
+    prefix: &str,
+    from: Option<usize>,
+    visibility: &str,
+) -> Result<String> {
+    if fields.is_empty() {
+        return Ok(String::new())
+    }
+    if fields.iter().all(|field| field.name.is_some()) {
+        let mut out = " {\n".to_string();
+        for field in fields {
+            let name = field.name.as_deref().expect("all fields are named");
+            out.push_str(&indent(&docs(&field.docs, &[])));
+            writeln!(
+                out,
+                "    {visibility}{}: {},",
+                rust_ident(name),
+                rust_type(contract, field.ty.id, prefix, from)?
+            )?;
+        }
+        out.push('}');
+        Ok(out)
+    } else {
+        let types = fields
+            .iter()
+            .map(|field| {
+                Ok(format!(
+                    "{visibility}{}",
+                    rust_type(contract, field.ty.id, prefix, from)?
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(format!("({})", types.join(", ")))
+    }
+}
+
+/// The function returning the encoded call of a constructor or message.
+fn call_function(
+    contract: &Contract,
+    name: &str,
+    label: &str,
+    selector: &[u8],
+    args: &[contract_transcode::ink_metadata::MessageParamSpec<PortableForm>],
+    return_type_id: u32,
+    docs: &str,
+) -> Result<String> {
+    let mut names = Names::new(&["call_data"]);
+    let args = args
+        .iter()
+        .map(|arg| {
+            Ok((
+                names.unique(&rust_ident(arg.label())),
+                rust_type(contract, arg.ty().ty().id, "types::", None)?,
+            ))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut out = docs.to_string();
+    writeln!(
+        out,
+        "pub fn {name}({}) -> Call<{}> {{",
+        args.iter()
+            .map(|(name, ty)| format!("{name}: {ty}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+        rust_type(contract, return_type_id, "types::", None)?
+    )?;
+    let selector = selector
+        .iter()
+        .map(|b| format!("{b:#04x}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if args.is_empty() {
+        writeln!(out, "    let call_data = vec![{selector}];")?;
+    } else {
+        writeln!(out, "    let mut call_data = vec![{selector}];")?;
+        for (name, _) in &args {
+            writeln!(out, "    {name}.encode_to(&mut call_data);")?;
+        }
+    }
+    writeln!(out, "    Call::new({label:?}, call_data)")?;
+    writeln!(out, "}}")?;
+    Ok(out)
+}
+
+/// The event types, with their signature topics, and the `Event` enum to decode them.
+fn push_events(out: &mut String, contract: &Contract) -> Result<()> {
+    let events = contract.project.spec().events();
+    writeln!(out, "/// The events of the contract.")?;
+    writeln!(out, "pub mod events {{")?;
+    writeln!(out, "    use super::*;")?;
+    for (event, name) in events.iter().zip(&contract.event_names) {
+        let mut definition = docs(event.docs(), &[]);
+        writeln!(definition, "{DERIVES}")?;
+        if event.args().is_empty() {
+            writeln!(definition, "pub struct {name};")?;
+        } else {
+            writeln!(definition, "pub struct {name} {{")?;
+            for arg in event.args() {
+                let notes = if arg.indexed() {
+                    vec!["A topic of the event.".to_string()]
+                } else {
+                    Vec::new()
+                };
+                definition.push_str(&indent(&docs(arg.docs(), &notes)));
+                writeln!(
+                    definition,
+                    "    pub {}: {},",
+                    rust_ident(arg.label()),
+                    rust_type(contract, arg.ty().ty().id, "types::", None)?
+                )?;
+            }
+            writeln!(definition, "}}")?;
+        }
+        if let Some(topic) = event.signature_topic() {
+            writeln!(definition)?;
+            writeln!(definition, "impl {name} {{")?;
+            writeln!(definition, "    /// The signature topic of the event.")?;
+            writeln!(
+                definition,
+                "    pub const SIGNATURE_TOPIC: [u8; 32] = {:?};",
+                topic.as_bytes()
+            )?;
+            writeln!(definition, "}}")?;
+        }
+        writeln!(out)?;
+        push_indented(out, &definition);
+    }
+
+    let mut event = "/// An event emitted by the contract.\n\
+        #[derive(Debug, Clone, PartialEq, Eq)]\n\
+        pub enum Event {\n"
+        .to_string();
+    for name in &contract.event_names {
+        writeln!(event, "    {name}({name}),")?;
+    }
+    writeln!(event, "}}")?;
+    writeln!(event)?;
+    writeln!(event, "impl Event {{")?;
+    writeln!(
+        event,
+        "    /// Decode an event emitted by the contract from the topics and the data of the\n    \
+        /// `ContractEmitted` event. Returns `None` if the first topic is none of the\n    \
+        /// signature topics, e.g. for anonymous events."
+    )?;
+    writeln!(
+        event,
+        "    pub fn decode(topics: &[[u8; 32]], data: &[u8]) -> Result<Option<Self>, scale::Error> {{"
+    )?;
+    writeln!(
+        event,
+        "        let Some(signature_topic) = topics.first() else {{"
+    )?;
+    writeln!(event, "            return Ok(None)")?;
+    writeln!(event, "        }};")?;
+    writeln!(event, "        match *signature_topic {{")?;
+    for (event_spec, name) in events.iter().zip(&contract.event_names) {
+        if event_spec.signature_topic().is_some() {
+            writeln!(event, "            {name}::SIGNATURE_TOPIC => {{")?;
+            writeln!(
+                event,
+                "                {name}::decode(&mut &data[..]).map(|event| Some(Self::{name}(event)))"
+            )?;
+            writeln!(event, "            }}")?;
+        }
+    }
+    writeln!(event, "            _ => Ok(None),")?;
+    writeln!(event, "        }}")?;
+    writeln!(event, "    }}")?;
+    writeln!(event, "}}")?;
+    writeln!(out)?;
+    push_indented(out, &event);
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+/// The functions returning the storage keys of the storage cells.
+fn push_storage(out: &mut String, contract: &Contract) -> Result<()> {
+    writeln!(out, "/// The storage cells of the contract.")?;
+    writeln!(out, "pub mod storage {{")?;
+    writeln!(out, "    use super::*;")?;
+    for cell in &contract.storage {
+        let value = rust_type(contract, cell.value_type_id, "types::", None)?;
+        let root_key = format!("{:#010x}", cell.root_key);
+        let path = if cell.path.is_empty() {
+            "the root".to_string()
+        } else {
+            format!("`{}`", cell.path)
+        };
+        let (docs, param, key) = match cell.kind {
+            CellKind::Mapping { key_type_id } => {
+                (
+                    format!("The entry with the given key of the `Mapping` at {path}."),
+                    format!(
+                        "key: &{}",
+                        rust_type(contract, key_type_id, "types::", None)?
+                    ),
+                    "&key.encode()",
+                )
+            }
+            CellKind::StorageVec => {
+                (
+                    format!(
+                        "The element at the given index of the `StorageVec` at {path}."
+                    ),
+                    "index: u32".to_string(),
+                    "&index.encode()",
+                )
+            }
+            CellKind::Lazy => {
+                (format!("The `Lazy` value at {path}."), String::new(), "&[]")
+            }
+            CellKind::Packed => {
+                (
+                    format!("The values stored together at {path} of the storage."),
+                    String::new(),
+                    "&[]",
+                )
+            }
+        };
+        writeln!(out)?;
+        writeln!(out, "    /// {docs}")?;
+        writeln!(
+            out,
+            "    pub fn {}({param}) -> StorageKey<{value}> {{",
+            rust_ident(&cell.name)
+        )?;
+        writeln!(out, "        StorageKey::new({root_key}, {key})")?;
+        writeln!(out, "    }}")?;
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+/// The Rust type of the type with the given id. Custom types are referenced with the
+/// `prefix`, and boxed if they are referenced from the custom type at position `from`
+/// before they are defined, i.e. the types are recursive.
+fn rust_type(
+    contract: &Contract,
+    type_id: u32,
+    prefix: &str,
+    from: Option<usize>,
+) -> Result<String> {
+    let ty = contract
+        .project
+        .registry()
+        .resolve(type_id)
+        .with_context(|| format!("Failed to resolve type with id `{type_id}`"))?;
+    if let Some(name) = contract.type_name(type_id) {
+        return Ok(if contract.is_forward_ref(from, type_id) {
+            format!("Box<{prefix}{name}>")
+        } else {
+            format!("{prefix}{name}")
+        })
+    }
+    let param = |id| rust_type(contract, id, prefix, from);
+    Ok(match &ty.type_def {
+        TypeDef::Variant(variant) if is_builtin(ty) => {
+            let field = |name: &str| {
+                variant
+                    .variants
+                    .iter()
+                    .find(|variant| variant.name == name)
+                    .and_then(|variant| variant.fields.first())
+                    .map(|field| field.ty.id)
+                    .with_context(|| format!("Missing `{name}` variant of {}", ty.path))
+            };
+            if ty.path.segments == ["Option"] {
+                format!("Option<{}>", param(field("Some")?)?)
+            } else {
+                format!(
+                    "Result<{}, {}>",
+                    param(field("Ok")?)?,
+                    param(field("Err")?)?
+                )
+            }
+        }
+        TypeDef::Primitive(primitive) => primitive_type(primitive).to_string(),
+        TypeDef::Array(array) => {
+            format!("[{}; {}]", param(array.type_param.id)?, array.len)
+        }
+        TypeDef::Sequence(sequence) => format!("Vec<{}>", param(sequence.type_param.id)?),
+        TypeDef::Tuple(tuple) => {
+            let fields = tuple
+                .fields
+                .iter()
+                .map(|field| param(field.id))
+                .collect::<Result<Vec<_>>>()?;
+            match fields.as_slice() {
+                [field] => format!("({field},)"),
+                fields => format!("({})", fields.join(", ")),
+            }
+        }
+        TypeDef::Compact(compact) => {
+            format!("scale::Compact<{}>", param(compact.type_param.id)?)
+        }
+        TypeDef::Composite(_) | TypeDef::Variant(_) | TypeDef::BitSequence(_) => {
+            anyhow::bail!("Unsupported type {}", ty.path)
+        }
+    })
+}
+
+fn primitive_type(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        TypeDefPrimitive::Char => "char",
+        TypeDefPrimitive::Str => "String",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        // 256 bit integers are little endian bytes, as they are SCALE encoded
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => "[u8; 32]",
+    }
+}
+
+/// The doc comment with the given lines, followed by the `notes` as a paragraph.
+fn docs(lines: &[String], notes: &[String]) -> String {
+    let mut out = String::new();
+    for line in lines {
+        push_doc_line(&mut out, line);
+    }
+    if !notes.is_empty() {
+        if !lines.is_empty() {
+            out.push_str("///\n");
+        }
+        let notes = notes.join(", ");
+        let mut chars = notes.chars();
+        let first = chars.next().map(|c| c.to_uppercase().to_string());
+        let mut note = first.unwrap_or_default() + chars.as_str();
+        if !note.ends_with('.') {
+            note.push('.');
+        }
+        push_doc_line(&mut out, &note);
+    }
+    out
+}
+
+fn push_doc_line(out: &mut String, line: &str) {
+    let line = line.trim_end();
+    if line.is_empty() {
+        out.push_str("///\n");
+    } else if line.starts_with(' ') {
+        out.push_str(&format!("///{line}\n"));
+    } else {
+        out.push_str(&format!("/// {line}\n"));
+    }
+}
+
+fn selector_note(selector: &[u8]) -> String {
+    format!("selector `0x{}`", hex::encode(selector))
+}
+
+/// Indent the non-empty lines of `code` by four spaces.
+fn indent(code: &str) -> String {
+    code.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("    {line}\n")
+            }
+        })
+        .collect()
+}
+
+fn push_indented(out: &mut String, code: &str) {
+    out.push_str(&indent(code))
+}
+
+/// Escape a Rust keyword as a raw identifier, or with a trailing `_` for the keywords
+/// which can't be raw identifiers.
+fn rust_ident(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const",
+        "continue", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for",
+        "if", "impl", "in", "let", "loop", "macro", "match", "mod", "move", "mut",
+        "override", "priv", "pub", "ref", "return", "static", "struct", "trait", "true",
+        "try", "type", "typeof", "unsafe", "unsized", "use", "virtual", "where", "while",
+        "yield",
+    ];
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{name}_"),
+        name if KEYWORDS.contains(&name) => format!("r#{name}"),
+        name => name.to_string(),
+    }
+}
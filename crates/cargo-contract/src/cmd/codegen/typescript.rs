@@ -0,0 +1,797 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! A TypeScript client: each custom type is a `scale-ts` codec together with the type
+//! of its values, and the storage keys are hashed with `@noble/hashes`.
+
+use super::{
+    camel_case,
+    is_builtin,
+    CellKind,
+    Contract,
+    Names,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use contract_transcode::ink_metadata::MessageParamSpec;
+use scale_info::{
+    form::PortableForm,
+    Field,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    fmt::Write,
+};
+
+/// Names of the module scope which can't be used for the custom types and events.
+pub const RESERVED_NAMES: &[&str] = &[
+    "Bytes",
+    "Call",
+    "Codec",
+    "CodecType",
+    "Enum",
+    "Event",
+    "Option",
+    "Result",
+    "StorageKey",
+    "Struct",
+    "Tuple",
+    "Uint8Array",
+    "Vector",
+    "blake2b",
+    "call",
+    "concat",
+    "constructors",
+    "createCodec",
+    "decodeEvent",
+    "lazy",
+    "messages",
+    "storage",
+    "storageKey",
+];
+
+const HELPERS: &str = r#"/** The encoded call of a constructor or message, with the type `R` of its return value. */
+export interface Call<R> {
+  /** The label of the constructor or message. */
+  readonly label: string;
+  /** The call data: the selector followed by the SCALE encoded arguments. */
+  readonly data: Uint8Array;
+  /** Decode the return value of the call. */
+  decodeReturn(data: Uint8Array): R;
+}
+
+/** The key of a storage entry in the contract child trie, with the type `V` of its value. */
+export interface StorageKey<V> {
+  /** The key in the contract child trie. */
+  readonly key: Uint8Array;
+  /** Decode the value fetched for the key. */
+  decodeValue(data: Uint8Array): V;
+}
+
+function concat(parts: Uint8Array[]): Uint8Array {
+  const bytes = new Uint8Array(parts.reduce((len, part) => len + part.length, 0));
+  let offset = 0;
+  for (const part of parts) {
+    bytes.set(part, offset);
+    offset += part.length;
+  }
+  return bytes;
+}
+
+function call<R>(label: string, selector: number[], args: Uint8Array[], ret: Codec<R>): Call<R> {
+  return {
+    label,
+    data: concat([new Uint8Array(selector), ...args]),
+    decodeReturn: (data) => ret.dec(data),
+  };
+}
+
+function storageKey<V>(rootKey: number, key: Uint8Array, value: Codec<V>): StorageKey<V> {
+  const raw = concat([u32.enc(rootKey), key]);
+  return {
+    key: concat([blake2b(raw, { dkLen: 16 }), raw]),
+    decodeValue: (data) => value.dec(data),
+  };
+}
+"#;
+
+const LAZY: &str = r#"
+function lazy<T>(codec: () => Codec<T>): Codec<T> {
+  return createCodec<T>((value) => codec().enc(value), (data) => codec().dec(data));
+}
+"#;
+
+/// Generate a TypeScript module with the client of the contract.
+pub fn generate(contract: &Contract) -> Result<String> {
+    Generator {
+        contract,
+        imports: RefCell::new(["u32"].into()),
+    }
+    .generate()
+}
+
+struct Generator<'a> {
+    contract: &'a Contract<'a>,
+    /// The codecs imported from `scale-ts`.
+    imports: RefCell<BTreeSet<&'static str>>,
+}
+
+impl<'a> Generator<'a> {
+    fn generate(&self) -> Result<String> {
+        let contract = self.contract;
+        let spec = contract.project.spec();
+        let mut body = String::new();
+
+        for (position, type_id) in contract.types.iter().enumerate() {
+            let ty = contract
+                .project
+                .registry()
+                .resolve(*type_id)
+                .context("type was collected")?;
+            let name = contract.type_name(*type_id).context("type was collected")?;
+            let codec = match &ty.type_def {
+                TypeDef::Composite(composite) => {
+                    self.fields_codec(&composite.fields, Some(position))?
+                }
+                TypeDef::Variant(variant) => {
+                    let variants = variant
+                        .variants
+                        .iter()
+                        .map(|variant| {
+                            Ok(format!(
+                                "{}: {}",
+                                ts_key(&variant.name),
+                                self.fields_codec(&variant.fields, Some(position))?
+                            ))
+                        })
+                        .collect::<Result<Vec<_>>>()?;
+                    let indexes = variant
+                        .variants
+                        .iter()
+                        .map(|variant| variant.index.to_string())
+                        .collect::<Vec<_>>();
+                    self.import("Enum");
+                    format!(
+                        "Enum({{ {} }}, [{}])",
+                        variants.join(", "),
+                        indexes.join(", ")
+                    )
+                }
+                _ => unreachable!("only composites and variants are custom types"),
+            };
+            writeln!(body)?;
+            push_value_type(&mut body, &jsdoc(&ty.docs, &[], ""), name, &codec)?;
+        }
+
+        writeln!(body)?;
+        writeln!(body, "/** The constructors of the contract. */")?;
+        writeln!(body, "export const constructors = {{")?;
+        let mut names = Names::new(&[]);
+        for constructor in spec.constructors() {
+            let mut notes = vec![selector_note(constructor.selector().to_bytes())];
+            if *constructor.payable() {
+                notes.push("payable".to_string())
+            }
+            body.push_str(&self.call_method(
+                &names.unique(&camel_case(constructor.label())),
+                constructor.label(),
+                constructor.selector().to_bytes(),
+                constructor.args(),
+                constructor.return_type().ret_type().ty().id,
+                &jsdoc(constructor.docs(), &notes, "  "),
+            )?);
+        }
+        writeln!(body, "}};")?;
+
+        writeln!(body)?;
+        writeln!(body, "/** The messages of the contract. */")?;
+        writeln!(body, "export const messages = {{")?;
+        let mut names = Names::new(&[]);
+        for message in spec.messages() {
+            let mut notes = vec![selector_note(message.selector().to_bytes())];
+            notes.push(
+                if message.mutates() {
+                    "mutates the contract state".to_string()
+                } else {
+                    "read-only".to_string()
+                },
+            );
+            if message.payable() {
+                notes.push("payable".to_string())
+            }
+            body.push_str(&self.call_method(
+                &names.unique(&camel_case(message.label())),
+                message.label(),
+                message.selector().to_bytes(),
+                message.args(),
+                message.return_type().ret_type().ty().id,
+                &jsdoc(message.docs(), &notes, "  "),
+            )?);
+        }
+        writeln!(body, "}};")?;
+
+        if !spec.events().is_empty() {
+            self.push_events(&mut body)?;
+        }
+        self.push_storage(&mut body)?;
+
+        let mut out = String::new();
+        writeln!(
+            out,
+            "// Client of the `{}` contract, generated by `cargo contract codegen`.",
+            contract.name
+        )?;
+        writeln!(out)?;
+        let imports = self.imports.borrow();
+        writeln!(
+            out,
+            "import {{ {} }} from \"scale-ts\";",
+            imports.iter().copied().collect::<Vec<_>>().join(", ")
+        )?;
+        writeln!(out, "import type {{ Codec, CodecType }} from \"scale-ts\";")?;
+        writeln!(out, "import {{ blake2b }} from \"@noble/hashes/blake2b\";")?;
+        writeln!(out)?;
+        out.push_str(HELPERS);
+        if imports.contains("createCodec") {
+            out.push_str(LAZY);
+        }
+        out.push_str(&body);
+        Ok(out)
+    }
+
+    /// The method of `constructors` or `messages` returning the encoded call.
+    fn call_method(
+        &self,
+        name: &str,
+        label: &str,
+        selector: &[u8],
+        args: &[MessageParamSpec<PortableForm>],
+        return_type_id: u32,
+        docs: &str,
+    ) -> Result<String> {
+        let mut names = Names::new(&[]);
+        let args = args
+            .iter()
+            .map(|arg| {
+                let type_id = arg.ty().ty().id;
+                Ok((
+                    names.unique(&ts_ident(&camel_case(arg.label()))),
+                    self.ts_type(type_id)?,
+                    self.codec(type_id, None)?,
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let params = args
+            .iter()
+            .map(|(name, ty, _)| format!("{name}: {ty}"))
+            .collect::<Vec<_>>();
+        let encoded = args
+            .iter()
+            .map(|(name, _, codec)| format!("{codec}.enc({name})"))
+            .collect::<Vec<_>>();
+        let selector = selector
+            .iter()
+            .map(|b| format!("{b:#04x}"))
+            .collect::<Vec<_>>();
+        let mut out = docs.to_string();
+        writeln!(
+            out,
+            "  {name}({}): Call<{}> {{",
+            params.join(", "),
+            self.ts_type(return_type_id)?
+        )?;
+        writeln!(
+            out,
+            "    return call({label:?}, [{}], [{}], {});",
+            selector.join(", "),
+            encoded.join(", "),
+            self.codec(return_type_id, None)?
+        )?;
+        writeln!(out, "  }},")?;
+        Ok(out)
+    }
+
+    /// The event codecs, the `Event` union and the `decodeEvent` function.
+    fn push_events(&self, out: &mut String) -> Result<()> {
+        let contract = self.contract;
+        let events = contract.project.spec().events();
+        for (event, name) in events.iter().zip(&contract.event_names) {
+            let codec = if event.args().is_empty() {
+                self.import("_void");
+                "_void".to_string()
+            } else {
+                let fields = event
+                    .args()
+                    .iter()
+                    .map(|arg| {
+                        Ok(format!(
+                            "{}: {}",
+                            ts_key(arg.label()),
+                            self.codec(arg.ty().ty().id, None)?
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                self.import("Struct");
+                format!("Struct({{ {} }})", fields.join(", "))
+            };
+            writeln!(out)?;
+            push_value_type(out, &jsdoc(event.docs(), &[], ""), name, &codec)?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "/** An event emitted by the contract. */")?;
+        writeln!(out, "export type Event =")?;
+        let variants = contract
+            .event_names
+            .iter()
+            .map(|name| format!("  | {{ name: {name:?}; value: {name} }}"))
+            .collect::<Vec<_>>();
+        writeln!(out, "{};", variants.join("\n"))?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "/**\n \
+            * Decode an event emitted by the contract from the hex encoded topics and the data\n \
+            * of the `ContractEmitted` event. Returns `undefined` if the first topic is none of\n \
+            * the signature topics, e.g. for anonymous events.\n \
+            */"
+        )?;
+        writeln!(
+            out,
+            "export function decodeEvent(topics: string[], data: Uint8Array): Event | undefined {{"
+        )?;
+        writeln!(out, "  switch (topics[0]?.toLowerCase()) {{")?;
+        for (event, name) in events.iter().zip(&contract.event_names) {
+            if let Some(topic) = event.signature_topic() {
+                writeln!(out, "    case \"0x{}\":", hex::encode(topic.as_bytes()))?;
+                writeln!(
+                    out,
+                    "      return {{ name: {name:?}, value: {name}.dec(data) }};"
+                )?;
+            }
+        }
+        writeln!(out, "    default:")?;
+        writeln!(out, "      return undefined;")?;
+        writeln!(out, "  }}")?;
+        writeln!(out, "}}")?;
+        Ok(())
+    }
+
+    /// The `storage` object with the methods returning the storage keys.
+    fn push_storage(&self, out: &mut String) -> Result<()> {
+        writeln!(out)?;
+        writeln!(out, "/** The storage cells of the contract. */")?;
+        writeln!(out, "export const storage = {{")?;
+        for cell in &self.contract.storage {
+            let path = if cell.path.is_empty() {
+                "the root".to_string()
+            } else {
+                format!("`{}`", cell.path)
+            };
+            let (docs, param, key) = match cell.kind {
+                CellKind::Mapping { key_type_id } => {
+                    (
+                        format!(
+                            "The entry with the given key of the `Mapping` at {path}."
+                        ),
+                        format!("key: {}", self.ts_type(key_type_id)?),
+                        format!("{}.enc(key)", self.codec(key_type_id, None)?),
+                    )
+                }
+                CellKind::StorageVec => {
+                    (
+                        format!(
+                        "The element at the given index of the `StorageVec` at {path}."
+                    ),
+                        "index: number".to_string(),
+                        "u32.enc(index)".to_string(),
+                    )
+                }
+                CellKind::Lazy => {
+                    (
+                        format!("The `Lazy` value at {path}."),
+                        String::new(),
+                        "new Uint8Array()".to_string(),
+                    )
+                }
+                CellKind::Packed => {
+                    (
+                        format!("The values stored together at {path} of the storage."),
+                        String::new(),
+                        "new Uint8Array()".to_string(),
+                    )
+                }
+            };
+            writeln!(out, "  /** {docs} */")?;
+            writeln!(
+                out,
+                "  {}({param}): StorageKey<{}> {{",
+                camel_case(&cell.name),
+                self.ts_type(cell.value_type_id)?
+            )?;
+            writeln!(
+                out,
+                "    return storageKey({:#010x}, {key}, {});",
+                cell.root_key,
+                self.codec(cell.value_type_id, None)?
+            )?;
+            writeln!(out, "  }},")?;
+        }
+        writeln!(out, "}};")?;
+        Ok(())
+    }
+
+    /// The codec of the fields of a struct or an enum variant: a `Struct` for named
+    /// fields, the codec of a single unnamed field and a `Tuple` for several.
+    fn fields_codec(
+        &self,
+        fields: &[Field<PortableForm>],
+        from: Option<usize>,
+    ) -> Result<String> {
+        if fields.is_empty() {
+            self.import("_void");
+            return Ok("_void".to_string())
+        }
+        if fields.iter().all(|field| field.name.is_some()) {
+            let fields = fields
+                .iter()
+                .map(|field| {
+                    let name = field.name.as_deref().expect("all fields are named");
+                    Ok(format!(
+                        "{}: {}",
+                        ts_key(name),
+                        self.codec(field.ty.id, from)?
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            self.import("Struct");
+            return Ok(format!("Struct({{ {} }})", fields.join(", ")))
+        }
+        match fields {
+            [field] => self.codec(field.ty.id, from),
+            fields => {
+                let codecs = fields
+                    .iter()
+                    .map(|field| self.codec(field.ty.id, from))
+                    .collect::<Result<Vec<_>>>()?;
+                self.import("Tuple");
+                Ok(format!("Tuple({})", codecs.join(", ")))
+            }
+        }
+    }
+
+    /// The codec expression of the type with the given id. Custom types referenced from
+    /// the custom type at position `from` before they are defined, i.e. recursive
+    /// types, are resolved lazily.
+    fn codec(&self, type_id: u32, from: Option<usize>) -> Result<String> {
+        let contract = self.contract;
+        let ty = contract
+            .project
+            .registry()
+            .resolve(type_id)
+            .with_context(|| format!("Failed to resolve type with id `{type_id}`"))?;
+        if let Some(name) = contract.type_name(type_id) {
+            return Ok(if contract.is_forward_ref(from, type_id) {
+                self.import("createCodec");
+                format!("lazy((): Codec<any> => {name})")
+            } else {
+                name.to_string()
+            })
+        }
+        let codec = |id| self.codec(id, from);
+        Ok(match &ty.type_def {
+            TypeDef::Variant(_) if is_builtin(ty) => {
+                if ty.path.segments == ["Option"] {
+                    self.import("Option");
+                    format!("Option({})", codec(builtin_param(ty, "Some")?)?)
+                } else {
+                    self.import("Result");
+                    format!(
+                        "Result({}, {})",
+                        codec(builtin_param(ty, "Ok")?)?,
+                        codec(builtin_param(ty, "Err")?)?
+                    )
+                }
+            }
+            TypeDef::Primitive(primitive) => {
+                let codec = primitive_codec(primitive);
+                self.import(codec);
+                codec.to_string()
+            }
+            TypeDef::Array(array) if self.is_u8(array.type_param.id) => {
+                self.import("Bytes");
+                format!("Bytes({})", array.len)
+            }
+            TypeDef::Array(array) => {
+                self.import("Vector");
+                format!("Vector({}, {})", codec(array.type_param.id)?, array.len)
+            }
+            TypeDef::Sequence(sequence) if self.is_u8(sequence.type_param.id) => {
+                self.import("Bytes");
+                "Bytes()".to_string()
+            }
+            TypeDef::Sequence(sequence) => {
+                self.import("Vector");
+                format!("Vector({})", codec(sequence.type_param.id)?)
+            }
+            TypeDef::Tuple(tuple) if tuple.fields.is_empty() => {
+                self.import("_void");
+                "_void".to_string()
+            }
+            TypeDef::Tuple(tuple) => {
+                let codecs = tuple
+                    .fields
+                    .iter()
+                    .map(|field| codec(field.id))
+                    .collect::<Result<Vec<_>>>()?;
+                self.import("Tuple");
+                format!("Tuple({})", codecs.join(", "))
+            }
+            TypeDef::Compact(_) => {
+                self.import("compact");
+                "compact".to_string()
+            }
+            TypeDef::Composite(_) | TypeDef::Variant(_) | TypeDef::BitSequence(_) => {
+                anyhow::bail!("Unsupported type {}", ty.path)
+            }
+        })
+    }
+
+    /// The TypeScript type of the values of the type with the given id.
+    fn ts_type(&self, type_id: u32) -> Result<String> {
+        let contract = self.contract;
+        let ty = contract
+            .project
+            .registry()
+            .resolve(type_id)
+            .with_context(|| format!("Failed to resolve type with id `{type_id}`"))?;
+        if let Some(name) = contract.type_name(type_id) {
+            return Ok(name.to_string())
+        }
+        Ok(match &ty.type_def {
+            TypeDef::Variant(_) if is_builtin(ty) => {
+                if ty.path.segments == ["Option"] {
+                    format!("{} | undefined", self.ts_type(builtin_param(ty, "Some")?)?)
+                } else {
+                    format!(
+                        "{{ success: true; value: {} }} | {{ success: false; value: {} }}",
+                        self.ts_type(builtin_param(ty, "Ok")?)?,
+                        self.ts_type(builtin_param(ty, "Err")?)?
+                    )
+                }
+            }
+            TypeDef::Primitive(primitive) => {
+                match primitive {
+                    TypeDefPrimitive::Bool => "boolean",
+                    TypeDefPrimitive::Str => "string",
+                    TypeDefPrimitive::Char
+                    | TypeDefPrimitive::U8
+                    | TypeDefPrimitive::U16
+                    | TypeDefPrimitive::U32
+                    | TypeDefPrimitive::I8
+                    | TypeDefPrimitive::I16
+                    | TypeDefPrimitive::I32 => "number",
+                    TypeDefPrimitive::U64
+                    | TypeDefPrimitive::U128
+                    | TypeDefPrimitive::U256
+                    | TypeDefPrimitive::I64
+                    | TypeDefPrimitive::I128
+                    | TypeDefPrimitive::I256 => "bigint",
+                }
+                .to_string()
+            }
+            TypeDef::Array(array) if self.is_u8(array.type_param.id) => {
+                "Uint8Array".to_string()
+            }
+            TypeDef::Array(array) => {
+                format!("Array<{}>", self.ts_type(array.type_param.id)?)
+            }
+            TypeDef::Sequence(sequence) if self.is_u8(sequence.type_param.id) => {
+                "Uint8Array".to_string()
+            }
+            TypeDef::Sequence(sequence) => {
+                format!("Array<{}>", self.ts_type(sequence.type_param.id)?)
+            }
+            TypeDef::Tuple(tuple) if tuple.fields.is_empty() => "undefined".to_string(),
+            TypeDef::Tuple(tuple) => {
+                let types = tuple
+                    .fields
+                    .iter()
+                    .map(|field| self.ts_type(field.id))
+                    .collect::<Result<Vec<_>>>()?;
+                format!("[{}]", types.join(", "))
+            }
+            TypeDef::Compact(_) => "number | bigint".to_string(),
+            TypeDef::Composite(_) | TypeDef::Variant(_) | TypeDef::BitSequence(_) => {
+                anyhow::bail!("Unsupported type {}", ty.path)
+            }
+        })
+    }
+
+    fn is_u8(&self, type_id: u32) -> bool {
+        self.contract
+            .project
+            .registry()
+            .resolve(type_id)
+            .is_some_and(|ty| {
+                matches!(ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8))
+            })
+    }
+
+    fn import(&self, codec: &'static str) {
+        self.imports.borrow_mut().insert(codec);
+    }
+}
+
+/// The type of the field of the given variant of `Option` or `Result`.
+fn builtin_param(ty: &scale_info::Type<PortableForm>, variant_name: &str) -> Result<u32> {
+    match &ty.type_def {
+        TypeDef::Variant(variant) => {
+            variant
+                .variants
+                .iter()
+                .find(|variant| variant.name == variant_name)
+                .and_then(|variant| variant.fields.first())
+                .map(|field| field.ty.id)
+        }
+        _ => None,
+    }
+    .with_context(|| format!("Missing `{variant_name}` variant of {}", ty.path))
+}
+
+fn primitive_codec(primitive: &TypeDefPrimitive) -> &'static str {
+    match primitive {
+        TypeDefPrimitive::Bool => "bool",
+        // a `char` is SCALE encoded as its `u32` code point
+        TypeDefPrimitive::Char => "u32",
+        TypeDefPrimitive::Str => "str",
+        TypeDefPrimitive::U8 => "u8",
+        TypeDefPrimitive::U16 => "u16",
+        TypeDefPrimitive::U32 => "u32",
+        TypeDefPrimitive::U64 => "u64",
+        TypeDefPrimitive::U128 => "u128",
+        TypeDefPrimitive::U256 => "u256",
+        TypeDefPrimitive::I8 => "i8",
+        TypeDefPrimitive::I16 => "i16",
+        TypeDefPrimitive::I32 => "i32",
+        TypeDefPrimitive::I64 => "i64",
+        TypeDefPrimitive::I128 => "i128",
+        TypeDefPrimitive::I256 => "i256",
+    }
+}
+
+/// Push a codec together with the type of its values, both exported as `name`.
+fn push_value_type(out: &mut String, docs: &str, name: &str, codec: &str) -> Result<()> {
+    out.push_str(docs);
+    writeln!(out, "export const {name} = {codec};")?;
+    writeln!(out, "export type {name} = CodecType<typeof {name}>;")?;
+    Ok(())
+}
+
+/// The JSDoc comment with the given lines, followed by the `notes` as a paragraph.
+fn jsdoc(lines: &[String], notes: &[String], indent: &str) -> String {
+    let mut lines = lines
+        .iter()
+        .map(|line| line.trim().replace("*/", "*\\/"))
+        .collect::<Vec<_>>();
+    if !notes.is_empty() {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        let notes = notes.join(", ");
+        let mut chars = notes.chars();
+        let first = chars.next().map(|c| c.to_uppercase().to_string());
+        lines.push(format!("{}{}.", first.unwrap_or_default(), chars.as_str()));
+    }
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{indent}/** {line} */\n"),
+        lines => {
+            let mut out = format!("{indent}/**\n");
+            for line in lines {
+                if line.is_empty() {
+                    out.push_str(&format!("{indent} *\n"));
+                } else {
+                    out.push_str(&format!("{indent} * {line}\n"));
+                }
+            }
+            out.push_str(&format!("{indent} */\n"));
+            out
+        }
+    }
+}
+
+fn selector_note(selector: &[u8]) -> String {
+    format!("selector `0x{}`", hex::encode(selector))
+}
+
+/// A property key, quoted if it isn't an identifier.
+fn ts_key(name: &str) -> String {
+    let is_ident = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_ident {
+        name.to_string()
+    } else {
+        format!("{name:?}")
+    }
+}
+
+/// Escape a reserved word with a trailing `_`, e.g. for a parameter name.
+fn ts_ident(name: &str) -> String {
+    const RESERVED: &[&str] = &[
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "debugger",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typeof",
+        "var",
+        "void",
+        "while",
+        "with",
+        "yield",
+    ];
+    if RESERVED.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
@@ -14,6 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::signer::{
+    impl_try_signer,
+    FromSignerSource,
+    KeySigner,
+};
+use contract_extrinsics::TrySigner;
 use ink_env::{
     DefaultEnvironment,
     Environment,
@@ -27,14 +33,7 @@ use subxt::{
         PolkadotExtrinsicParams,
         SubstrateExtrinsicParams,
     },
-    ext::{
-        sp_core,
-        sp_core::Pair,
-    },
-    tx::{
-        PairSigner,
-        Signer as SignerT,
-    },
+    ext::sp_core,
     Config,
     PolkadotConfig,
     SubstrateConfig,
@@ -42,7 +41,7 @@ use subxt::{
 
 /// Configuration for signer
 pub trait SignerConfig<C: Config + Environment> {
-    type Signer: SignerT<C> + FromStr + Clone;
+    type Signer: TrySigner<C> + FromStr + FromSignerSource + Clone;
 }
 
 /// A runtime configuration for the ecdsa test chain.
@@ -78,6 +77,8 @@ where
     type Signer = SignerEcdsa<Self>;
 }
 
+impl_try_signer!(Ecdsachain);
+
 /// A runtime configuration for the Substrate based chain.
 /// This thing is not meant to be instantiated; it is just a collection of types.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -108,6 +109,8 @@ impl SignerConfig<Self> for Substrate {
     type Signer = SignerSR25519<Self>;
 }
 
+impl_try_signer!(Substrate);
+
 /// A runtime configuration for the Polkadot based chain.
 /// This thing is not meant to be instantiated; it is just a collection of types.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -138,77 +141,13 @@ impl SignerConfig<Self> for Polkadot {
     type Signer = SignerSR25519<Self>;
 }
 
-/// Struct representing the implementation of the sr25519 signer
-#[derive(Clone)]
-pub struct SignerSR25519<C: Config>(pub PairSigner<C, sp_core::sr25519::Pair>);
-
-impl<C: Config> FromStr for SignerSR25519<C>
-where
-    <C as Config>::AccountId: From<sp_core::crypto::AccountId32>,
-{
-    type Err = anyhow::Error;
-
-    /// Attempts to parse the Signer suri string
-    fn from_str(input: &str) -> Result<SignerSR25519<C>, Self::Err> {
-        let keypair = sp_core::sr25519::Pair::from_string(input, None)?;
-        let signer = PairSigner::<C, _>::new(keypair);
-        Ok(Self(signer))
-    }
-}
-
-impl<C: Config> SignerT<C> for SignerSR25519<C>
-where
-    <C as Config>::Signature: From<sp_core::sr25519::Signature>,
-{
-    fn account_id(&self) -> <C as Config>::AccountId {
-        self.0.account_id().clone()
-    }
-
-    fn address(&self) -> C::Address {
-        self.0.address()
-    }
-
-    fn sign(&self, signer_payload: &[u8]) -> C::Signature {
-        self.0.sign(signer_payload)
-    }
-}
-
-/// Struct representing the implementation of the ecdsa signer
-#[derive(Clone)]
-pub struct SignerEcdsa<C: Config>(pub PairSigner<C, sp_core::ecdsa::Pair>);
-
-impl<C: Config> FromStr for SignerEcdsa<C>
-where
-    // Requirements of the `PairSigner where:
-    // T::AccountId: From<SpAccountId32>`
-    <C as Config>::AccountId: From<sp_core::crypto::AccountId32>,
-{
-    type Err = anyhow::Error;
-
-    /// Attempts to parse the Signer suri string
-    fn from_str(input: &str) -> Result<SignerEcdsa<C>, Self::Err> {
-        let keypair = sp_core::ecdsa::Pair::from_string(input, None)?;
-        let signer = PairSigner::<C, _>::new(keypair);
-        Ok(Self(signer))
-    }
-}
-
-impl<C: Config> SignerT<C> for SignerEcdsa<C>
-where
-    <C as Config>::Signature: From<sp_core::ecdsa::Signature>,
-{
-    fn account_id(&self) -> <C as Config>::AccountId {
-        self.0.account_id().clone()
-    }
+impl_try_signer!(Polkadot);
 
-    fn address(&self) -> C::Address {
-        self.0.address()
-    }
+/// Signer for chains using sr25519 keys.
+pub type SignerSR25519<C> = KeySigner<C, sp_core::sr25519::Pair>;
 
-    fn sign(&self, signer_payload: &[u8]) -> C::Signature {
-        self.0.sign(signer_payload)
-    }
-}
+/// Signer for chains using ecdsa keys.
+pub type SignerEcdsa<C> = KeySigner<C, sp_core::ecdsa::Pair>;
 
 #[macro_export]
 macro_rules! call_with_config_internal {
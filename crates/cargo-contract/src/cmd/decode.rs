@@ -14,7 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::DEFAULT_KEY_COL_WIDTH;
+use super::{
+    renderer,
+    CLICustomTypesOpts,
+    CLINodeOpts,
+};
+use crate::{
+    call_with_config,
+    DEFAULT_KEY_COL_WIDTH,
+};
 use anyhow::{
     Context,
     Result,
@@ -25,15 +33,56 @@ use clap::{
 };
 use colored::Colorize as _;
 use contract_build::{
+    name_value_println,
     util,
     CrateMetadata,
 };
+use contract_extrinsics::{
+    fetch_contract_info_at,
+    fetch_extrinsic,
+    fetch_ss58_prefix,
+    format_ss58,
+    url_to_string,
+    ArtifactRegistry,
+    BalanceVariant,
+    ContractArtifacts,
+    ContractCall,
+    ContractExtrinsic,
+    ErrorVariant,
+    TokenMetadata,
+};
 use contract_transcode::ContractMessageTranscoder;
+use ink_env::Environment;
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+};
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::RpcClient,
+    },
+    ext::scale_decode::IntoVisitor,
+    Config,
+    OnlineClient,
+};
 
 #[derive(Debug, Args)]
 pub struct DecodeCommand {
     #[clap(subcommand)]
     commands: DecodeCommands,
+    /// Display the decoded data as plain data, e.g. timestamps in milliseconds, instead
+    /// of in a human-readable form.
+    #[clap(long, global = true)]
+    plain: bool,
+    /// SS58 address format prefix the accounts are displayed with, instead of the
+    /// generic Substrate prefix `42`, or the `ss58Format` of the node for `extrinsic`.
+    #[clap(long, global = true, value_parser = clap::value_parser!(u16).range(..16_384))]
+    ss58_prefix: Option<u16>,
+    #[clap(flatten)]
+    custom_types_cli_opts: CLICustomTypesOpts,
 }
 
 #[derive(Debug, Subcommand)]
@@ -47,6 +96,9 @@ pub enum DecodeCommands {
     /// Decode an event as input
     #[clap(name = "event")]
     Event(DecodeEvent),
+    /// Decode the contract calls of an extrinsic
+    #[clap(name = "extrinsic")]
+    Extrinsic(DecodeExtrinsic),
 }
 
 #[derive(Debug, Clone, Args)]
@@ -74,12 +126,50 @@ pub struct DecodeEvent {
     data: String,
 }
 
-impl DecodeCommand {
-    pub fn run(&self) -> Result<()> {
-        let crate_metadata =
-            CrateMetadata::from_manifest_path(None, contract_build::Target::Wasm)?;
-        let transcoder = ContractMessageTranscoder::load(crate_metadata.metadata_path())?;
+#[derive(Debug, Clone, Args)]
+pub struct DecodeExtrinsic {
+    /// The extrinsic to decode: either its hex encoding starting with `0x`, or its
+    /// position `<block number>:<index>` in a block of the chain.
+    #[clap(value_parser = clap::value_parser!(ExtrinsicSource))]
+    extrinsic: ExtrinsicSource,
+    /// Path to a contract build artifact file used to decode the contract calls: a
+    /// `.contract` bundle or a `.json` metadata file. Defaults to the artifacts of the
+    /// contract in the current directory, if any.
+    #[clap(long, value_parser)]
+    file: Option<PathBuf>,
+    /// Directory of contract artifacts used to decode the calls of other contracts.
+    #[clap(long, value_parser)]
+    artifacts_dir: Option<PathBuf>,
+    /// Arguments required for communicating with a Substrate node.
+    #[clap(flatten)]
+    node_cli_opts: CLINodeOpts,
+}
+
+/// The extrinsic to decode, given either encoded or by its position in a block.
+#[derive(Debug, Clone, PartialEq)]
+enum ExtrinsicSource {
+    Encoded(Vec<u8>),
+    InBlock { block_number: u64, index: usize },
+}
+
+impl FromStr for ExtrinsicSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if let Some((block_number, index)) = s.split_once(':') {
+            return Ok(Self::InBlock {
+                block_number: block_number.parse().context("Invalid block number")?,
+                index: index.parse().context("Invalid extrinsic index")?,
+            })
+        }
+        let encoded = util::decode_hex(s)
+            .context("Failed to decode specified extrinsic as a hex value")?;
+        Ok(Self::Encoded(encoded))
+    }
+}
 
+impl DecodeCommand {
+    pub async fn handle(&self) -> Result<(), ErrorVariant> {
         const ERR_MSG: &str = "Failed to decode specified data as a hex value";
         let decoded_data = match &self.commands {
             DecodeCommands::Event(event) => {
@@ -87,21 +177,32 @@ impl DecodeCommand {
                     util::decode_hex(&event.signature_topic).context(ERR_MSG)?;
                 let signature_topic =
                     primitive_types::H256::from_slice(&signature_topic_data);
-                transcoder.decode_contract_event(
+                self.transcoder()?.decode_contract_event(
                     &signature_topic,
                     &mut &util::decode_hex(&event.data).context(ERR_MSG)?[..],
                 )?
             }
             DecodeCommands::Message(message) => {
-                transcoder.decode_contract_message(
+                self.transcoder()?.decode_contract_message(
                     &mut &util::decode_hex(&message.data).context(ERR_MSG)?[..],
                 )?
             }
             DecodeCommands::Constructor(constructor) => {
-                transcoder.decode_contract_constructor(
+                self.transcoder()?.decode_contract_constructor(
                     &mut &util::decode_hex(&constructor.data).context(ERR_MSG)?[..],
                 )?
             }
+            DecodeCommands::Extrinsic(extrinsic) => {
+                let decoder = ExtrinsicDecoder {
+                    command: self,
+                    extrinsic,
+                };
+                return call_with_config!(
+                    decoder,
+                    run,
+                    extrinsic.node_cli_opts.chain().config()
+                )
+            }
         };
 
         println!(
@@ -113,4 +214,233 @@ impl DecodeCommand {
 
         Ok(())
     }
+
+    /// Load the transcoder of the contract in the current directory.
+    fn transcoder(&self) -> Result<ContractMessageTranscoder> {
+        let crate_metadata =
+            CrateMetadata::from_manifest_path(None, contract_build::Target::Wasm)?;
+        let mut transcoder =
+            self.custom_types_cli_opts
+                .apply(ContractMessageTranscoder::load(
+                    crate_metadata.metadata_path(),
+                )?)?;
+        if let Some(prefix) = self.ss58_prefix {
+            transcoder = transcoder.with_ss58_prefix(prefix)?;
+        }
+        if !self.plain {
+            transcoder = transcoder.with_renderer(renderer(None));
+        }
+        Ok(transcoder)
+    }
+}
+
+/// Decodes the contract calls of an extrinsic with the chain configuration `C`.
+struct ExtrinsicDecoder<'a> {
+    command: &'a DecodeCommand,
+    extrinsic: &'a DecodeExtrinsic,
+}
+
+impl ExtrinsicDecoder<'_> {
+    async fn run<C: Config + Environment>(&self) -> Result<(), ErrorVariant>
+    where
+        <C as Config>::AccountId: IntoVisitor + AsRef<[u8]> + Display,
+        <C as Config>::Hash: IntoVisitor + From<[u8; 32]> + AsRef<[u8]> + Serialize,
+        C::Balance: IntoVisitor + Into<u128> + Copy + Display + Serialize,
+    {
+        let chain = self.extrinsic.node_cli_opts.chain();
+        let rpc_cli = RpcClient::from_url(url_to_string(&chain.url())).await?;
+        let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
+        let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+
+        // the contracts of an extrinsic in a block are looked up in the state it was
+        // applied to, as they may have been removed or changed since
+        let (encoded, block_hash) = match self.extrinsic.extrinsic {
+            ExtrinsicSource::Encoded(ref encoded) => (encoded.clone(), None),
+            ExtrinsicSource::InBlock {
+                block_number,
+                index,
+            } => {
+                let (encoded, parent_hash) =
+                    fetch_extrinsic(&rpc, block_number, index).await?;
+                (encoded, Some(parent_hash))
+            }
+        };
+        let decoded = ContractExtrinsic::<
+            <C as Config>::AccountId,
+            C::Balance,
+            <C as Config>::Hash,
+        >::decode(&encoded, &client.metadata())?;
+
+        let token_metadata = TokenMetadata::fetch(&rpc).await?;
+        let ss58_prefix = match self.command.ss58_prefix {
+            Some(prefix) => prefix,
+            None => fetch_ss58_prefix(&rpc).await?,
+        };
+        let registry = self.artifact_registry(&token_metadata, ss58_prefix)?;
+
+        let signer = decoded
+            .signer()
+            .map(|signer| format_ss58(signer, Some(ss58_prefix)))
+            .unwrap_or_else(|| "unsigned".to_string());
+        name_value_println!("Signer", signer, DEFAULT_KEY_COL_WIDTH);
+        if decoded.calls().is_empty() {
+            println!("The extrinsic doesn't call any contract");
+        }
+        for call in decoded.calls() {
+            let code_hash = match (call.contract(), call.code_hash()) {
+                (Some(contract), _) => {
+                    fetch_contract_info_at::<C, C>(contract, &rpc, &client, block_hash)
+                        .await
+                        .map(|info| *info.code_hash())
+                        .map_err(|err| tracing::debug!("no contract info: {err:?}"))
+                        .ok()
+                }
+                (None, code_hash) => code_hash.copied(),
+            };
+            let transcoder = code_hash
+                .as_ref()
+                .and_then(|code_hash| registry.code_transcoder(code_hash));
+            self.display_call::<C>(
+                call,
+                transcoder,
+                code_hash,
+                &token_metadata,
+                ss58_prefix,
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Load the artifacts of the contract in the current directory or the given file,
+    /// and of the artifacts directory, to decode the data of the contract calls.
+    fn artifact_registry(
+        &self,
+        token_metadata: &TokenMetadata,
+        ss58_prefix: u16,
+    ) -> Result<ArtifactRegistry> {
+        let registry = match self.extrinsic.artifacts_dir {
+            Some(ref dir) => ArtifactRegistry::load(dir)?,
+            None => ArtifactRegistry::default(),
+        };
+        let registry = match self.extrinsic.file {
+            Some(ref file) => {
+                registry.with_artifacts(&ContractArtifacts::from_manifest_or_file(
+                    None,
+                    Some(file),
+                )?)?
+            }
+            None => {
+                match ContractArtifacts::from_manifest_or_file(None, None) {
+                    Ok(artifacts) => registry.with_artifacts(&artifacts)?,
+                    Err(_) => registry,
+                }
+            }
+        };
+        let registry = registry.with_ss58_prefix(ss58_prefix)?;
+        let registry = match self.command.custom_types_cli_opts.load()? {
+            Some(ref custom_types) => registry.with_custom_types(custom_types)?,
+            None => registry,
+        };
+        let registry = if self.command.plain {
+            registry
+        } else {
+            registry.with_renderer(renderer(Some(token_metadata)))
+        };
+        Ok(registry)
+    }
+
+    fn display_call<C: Config + Environment>(
+        &self,
+        call: &ContractCall<<C as Config>::AccountId, C::Balance, <C as Config>::Hash>,
+        transcoder: Option<&ContractMessageTranscoder>,
+        code_hash: Option<<C as Config>::Hash>,
+        token_metadata: &TokenMetadata,
+        ss58_prefix: u16,
+    ) -> Result<()>
+    where
+        <C as Config>::AccountId: Display,
+        <C as Config>::Hash: AsRef<[u8]>,
+        C::Balance: Into<u128> + Copy + Display,
+    {
+        let balance = |balance: &C::Balance| {
+            if self.command.plain {
+                return balance.to_string()
+            }
+            BalanceVariant::<u128>::from(*balance, Some(token_metadata))
+                .map(|balance| balance.to_string())
+                .unwrap_or_else(|_| balance.to_string())
+        };
+
+        let mut name = format!("Contracts::{}", call.name());
+        if !call.dispatched_by().is_empty() {
+            name = format!("{name} (in {})", call.dispatched_by().join(" > "));
+        }
+        name_value_println!("Call", name, DEFAULT_KEY_COL_WIDTH);
+        if let Some(contract) = call.contract() {
+            name_value_println!(
+                "Contract",
+                format_ss58(contract, Some(ss58_prefix)),
+                DEFAULT_KEY_COL_WIDTH
+            );
+        }
+        if let Some(code_hash) = code_hash {
+            name_value_println!(
+                "Code hash",
+                format!("0x{}", hex::encode(code_hash)),
+                DEFAULT_KEY_COL_WIDTH
+            );
+        }
+        if let Some(value) = call.value() {
+            name_value_println!("Value", balance(value), DEFAULT_KEY_COL_WIDTH);
+        }
+        if let Some(gas_limit) = call.gas_limit() {
+            name_value_println!(
+                "Gas limit",
+                format!("{gas_limit:?}"),
+                DEFAULT_KEY_COL_WIDTH
+            );
+        }
+        if let Some(limit) = call.storage_deposit_limit() {
+            name_value_println!("Deposit limit", balance(limit), DEFAULT_KEY_COL_WIDTH);
+        }
+        if let Some(data) = call.data() {
+            let decoded = match transcoder {
+                Some(transcoder) if call.is_instantiation() => {
+                    transcoder
+                        .decode_contract_constructor(&mut &data[..])?
+                        .to_string()
+                }
+                Some(transcoder) => {
+                    transcoder
+                        .decode_contract_message(&mut &data[..])?
+                        .to_string()
+                }
+                None => format!("0x{} (no contract metadata found)", hex::encode(data)),
+            };
+            name_value_println!("Data", decoded, DEFAULT_KEY_COL_WIDTH);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_extrinsic_source() {
+        assert_eq!(
+            ExtrinsicSource::from_str("0x0401").unwrap(),
+            ExtrinsicSource::Encoded(vec![4, 1])
+        );
+        assert_eq!(
+            ExtrinsicSource::from_str("1200:3").unwrap(),
+            ExtrinsicSource::InBlock {
+                block_number: 1200,
+                index: 3
+            }
+        );
+        assert!(ExtrinsicSource::from_str("0xzz").is_err());
+        assert!(ExtrinsicSource::from_str("#12:3").is_err());
+    }
 }
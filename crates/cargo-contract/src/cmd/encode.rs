@@ -16,6 +16,10 @@
 
 use std::path::PathBuf;
 
+use super::{
+    CLIArgsOpts,
+    CLICustomTypesOpts,
+};
 use crate::DEFAULT_KEY_COL_WIDTH;
 use anyhow::Result;
 use colored::Colorize as _;
@@ -37,9 +41,10 @@ pub struct EncodeCommand {
     /// The name of the contract message to encode.
     #[clap(long, short)]
     message: String,
-    /// The arguments to encode
-    #[clap(long, num_args = 0..)]
-    args: Vec<String>,
+    #[clap(flatten)]
+    args_opts: CLIArgsOpts,
+    #[clap(flatten)]
+    custom_types_cli_opts: CLICustomTypesOpts,
 }
 
 impl EncodeCommand {
@@ -48,9 +53,11 @@ impl EncodeCommand {
             self.manifest_path.as_ref(),
             self.file.as_ref(),
         )?;
-        let transcoder = artifacts.contract_transcoder()?;
+        let transcoder = self
+            .custom_types_cli_opts
+            .apply(artifacts.contract_transcoder()?)?;
 
-        let call_data = transcoder.encode(&self.message, &self.args)?;
+        let call_data = self.args_opts.encode(&transcoder, &self.message)?;
         let call_data_encoded = hex::encode_upper(call_data);
 
         println!(
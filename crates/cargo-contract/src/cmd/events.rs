@@ -0,0 +1,274 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::call_with_config;
+
+use super::{
+    parse_account,
+    parse_code_hash,
+    renderer,
+    CLIChainOpts,
+    CLICustomTypesOpts,
+};
+use anyhow::{
+    anyhow,
+    Result,
+};
+use contract_build::{
+    name_value_println,
+    Verbosity,
+    VerbosityFlags,
+    DEFAULT_KEY_COL_WIDTH,
+};
+use contract_extrinsics::{
+    url_to_string,
+    ArtifactRegistry,
+    ContractArtifacts,
+    ContractEventFilter,
+    ContractMessageTranscoder,
+    DisplayEvents,
+    ErrorVariant,
+    TokenMetadata,
+};
+use ink_env::Environment;
+use std::{
+    fmt::Display,
+    path::PathBuf,
+    str::FromStr,
+};
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::RpcClient,
+    },
+    blocks::Block,
+    ext::scale_decode::IntoVisitor,
+    Config,
+    OnlineClient,
+};
+
+#[derive(Debug, clap::Args)]
+#[clap(name = "events", about = "Display the events of contracts")]
+pub struct EventsCommand {
+    /// Only display the events of the contracts with these addresses.
+    #[clap(name = "contract", long, value_delimiter = ',')]
+    contracts: Vec<String>,
+    /// Only display the contract events with these signature topics.
+    #[clap(name = "topic", long, value_delimiter = ',')]
+    topics: Vec<String>,
+    /// Subscribe to new blocks and display their events as they are imported.
+    #[clap(long, conflicts_with_all = ["from_block", "to_block"])]
+    follow: bool,
+    /// Follow the best blocks instead of the finalized blocks.
+    #[clap(long, requires = "follow")]
+    best: bool,
+    /// The number of the first block of a block range to display the events of.
+    #[clap(long, required_unless_present = "follow")]
+    from_block: Option<u64>,
+    /// The number of the last block of the block range. Defaults to the last finalized
+    /// block.
+    #[clap(long, requires = "from_block")]
+    to_block: Option<u64>,
+    /// Path to a contract build artifact file used to decode the contract events: a
+    /// `.contract` bundle or a `.json` metadata file.
+    #[clap(value_parser)]
+    file: Option<PathBuf>,
+    /// Directory of contract artifacts used to decode the events of other contracts.
+    #[clap(long, value_parser)]
+    artifacts_dir: Option<PathBuf>,
+    /// Export the events as JSON lines, one event per line.
+    #[clap(name = "output-json", long)]
+    output_json: bool,
+    /// Display the contract events as plain data, e.g. balances as integers and
+    /// timestamps in milliseconds, instead of in a human-readable form.
+    #[clap(long)]
+    plain: bool,
+    #[clap(flatten)]
+    verbosity: VerbosityFlags,
+    /// Arguments required for communicating with a Substrate node.
+    #[clap(flatten)]
+    chain_cli_opts: CLIChainOpts,
+    #[clap(flatten)]
+    custom_types_cli_opts: CLICustomTypesOpts,
+}
+
+impl EventsCommand {
+    pub async fn handle(&self) -> Result<(), ErrorVariant> {
+        call_with_config!(self, run, self.chain_cli_opts.chain().config())
+    }
+
+    pub async fn run<C: Config + Environment>(&self) -> Result<(), ErrorVariant>
+    where
+        <C as Config>::AccountId: IntoVisitor + PartialEq + FromStr,
+        <<C as Config>::AccountId as FromStr>::Err: Display,
+        <C as Config>::Hash: From<[u8; 32]>,
+        C::Balance: Display + From<u128>,
+    {
+        let contracts = self
+            .contracts
+            .iter()
+            .map(|c| parse_account(c))
+            .collect::<Result<Vec<<C as Config>::AccountId>>>()?;
+        let signature_topics = self
+            .topics
+            .iter()
+            .map(|t| parse_code_hash(t))
+            .collect::<Result<Vec<<C as Config>::Hash>>>()?;
+        let filter = ContractEventFilter::default()
+            .contracts(contracts)
+            .signature_topics(signature_topics);
+        let token_metadata =
+            TokenMetadata::query::<C>(&self.chain_cli_opts.chain().url()).await?;
+        let renderer =
+            (!self.plain && !self.output_json).then(|| renderer(Some(&token_metadata)));
+        let custom_types = self.custom_types_cli_opts.load()?;
+        let ss58_prefix = self.chain_cli_opts.ss58_prefix::<C>().await?;
+        let transcoder = self
+            .file
+            .as_ref()
+            .map(|file| {
+                let transcoder =
+                    ContractArtifacts::from_manifest_or_file(None, Some(file))?
+                        .contract_transcoder()?
+                        .with_ss58_prefix(ss58_prefix)?;
+                match custom_types {
+                    Some(ref custom_types) => transcoder.with_custom_types(custom_types),
+                    None => Ok(transcoder),
+                }
+            })
+            .transpose()?
+            .map(|transcoder| {
+                match renderer {
+                    Some(ref renderer) => transcoder.with_renderer(renderer.clone()),
+                    None => transcoder,
+                }
+            });
+        let registry = match self.artifacts_dir {
+            Some(ref dir) => ArtifactRegistry::load(dir)?,
+            None => ArtifactRegistry::default(),
+        };
+        let registry = registry.with_ss58_prefix(ss58_prefix)?;
+        let registry = match custom_types {
+            Some(ref custom_types) => registry.with_custom_types(custom_types)?,
+            None => registry,
+        };
+        let registry = match renderer {
+            Some(renderer) => registry.with_renderer(renderer),
+            None => registry,
+        };
+        let decoder = EventsDecoder {
+            filter,
+            transcoder,
+            registry,
+            token_metadata,
+            verbosity: TryFrom::<&VerbosityFlags>::try_from(&self.verbosity)?,
+            output_json: self.output_json,
+        };
+
+        let rpc_cli =
+            RpcClient::from_url(url_to_string(&self.chain_cli_opts.chain().url()))
+                .await?;
+        let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
+        let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+
+        if self.follow {
+            let mut blocks = if self.best {
+                client.blocks().subscribe_best().await?
+            } else {
+                client.blocks().subscribe_finalized().await?
+            };
+            while let Some(block) = blocks.next().await {
+                decoder.display_block(&client, &block?).await?;
+            }
+            return Ok(())
+        }
+
+        let from_block = self
+            .from_block
+            .expect("from_block shall be present, it is enforced by clap configuration");
+        let to_block = match self.to_block {
+            Some(to_block) => to_block,
+            None => client.blocks().at_latest().await?.number().into(),
+        };
+        for number in from_block..=to_block {
+            let hash = rpc
+                .chain_get_block_hash(Some(number.into()))
+                .await?
+                .ok_or_else(|| anyhow!("Block #{number} not found"))?;
+            let block = client.blocks().at(hash).await?;
+            decoder.display_block(&client, &block).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Decodes and displays the selected events of blocks.
+struct EventsDecoder<C: Config> {
+    filter: ContractEventFilter<C::AccountId, C::Hash>,
+    transcoder: Option<ContractMessageTranscoder>,
+    registry: ArtifactRegistry,
+    token_metadata: TokenMetadata,
+    verbosity: Verbosity,
+    output_json: bool,
+}
+
+impl<C: Config + Environment> EventsDecoder<C>
+where
+    <C as Config>::AccountId: IntoVisitor + PartialEq,
+    C::Balance: Display + From<u128>,
+{
+    async fn display_block(
+        &self,
+        client: &OnlineClient<C>,
+        block: &Block<C, OnlineClient<C>>,
+    ) -> Result<()> {
+        let block_number: u64 = block.number().into();
+        let block_hash = block.hash();
+        let display_events = DisplayEvents::from_block_events::<C, C>(
+            &block.events().await?,
+            &self.filter,
+            self.transcoder.as_ref(),
+            &self.registry,
+            &client.metadata(),
+        )?;
+        if display_events.is_empty() {
+            return Ok(())
+        }
+
+        if self.output_json {
+            // Print every event as a separate JSON object annotated with its block.
+            let serde_json::Value::Array(events) = serde_json::to_value(&display_events)?
+            else {
+                unreachable!("events are serialized as a sequence")
+            };
+            for mut event in events {
+                event["block_number"] = serde_json::json!(block_number);
+                event["block_hash"] = serde_json::json!(block_hash);
+                println!("{}", serde_json::to_string(&event)?);
+            }
+        } else {
+            name_value_println!(
+                "Block",
+                format!("#{block_number} {block_hash:?}"),
+                DEFAULT_KEY_COL_WIDTH
+            );
+            let output = display_events
+                .display_events::<C>(self.verbosity, &self.token_metadata)?;
+            println!("{output}");
+        }
+        Ok(())
+    }
+}
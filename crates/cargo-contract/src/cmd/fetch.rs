@@ -0,0 +1,219 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::call_with_config;
+
+use super::{
+    parse_account,
+    BlockRef,
+    CLIChainOpts,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_build::code_hash;
+use contract_extrinsics::{
+    fetch_contract_info_at,
+    fetch_wasm_code_at,
+    url_to_string,
+    ErrorVariant,
+};
+use contract_metadata::{
+    CodeHash,
+    ContractMetadata,
+    SourceWasm,
+};
+use ink_env::Environment;
+use serde::Serialize;
+use std::{
+    fmt::{
+        Debug,
+        Display,
+    },
+    fs::File,
+    path::PathBuf,
+    str::FromStr,
+};
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::RpcClient,
+    },
+    ext::scale_decode::IntoVisitor,
+    Config,
+    OnlineClient,
+};
+
+/// Download the code of a contract and bundle it with its metadata into a `.contract`
+/// file.
+#[derive(Debug, clap::Args)]
+#[clap(name = "fetch")]
+pub struct FetchCommand {
+    /// The address of the contract to download the code of.
+    #[clap(name = "contract", long, env = "CONTRACT")]
+    contract: String,
+    /// Path to the metadata of the contract: either a `.json` metadata file or a
+    /// `.contract` bundle, e.g. the result of a verifiable build. Its `source.hash` must
+    /// match the code hash of the contract on chain.
+    #[clap(long, value_parser)]
+    metadata: PathBuf,
+    /// The file to write the bundle to, by default `<name>.contract` in the current
+    /// directory.
+    #[clap(short, long, value_parser)]
+    output: Option<PathBuf>,
+    /// Download the code as it was at the given block hash or number, instead of at the
+    /// best block.
+    #[clap(long)]
+    at: Option<BlockRef>,
+    /// Arguments required for communicating with a Substrate node.
+    #[clap(flatten)]
+    chain_cli_opts: CLIChainOpts,
+}
+
+impl FetchCommand {
+    pub async fn handle(&self) -> Result<(), ErrorVariant> {
+        call_with_config!(self, run, self.chain_cli_opts.chain().config())
+    }
+
+    pub async fn run<C: Config + Environment>(&self) -> Result<(), ErrorVariant>
+    where
+        <C as Config>::AccountId: Display + IntoVisitor + AsRef<[u8]> + FromStr,
+        <C as Config>::Hash: IntoVisitor + Display + From<[u8; 32]>,
+        <C as Environment>::Balance: Serialize + Debug + IntoVisitor,
+        <<C as Config>::AccountId as FromStr>::Err:
+            Into<Box<dyn std::error::Error>> + Display,
+    {
+        let metadata = ContractMetadata::load(&self.metadata)?;
+        let url = self.chain_cli_opts.chain().url();
+        let rpc_cli = RpcClient::from_url(url_to_string(&url)).await?;
+        let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
+        let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+        let block_hash = match self.at {
+            Some(ref at) => Some(at.block_hash::<C>(&url).await?),
+            None => None,
+        };
+
+        let contract = parse_account(&self.contract)?;
+        let info =
+            fetch_contract_info_at::<C, C>(&contract, &rpc, &client, block_hash).await?;
+        let code =
+            fetch_wasm_code_at(&client, &rpc, info.code_hash(), block_hash).await?;
+
+        let on_chain_hash = info
+            .code_hash()
+            .as_ref()
+            .try_into()
+            .context("The code hash of the contract should be 32 bytes in length")?;
+        let bundle = bundle_code(metadata, CodeHash(on_chain_hash), code)?;
+
+        let output = self.output.clone().unwrap_or_else(|| {
+            PathBuf::from(format!("{}.contract", bundle.contract.name))
+        });
+        let file = File::create(&output)
+            .context(format!("Failed to create bundle {}", output.display()))?;
+        serde_json::to_writer(file, &bundle)?;
+
+        eprintln!(
+            "{} the code of contract {} with hash {} to {}",
+            "Bundled".green().bold(),
+            self.contract,
+            bundle.source.hash,
+            output.display()
+        );
+        if let Some(ref image) = bundle.image {
+            eprintln!(
+                "{} the metadata comes from a verifiable build with image {image}",
+                "Note:".bold()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Bundle the on-chain `code` with the `metadata`, after checking that the code hash
+/// of the metadata and of the code match the on-chain code hash.
+fn bundle_code(
+    mut metadata: ContractMetadata,
+    on_chain_hash: CodeHash,
+    code: Vec<u8>,
+) -> Result<ContractMetadata> {
+    anyhow::ensure!(
+        metadata.source.hash == on_chain_hash,
+        "The code hash {} of the metadata does not match the code hash {} of the \
+         contract on chain",
+        metadata.source.hash,
+        on_chain_hash
+    );
+    let code_hash = CodeHash(code_hash(&code));
+    anyhow::ensure!(
+        code_hash == on_chain_hash,
+        "The hash {code_hash} of the downloaded code does not match the code hash \
+         {on_chain_hash} of the contract on chain"
+    );
+    metadata.source.wasm = Some(SourceWasm::new(code));
+    Ok(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_metadata::{
+        Contract,
+        Source,
+        SourceCompiler,
+        SourceLanguage,
+    };
+
+    fn metadata(hash: CodeHash) -> ContractMetadata {
+        let source = Source::new(
+            None,
+            hash,
+            SourceLanguage::from_str("ink! 5.0.0").unwrap(),
+            SourceCompiler::from_str("rustc 1.76.0").unwrap(),
+            None,
+        );
+        let contract = Contract::builder()
+            .name("flipper")
+            .version(semver::Version::new(0, 1, 0))
+            .authors(vec!["Use Ink <ink@use.ink>"])
+            .build()
+            .unwrap();
+        ContractMetadata::new(source, contract, None, None, Default::default())
+    }
+
+    #[test]
+    fn bundle_code_works() {
+        let code = vec![0u8, 97, 115, 109];
+        let hash = CodeHash(code_hash(&code));
+
+        let bundle = bundle_code(metadata(hash), hash, code.clone())
+            .expect("code shall be bundled");
+
+        assert_eq!(bundle.source.wasm, Some(SourceWasm::new(code)));
+    }
+
+    #[test]
+    fn bundle_code_fails_on_hash_mismatch() {
+        let code = vec![0u8, 97, 115, 109];
+        let hash = CodeHash(code_hash(&code));
+        let other_hash = CodeHash([1u8; 32]);
+
+        assert!(bundle_code(metadata(other_hash), hash, code.clone()).is_err());
+        assert!(bundle_code(metadata(other_hash), other_hash, code).is_err());
+    }
+}
@@ -18,21 +18,30 @@ use crate::call_with_config;
 
 use super::{
     basic_display_format_extended_contract_info,
-    display_all_contracts,
     parse_account,
+    parse_code_hash,
+    BlockRef,
     CLIChainOpts,
+    MAX_KEY_COL_WIDTH,
 };
 use anyhow::Result;
 use contract_analyze::determine_language;
+use contract_build::name_value_println;
 use contract_extrinsics::{
-    fetch_all_contracts,
-    fetch_contract_info,
-    fetch_wasm_code,
+    fetch_code_info_at,
+    fetch_contract_info_at,
+    fetch_contract_instantiation,
+    fetch_contracts_page_at,
+    fetch_wasm_code_at,
+    format_ss58,
     url_to_string,
+    CodeInfo,
     ContractInfo,
+    ContractInstantiation,
     ErrorVariant,
     TrieId,
 };
+use contract_metadata::Language;
 use ink_env::Environment;
 use serde::Serialize;
 use std::{
@@ -76,12 +85,47 @@ pub struct InfoCommand {
     /// Display all contracts addresses
     #[clap(name = "all", long)]
     all: bool,
+    /// Display at most this number of contracts.
+    #[clap(long, requires = "all")]
+    limit: Option<u32>,
+    /// Start listing after the contract with this address, e.g. the last address of a
+    /// previous page.
+    #[clap(long, requires = "all")]
+    start_after: Option<String>,
+    /// Only display contracts instantiated from the code with this hash.
+    #[clap(long, requires = "all")]
+    code_hash: Option<String>,
+    /// Only display contracts whose code was uploaded by this account, i.e. the owner
+    /// of the code. This is not necessarily the account which instantiated them.
+    #[clap(long, requires = "all")]
+    code_owner: Option<String>,
+    /// Only display contracts instantiated by this account. Searches the chain for the
+    /// instantiation of each contract, which requires an archive node and is slow.
+    #[clap(long, requires = "all")]
+    deployer: Option<String>,
+    /// Only display contracts written in this language, e.g. `ink!` or `Solidity`.
+    #[clap(long, requires = "all")]
+    language: Option<Language>,
+    /// Display the info of each contract in addition to its address.
+    #[clap(long, requires = "all")]
+    extended: bool,
+    /// Search the chain for the block in which the contract was instantiated and the
+    /// account which instantiated it. Requires an archive node.
+    #[clap(long, conflicts_with_all = ["all", "binary"])]
+    find_deployer: bool,
+    /// Display the info as it was at the given block hash or number, instead of at the
+    /// best block.
+    #[clap(long)]
+    at: Option<BlockRef>,
     /// Arguments required for communicating with a Substrate node.
     #[clap(flatten)]
     chain_cli_opts: CLIChainOpts,
 }
 
 impl InfoCommand {
+    /// The number of contract addresses fetched per request with `--all`.
+    const PAGE_SIZE: u32 = 100;
+
     pub async fn handle(&self) -> Result<(), ErrorVariant> {
         call_with_config!(self, run, self.chain_cli_opts.chain().config())
     }
@@ -90,7 +134,7 @@ impl InfoCommand {
     where
         <C as Config>::AccountId:
             Serialize + Display + IntoVisitor + Decode + AsRef<[u8]> + FromStr,
-        <C as Config>::Hash: IntoVisitor + Display,
+        <C as Config>::Hash: IntoVisitor + Display + From<[u8; 32]>,
         <C as Environment>::Balance: Serialize + Debug + IntoVisitor,
         <<C as Config>::AccountId as FromStr>::Err:
             Into<Box<(dyn std::error::Error)>> + Display,
@@ -100,19 +144,26 @@ impl InfoCommand {
                 .await?;
         let client = OnlineClient::<C>::from_rpc_client(rpc_cli.clone()).await?;
         let rpc = LegacyRpcMethods::<C>::new(rpc_cli.clone());
+        let ss58_prefix = self.chain_cli_opts.ss58_prefix::<C>().await?;
+        let block_hash = match self.at {
+            Some(ref at) => {
+                Some(
+                    at.block_hash::<C>(&self.chain_cli_opts.chain().url())
+                        .await?,
+                )
+            }
+            None => None,
+        };
 
         // All flag applied
         if self.all {
-            let contracts = fetch_all_contracts(&client, &rpc).await?;
-
-            if self.output_json {
-                let contracts_json = serde_json::json!({
-                    "contracts": contracts
-                });
-                println!("{}", serde_json::to_string_pretty(&contracts_json)?);
-            } else {
-                display_all_contracts(&contracts)
-            }
+            // Pin the best block, so that all pages are fetched from the same state.
+            let block_hash = match block_hash {
+                Some(block_hash) => Some(block_hash),
+                None => rpc.chain_get_block_hash(None).await?,
+            };
+            self.list_contracts(&client, &rpc, block_hash, ss58_prefix)
+                .await?;
             Ok(())
         } else {
             // Contract arg shall be always present in this case, it is enforced by
@@ -125,10 +176,12 @@ impl InfoCommand {
                 .expect("Contract argument shall be present");
 
             let info_to_json =
-                fetch_contract_info::<C, C>(&contract, &rpc, &client).await?;
+                fetch_contract_info_at::<C, C>(&contract, &rpc, &client, block_hash)
+                    .await?;
 
             let wasm_code =
-                fetch_wasm_code(&client, &rpc, info_to_json.code_hash()).await?;
+                fetch_wasm_code_at(&client, &rpc, info_to_json.code_hash(), block_hash)
+                    .await?;
             // Binary flag applied
             if self.binary {
                 if self.output_json {
@@ -141,45 +194,235 @@ impl InfoCommand {
                         .write_all(&wasm_code)
                         .expect("Writing to stdout failed")
                 }
-            } else if self.output_json {
-                println!(
-                    "{}",
-                    serde_json::to_string_pretty(&ExtendedContractInfo::<
-                        <C as Config>::Hash,
-                        C::Balance,
-                    >::new(
-                        info_to_json, &wasm_code
-                    ))?
-                )
             } else {
-                basic_display_format_extended_contract_info(&ExtendedContractInfo::<
-                    <C as Config>::Hash,
-                    C::Balance,
-                >::new(
-                    info_to_json, &wasm_code
-                ))
+                let code_info = fetch_code_info_at::<C, C>(
+                    &client,
+                    &rpc,
+                    info_to_json.code_hash(),
+                    block_hash,
+                )
+                .await?;
+                let mut info =
+                    ExtendedContractInfo::new(info_to_json, code_info, &wasm_code);
+                if self.find_deployer {
+                    info.instantiation = Some(
+                        fetch_contract_instantiation(
+                            &contract, &rpc, &client, block_hash,
+                        )
+                        .await?,
+                    );
+                }
+                let info = info.format_accounts(ss58_prefix);
+                if self.output_json {
+                    println!("{}", serde_json::to_string_pretty(&info)?)
+                } else {
+                    basic_display_format_extended_contract_info(&info)
+                }
             }
             Ok(())
         }
     }
+
+    /// Stream the addresses of the contracts matching the filters, fetching them page by
+    /// page.
+    async fn list_contracts<C: Config + Environment>(
+        &self,
+        client: &OnlineClient<C>,
+        rpc: &LegacyRpcMethods<C>,
+        block_hash: Option<<C as Config>::Hash>,
+        ss58_prefix: u16,
+    ) -> Result<()>
+    where
+        <C as Config>::AccountId:
+            Serialize + Display + IntoVisitor + Decode + AsRef<[u8]> + FromStr,
+        <C as Config>::Hash: IntoVisitor + Display + From<[u8; 32]>,
+        <C as Environment>::Balance: Serialize + Debug + IntoVisitor,
+        <<C as Config>::AccountId as FromStr>::Err: Display,
+    {
+        let mut start_after = self
+            .start_after
+            .as_ref()
+            .map(|c| parse_account::<<C as Config>::AccountId>(c))
+            .transpose()?;
+        let code_hash = self
+            .code_hash
+            .as_ref()
+            .map(|h| parse_code_hash::<<C as Config>::Hash>(h))
+            .transpose()?;
+        let code_owner = self
+            .code_owner
+            .as_ref()
+            .map(|d| parse_account::<<C as Config>::AccountId>(d))
+            .transpose()?;
+        let deployer = self
+            .deployer
+            .as_ref()
+            .map(|d| parse_account::<<C as Config>::AccountId>(d))
+            .transpose()?;
+        let needs_info = code_hash.is_some()
+            || code_owner.is_some()
+            || deployer.is_some()
+            || self.language.is_some()
+            || self.extended;
+        let mut remaining = self.limit;
+
+        while remaining != Some(0) {
+            let page = fetch_contracts_page_at(
+                client,
+                rpc,
+                start_after.as_ref(),
+                Self::PAGE_SIZE,
+                block_hash,
+            )
+            .await?;
+            for contract in &page {
+                if remaining == Some(0) {
+                    break
+                }
+                if !needs_info {
+                    self.display_contract::<C>(contract, None, ss58_prefix)?;
+                    remaining = remaining.map(|r| r - 1);
+                    continue
+                }
+
+                let info =
+                    fetch_contract_info_at::<C, C>(contract, rpc, client, block_hash)
+                        .await?;
+                if code_hash.is_some_and(|h| &h != info.code_hash()) {
+                    continue
+                }
+                let code_info = if code_owner.is_some() || self.extended {
+                    Some(
+                        fetch_code_info_at::<C, C>(
+                            client,
+                            rpc,
+                            info.code_hash(),
+                            block_hash,
+                        )
+                        .await?,
+                    )
+                } else {
+                    None
+                };
+                if let (Some(code_owner), Some(code_info)) = (&code_owner, &code_info) {
+                    if code_info.owner().as_ref() != code_owner.as_ref() {
+                        continue
+                    }
+                }
+                let code = if self.language.is_some() || self.extended {
+                    fetch_wasm_code_at(client, rpc, info.code_hash(), block_hash).await?
+                } else {
+                    Vec::new()
+                };
+                if let Some(ref language) = self.language {
+                    if determine_language(&code).ok().as_ref() != Some(language) {
+                        continue
+                    }
+                }
+                let instantiation = match deployer {
+                    Some(ref deployer) => {
+                        let instantiation = fetch_contract_instantiation(
+                            contract, rpc, client, block_hash,
+                        )
+                        .await?;
+                        if instantiation.deployer().map(AsRef::as_ref)
+                            != Some(deployer.as_ref())
+                        {
+                            continue
+                        }
+                        Some(instantiation)
+                    }
+                    None => None,
+                };
+                let info = code_info.filter(|_| self.extended).map(|code_info| {
+                    let mut info = ExtendedContractInfo::new(info, code_info, &code);
+                    info.instantiation = instantiation;
+                    info
+                });
+                self.display_contract::<C>(contract, info, ss58_prefix)?;
+                remaining = remaining.map(|r| r - 1);
+            }
+            match page.last() {
+                Some(last) if page.len() == Self::PAGE_SIZE as usize => {
+                    start_after = Some(last.clone())
+                }
+                _ => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Display a single contract of the list, as a JSON line with `--output-json`.
+    fn display_contract<C: Config + Environment>(
+        &self,
+        contract: &<C as Config>::AccountId,
+        info: Option<
+            ExtendedContractInfo<
+                <C as Config>::AccountId,
+                <C as Config>::Hash,
+                C::Balance,
+            >,
+        >,
+        ss58_prefix: u16,
+    ) -> Result<()>
+    where
+        <C as Config>::AccountId: Serialize + Display,
+        <C as Environment>::Balance: Serialize + Debug,
+    {
+        let contract = &format_ss58(contract, Some(ss58_prefix));
+        let info = info.map(|info| info.format_accounts(ss58_prefix));
+        if self.output_json {
+            let entry = ContractListEntry { contract, info };
+            println!("{}", serde_json::to_string(&entry)?);
+        } else {
+            match info {
+                Some(info) => {
+                    name_value_println!("Contract", contract, MAX_KEY_COL_WIDTH);
+                    basic_display_format_extended_contract_info(&info);
+                    println!();
+                }
+                None => println!("{contract}"),
+            }
+        }
+        Ok(())
+    }
 }
 
+/// A contract of the `--all` list, with its info if `--extended` is specified.
 #[derive(serde::Serialize)]
-pub struct ExtendedContractInfo<Hash, Balance> {
+struct ContractListEntry<'a, AccountId, Hash, Balance> {
+    contract: &'a AccountId,
+    #[serde(flatten)]
+    info: Option<ExtendedContractInfo<AccountId, Hash, Balance>>,
+}
+
+#[derive(serde::Serialize)]
+pub struct ExtendedContractInfo<AccountId, Hash, Balance> {
     pub trie_id: TrieId,
     pub code_hash: Hash,
     pub storage_items: u32,
     pub storage_items_deposit: Balance,
     pub storage_total_deposit: Balance,
+    pub deposit_account: AccountId,
+    pub code_owner: AccountId,
+    pub code_deposit: Balance,
+    pub code_refcount: u64,
     pub source_language: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instantiation: Option<ContractInstantiation<AccountId, Hash>>,
 }
 
-impl<Hash, Balance> ExtendedContractInfo<Hash, Balance>
+impl<AccountId, Hash, Balance> ExtendedContractInfo<AccountId, Hash, Balance>
 where
+    AccountId: Clone,
     Hash: serde::Serialize + Copy,
     Balance: serde::Serialize + Copy,
 {
-    pub fn new(contract_info: ContractInfo<Hash, Balance>, code: &[u8]) -> Self {
+    pub fn new(
+        contract_info: ContractInfo<Hash, Balance, AccountId>,
+        code_info: CodeInfo<AccountId, Balance>,
+        code: &[u8],
+    ) -> Self {
         let language = match determine_language(code).ok() {
             Some(lang) => lang.to_string(),
             None => "Unknown".to_string(),
@@ -190,7 +433,40 @@ where
             storage_items: contract_info.storage_items(),
             storage_items_deposit: contract_info.storage_items_deposit(),
             storage_total_deposit: contract_info.storage_total_deposit(),
+            deposit_account: contract_info.deposit_account().clone(),
+            code_owner: code_info.owner().clone(),
+            code_deposit: code_info.deposit(),
+            code_refcount: code_info.refcount(),
             source_language: language,
+            instantiation: None,
+        }
+    }
+}
+
+impl<AccountId, Hash, Balance> ExtendedContractInfo<AccountId, Hash, Balance>
+where
+    AccountId: Display,
+{
+    /// Format the accounts as SS58 addresses with the given address format prefix.
+    pub fn format_accounts(
+        self,
+        ss58_prefix: u16,
+    ) -> ExtendedContractInfo<String, Hash, Balance> {
+        let format = |account: &AccountId| format_ss58(account, Some(ss58_prefix));
+        ExtendedContractInfo {
+            trie_id: self.trie_id,
+            code_hash: self.code_hash,
+            storage_items: self.storage_items,
+            storage_items_deposit: self.storage_items_deposit,
+            storage_total_deposit: self.storage_total_deposit,
+            deposit_account: format(&self.deposit_account),
+            code_owner: format(&self.code_owner),
+            code_deposit: self.code_deposit,
+            code_refcount: self.code_refcount,
+            source_language: self.source_language,
+            instantiation: self
+                .instantiation
+                .map(|instantiation| instantiation.map_deployer(|d| format(&d))),
         }
     }
 }
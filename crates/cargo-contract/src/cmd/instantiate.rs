@@ -19,10 +19,15 @@ use super::{
     display_contract_exec_result,
     display_contract_exec_result_debug,
     display_dry_run_result_warning,
+    display_fee_estimate,
+    display_multisig_details,
+    display_multisig_status,
+    interactive::Prompt,
     parse_balance,
     print_dry_running_status,
     print_gas_required_success,
     prompt_confirm_tx,
+    CLIArgsOpts,
     CLIExtrinsicOpts,
     MAX_KEY_COL_WIDTH,
 };
@@ -44,13 +49,16 @@ use contract_build::{
     Verbosity,
 };
 use contract_extrinsics::{
+    format_ss58,
     Code,
     DisplayEvents,
     ExtrinsicOptsBuilder,
     InstantiateCommandBuilder,
     InstantiateDryRunResult,
     InstantiateExecResult,
+    MultisigStatus,
     TokenMetadata,
+    TrySigner,
 };
 use ink_env::Environment;
 use serde::Serialize;
@@ -77,12 +85,19 @@ use subxt::{
 
 #[derive(Debug, clap::Args)]
 pub struct InstantiateCommand {
-    /// The name of the contract constructor to call
-    #[clap(name = "constructor", long, default_value = "new")]
-    constructor: String,
-    /// The constructor arguments, encoded as strings
-    #[clap(long, num_args = 0..)]
-    args: Vec<String>,
+    /// The name of the contract constructor to call, `new` if omitted unless
+    /// `--interactive`.
+    #[clap(name = "constructor", long)]
+    constructor: Option<String>,
+    #[clap(flatten)]
+    args_opts: CLIArgsOpts,
+    /// Prompt for each argument of the constructor, validated against its type, and for
+    /// the constructor itself if `--constructor` is omitted.
+    #[clap(
+        long,
+        conflicts_with_all = ["args", "args_file", "named_args", "output_json"]
+    )]
+    interactive: bool,
     #[clap(flatten)]
     extrinsic_cli_opts: CLIExtrinsicOpts,
     /// Transfers an initial balance to the instantiated contract
@@ -130,17 +145,22 @@ impl InstantiateCommand {
         &self,
     ) -> Result<(), ErrorVariant>
     where
-        <C as SignerConfig<C>>::Signer: subxt::tx::Signer<C> + Clone + FromStr,
-        <C as Config>::AccountId: IntoVisitor + FromStr + EncodeAsType + Decode + Display,
+        <C as SignerConfig<C>>::Signer: TrySigner<C> + Clone + FromStr,
+        <C as Config>::AccountId:
+            IntoVisitor + FromStr + EncodeAsType + Decode + Ord + Display + Serialize,
         <<C as Config>::AccountId as FromStr>::Err: Display,
+        <C as Config>::AssetId: FromStr,
+        <<C as Config>::AssetId as FromStr>::Err: Display,
         C::Balance:
             From<u128> + Display + Default + FromStr + Serialize + Debug + EncodeAsType,
         <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
             From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
         <C as Config>::Hash: From<[u8; 32]> + IntoVisitor + EncodeAsType,
     {
-        let signer = C::Signer::from_str(&self.extrinsic_cli_opts.suri)
-            .map_err(|_| anyhow::anyhow!("Failed to parse suri option"))?;
+        let signer: C::Signer = self.extrinsic_cli_opts.signer()?;
+        self.extrinsic_cli_opts
+            .ensure_multisig_weight(self.gas_limit, self.proof_size)?;
+        let multisig = self.extrinsic_cli_opts.multisig(&signer.account_id())?;
         let chain = self.extrinsic_cli_opts.chain_cli_opts.chain();
         let token_metadata = TokenMetadata::query::<C>(&chain.url()).await?;
 
@@ -155,17 +175,48 @@ impl InstantiateCommand {
             })?;
         let value = parse_balance(&self.value, &token_metadata)
             .map_err(|e| anyhow::anyhow!("Failed to parse value option: {}", e))?;
+        let args_format = self.args_opts.args_format;
+        let transcoder = self.extrinsic_cli_opts.contract_transcoder()?;
+        let (constructor, args, call_data) = if self.interactive {
+            let prompt = Prompt::new(&transcoder, args_format);
+            let constructor = match self.constructor {
+                Some(ref constructor) => constructor.clone(),
+                None => prompt.select_constructor()?,
+            };
+            let args = prompt.constructor_args(&constructor)?;
+            let call_data =
+                transcoder.encode_with_format(&constructor, &args, args_format)?;
+            (constructor, args, call_data)
+        } else {
+            let constructor = self.constructor.as_deref().unwrap_or("new").to_string();
+            let call_data = self.args_opts.encode(&transcoder, &constructor)?;
+            (constructor, self.args_opts.args.clone(), call_data)
+        };
         let extrinsic_opts = ExtrinsicOptsBuilder::new(signer)
             .file(self.extrinsic_cli_opts.file.clone())
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
             .url(chain.url())
             .storage_deposit_limit(storage_deposit_limit)
+            .multisig(multisig)
+            .proxy_for(self.extrinsic_cli_opts.proxy_for()?)
+            .nonce(self.extrinsic_cli_opts.nonce)
+            .tip(self.extrinsic_cli_opts.tip(&token_metadata)?)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .fee_asset(self.extrinsic_cli_opts.fee_asset()?)
+            .custom_types(self.extrinsic_cli_opts.custom_types()?)
+            .ss58_prefix(Some(
+                self.extrinsic_cli_opts
+                    .chain_cli_opts
+                    .ss58_prefix::<C>()
+                    .await?,
+            ))
             .done();
 
         let instantiate_exec: InstantiateExec<C, C, _> =
             InstantiateCommandBuilder::new(extrinsic_opts)
-                .constructor(self.constructor.clone())
-                .args(self.args.clone())
+                .constructor(constructor)
+                .args(args)
+                .call_data(call_data)
                 .value(value)
                 .gas_limit(self.gas_limit)
                 .proof_size(self.proof_size)
@@ -216,6 +267,7 @@ impl InstantiateCommand {
             )
             .await?;
             if !self.extrinsic_cli_opts.skip_confirm {
+                let fee = instantiate_exec.estimate_fee(gas_limit).await;
                 prompt_confirm_tx(|| {
                     print_default_instantiate_preview(&instantiate_exec, gas_limit);
                     if let Code::Existing(code_hash) =
@@ -227,6 +279,21 @@ impl InstantiateCommand {
                             DEFAULT_KEY_COL_WIDTH
                         );
                     }
+                    display_fee_estimate(
+                        &fee,
+                        &token_metadata,
+                        instantiate_exec.opts().fee_asset(),
+                    );
+                    if let Some(real) = instantiate_exec.opts().proxy_for() {
+                        name_value_println!(
+                            "Proxy for",
+                            real.to_string(),
+                            DEFAULT_KEY_COL_WIDTH
+                        );
+                    }
+                    if let Some(multisig) = instantiate_exec.opts().multisig() {
+                        display_multisig_details(multisig);
+                    }
                 })?;
             }
             let instantiate_result =
@@ -253,8 +320,8 @@ async fn pre_submit_dry_run_gas_estimate_instantiate<
     skip_dry_run: bool,
 ) -> Result<Weight>
 where
-    C::Signer: subxt::tx::Signer<C> + Clone,
-    <C as Config>::AccountId: IntoVisitor + Display + Decode,
+    C::Signer: TrySigner<C> + Clone,
+    <C as Config>::AccountId: IntoVisitor + EncodeAsType + Display + Decode + Serialize,
     <C as Config>::Hash: IntoVisitor + EncodeAsType,
     C::Balance: Serialize + Debug + EncodeAsType,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
@@ -310,7 +377,7 @@ where
 }
 
 /// Displays the results of contract instantiation, including contract address,
-/// events, and optional code hash.
+/// events, optional code hash and the state of the multisig operation.
 pub async fn display_result<C: Config + Environment + SignerConfig<C>>(
     instantiate_exec: &InstantiateExec<C, C, C::Signer>,
     instantiate_exec_result: InstantiateExecResult<C>,
@@ -319,7 +386,8 @@ pub async fn display_result<C: Config + Environment + SignerConfig<C>>(
     verbosity: Verbosity,
 ) -> Result<(), ErrorVariant>
 where
-    <C as Config>::AccountId: IntoVisitor + EncodeAsType + Display + Decode,
+    <C as Config>::AccountId:
+        IntoVisitor + EncodeAsType + Display + Decode + PartialEq + Serialize,
     <C as Config>::Hash: IntoVisitor + EncodeAsType,
     C::Balance: Serialize + From<u128> + Display + EncodeAsType,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
@@ -330,13 +398,27 @@ where
         Some(instantiate_exec.transcoder()),
         &instantiate_exec.client().metadata(),
     )?;
-    let contract_address = instantiate_exec_result.contract_address.to_string();
+    let contract_address = instantiate_exec_result
+        .contract_address
+        .as_ref()
+        .map(|address| format_ss58(address, instantiate_exec.opts().ss58_prefix()));
+    let multisig = match instantiate_exec.opts().multisig() {
+        Some(multisig) => {
+            Some(
+                multisig
+                    .status(instantiate_exec.client(), &instantiate_exec_result.events)
+                    .await?,
+            )
+        }
+        None => None,
+    };
     if output_json {
         let display_instantiate_result = InstantiateResult {
             code_hash: instantiate_exec_result
                 .code_hash
                 .map(|ch| format!("{ch:?}")),
-            contract: Some(contract_address),
+            contract: contract_address,
+            multisig,
             events,
         };
         println!("{}", display_instantiate_result.to_json()?)
@@ -345,7 +427,12 @@ where
         if let Some(code_hash) = instantiate_exec_result.code_hash {
             name_value_println!("Code hash", format!("{code_hash:?}"));
         }
-        name_value_println!("Contract", contract_address);
+        if let Some(contract_address) = contract_address {
+            name_value_println!("Contract", contract_address);
+        }
+        if let Some(status) = multisig {
+            display_multisig_status(&status);
+        }
     };
     Ok(())
 }
@@ -354,8 +441,8 @@ pub fn print_default_instantiate_preview<C: Config + Environment + SignerConfig<
     instantiate_exec: &InstantiateExec<C, C, C::Signer>,
     gas_limit: Weight,
 ) where
-    C::Signer: subxt::tx::Signer<C> + Clone,
-    <C as Config>::AccountId: IntoVisitor + EncodeAsType + Display + Decode,
+    C::Signer: TrySigner<C> + Clone,
+    <C as Config>::AccountId: IntoVisitor + EncodeAsType + Display + Decode + Serialize,
     <C as Config>::Hash: IntoVisitor + EncodeAsType,
     C::Balance: Serialize + EncodeAsType,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
@@ -376,18 +463,21 @@ pub fn print_default_instantiate_preview<C: Config + Environment + SignerConfig<
 
 /// Result of a successful contract instantiation for displaying.
 #[derive(serde::Serialize)]
-pub struct InstantiateResult {
+pub struct InstantiateResult<AccountId> {
     /// Instantiated contract hash
     #[serde(skip_serializing_if = "Option::is_none")]
     pub contract: Option<String>,
     /// Instantiated code hash
     #[serde(skip_serializing_if = "Option::is_none")]
     pub code_hash: Option<String>,
+    /// The state of the multisig operation, if submitted for a multisig account.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig: Option<MultisigStatus<AccountId>>,
     /// The events emitted from the instantiate extrinsic invocation.
     pub events: DisplayEvents,
 }
 
-impl InstantiateResult {
+impl<AccountId: Serialize> InstantiateResult<AccountId> {
     pub fn to_json(&self) -> Result<String> {
         Ok(serde_json::to_string_pretty(self)?)
     }
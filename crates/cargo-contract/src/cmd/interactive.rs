@@ -0,0 +1,319 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Prompts for `--interactive` calls and instantiations, driven by the contract
+//! metadata.
+
+use anyhow::{
+    anyhow,
+    Result,
+};
+use colored::Colorize as _;
+use contract_extrinsics::{
+    ArgsFormat,
+    ContractMessageTranscoder,
+};
+use contract_transcode::ink_metadata::MessageParamSpec;
+use scale_info::form::PortableForm;
+use std::io::{
+    self,
+    BufRead,
+    Write,
+};
+
+/// A constructor or message to select from.
+struct Entry<'a> {
+    label: &'a str,
+    docs: &'a [String],
+    tags: Vec<&'static str>,
+}
+
+/// Prompts on stdin and stdout for the constructor or message to call and its arguments.
+pub struct Prompt<'a> {
+    transcoder: &'a ContractMessageTranscoder,
+    format: ArgsFormat,
+}
+
+impl<'a> Prompt<'a> {
+    pub fn new(transcoder: &'a ContractMessageTranscoder, format: ArgsFormat) -> Self {
+        Self { transcoder, format }
+    }
+
+    /// List the messages of the contract and prompt for the one to call.
+    pub fn select_message(&self) -> Result<String> {
+        let entries = self
+            .transcoder
+            .metadata()
+            .spec()
+            .messages()
+            .iter()
+            .map(|message| {
+                let mut tags = vec![
+                    if message.mutates() {
+                        "mutates"
+                    } else {
+                        "read-only"
+                    },
+                ];
+                if message.payable() {
+                    tags.push("payable")
+                }
+                Entry {
+                    label: message.label(),
+                    docs: message.docs(),
+                    tags,
+                }
+            })
+            .collect::<Vec<_>>();
+        select(
+            "Messages",
+            &entries,
+            &mut io::stdin().lock(),
+            &mut io::stdout(),
+        )
+    }
+
+    /// List the constructors of the contract and prompt for the one to call.
+    pub fn select_constructor(&self) -> Result<String> {
+        let entries = self
+            .transcoder
+            .metadata()
+            .spec()
+            .constructors()
+            .iter()
+            .map(|constructor| {
+                Entry {
+                    label: constructor.label(),
+                    docs: constructor.docs(),
+                    tags: if *constructor.payable() {
+                        vec!["payable"]
+                    } else {
+                        Vec::new()
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        select(
+            "Constructors",
+            &entries,
+            &mut io::stdin().lock(),
+            &mut io::stdout(),
+        )
+    }
+
+    /// Prompt for each argument of the message with the given name, validating each
+    /// value right away against the argument type. Prints the encoded call data and
+    /// returns the arguments.
+    pub fn message_args(&self, name: &str) -> Result<Vec<String>> {
+        let message = self
+            .transcoder
+            .metadata()
+            .spec()
+            .messages()
+            .iter()
+            .find(|message| message.label() == name)
+            .ok_or_else(|| anyhow!("No message with the name '{name}' found"))?;
+        self.args(name, message.args())
+    }
+
+    /// Prompt for each argument of the constructor with the given name, validating
+    /// each value right away against the argument type. Prints the encoded call data
+    /// and returns the arguments.
+    pub fn constructor_args(&self, name: &str) -> Result<Vec<String>> {
+        let constructor = self
+            .transcoder
+            .metadata()
+            .spec()
+            .constructors()
+            .iter()
+            .find(|constructor| constructor.label() == name)
+            .ok_or_else(|| anyhow!("No constructor with the name '{name}' found"))?;
+        self.args(name, constructor.args())
+    }
+
+    fn args(
+        &self,
+        name: &str,
+        spec_args: &[MessageParamSpec<PortableForm>],
+    ) -> Result<Vec<String>> {
+        let (mut input, mut output) = (io::stdin().lock(), io::stdout());
+        let args = spec_args
+            .iter()
+            .map(|arg| {
+                let type_id = arg.ty().ty().id;
+                let prompt = format!(
+                    "{} ({})",
+                    arg.label().bold(),
+                    self.transcoder.type_name(type_id).cyan()
+                );
+                prompt_value(&prompt, &mut input, &mut output, |value| {
+                    self.transcoder
+                        .encode_value_with_format(type_id, value, self.format)
+                        .map(|_| ())
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let call_data = self
+            .transcoder
+            .encode_with_format(name, &args, self.format)?;
+        println!(
+            "{} 0x{}",
+            "Encoded call:".bright_green().bold(),
+            hex::encode(call_data)
+        );
+        Ok(args)
+    }
+}
+
+/// List the `entries` and prompt for one of them, by its number or its label.
+fn select<R: BufRead, W: Write>(
+    title: &str,
+    entries: &[Entry],
+    input: &mut R,
+    output: &mut W,
+) -> Result<String> {
+    writeln!(output, "{}", format!("{title}:").bright_white().bold())?;
+    for (i, entry) in entries.iter().enumerate() {
+        let tags = if entry.tags.is_empty() {
+            String::new()
+        } else {
+            format!(" [{}]", entry.tags.join(", "))
+        };
+        writeln!(
+            output,
+            "{:>4}. {}{}",
+            i + 1,
+            entry.label.bold(),
+            tags.dimmed()
+        )?;
+        for line in entry.docs.iter().map(|line| line.trim()) {
+            if !line.is_empty() {
+                writeln!(output, "      {line}")?;
+            }
+        }
+    }
+    let prompt = format!("Select 1-{} or a name", entries.len());
+    prompt_value(&prompt, input, output, |choice| {
+        entry_by_choice(entries, choice).map(|_| ())
+    })
+    .map(|choice| {
+        entry_by_choice(entries, &choice)
+            .expect("the choice was validated")
+            .to_string()
+    })
+}
+
+/// Returns the label of the entry with the given number or label.
+fn entry_by_choice<'a>(entries: &[Entry<'a>], choice: &str) -> Result<&'a str> {
+    let entry = match choice.parse::<usize>() {
+        Ok(n) => n.checked_sub(1).and_then(|i| entries.get(i)),
+        Err(_) => entries.iter().find(|entry| entry.label == choice),
+    };
+    entry
+        .map(|entry| entry.label)
+        .ok_or_else(|| anyhow!("No entry '{choice}'"))
+}
+
+/// Prompt for a value until it passes `validate`, printing the validation errors.
+fn prompt_value<R, W, F>(
+    prompt: &str,
+    input: &mut R,
+    output: &mut W,
+    validate: F,
+) -> Result<String>
+where
+    R: BufRead,
+    W: Write,
+    F: Fn(&str) -> Result<()>,
+{
+    loop {
+        write!(output, "{prompt}: ")?;
+        output.flush()?;
+        let mut buf = String::new();
+        if input.read_line(&mut buf)? == 0 {
+            anyhow::bail!("Aborted, no more input")
+        }
+        let value = buf.trim();
+        match validate(value) {
+            Ok(()) => return Ok(value.to_string()),
+            Err(err) => writeln!(output, "{} {err:#}", "Invalid:".red().bold())?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<Entry<'static>> {
+        vec![
+            Entry {
+                label: "flip",
+                docs: &[],
+                tags: vec!["mutates"],
+            },
+            Entry {
+                label: "get",
+                docs: &[],
+                tags: vec!["read-only"],
+            },
+        ]
+    }
+
+    #[test]
+    fn select_by_number_or_label() {
+        let mut output = Vec::new();
+
+        let by_number = select("Messages", &entries(), &mut &b"2\n"[..], &mut output);
+        let by_label = select("Messages", &entries(), &mut &b"flip\n"[..], &mut output);
+
+        assert_eq!(by_number.unwrap(), "get");
+        assert_eq!(by_label.unwrap(), "flip");
+    }
+
+    #[test]
+    fn prompt_again_until_valid() {
+        let mut output = Vec::new();
+
+        let value =
+            prompt_value("value", &mut &b"x\n0\n3\n"[..], &mut output, positive).unwrap();
+
+        assert_eq!(value, "3");
+        assert_eq!(
+            String::from_utf8(output)
+                .unwrap()
+                .matches("Invalid")
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn prompt_fails_at_end_of_input() {
+        let mut output = Vec::new();
+
+        let value = prompt_value("value", &mut &b"x\n"[..], &mut output, positive);
+
+        assert!(value.is_err());
+    }
+
+    fn positive(value: &str) -> Result<()> {
+        match value.parse::<u32>() {
+            Ok(n) if n > 0 => Ok(()),
+            _ => Err(anyhow!("expected a positive number")),
+        }
+    }
+}
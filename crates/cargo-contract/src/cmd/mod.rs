@@ -15,17 +15,23 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 mod config;
+mod interactive;
 mod prod_chains;
+mod signer;
 
 pub mod build;
 pub mod call;
+pub mod codegen;
 pub mod decode;
 pub mod encode;
+pub mod events;
+pub mod fetch;
 pub mod info;
 pub mod instantiate;
 pub mod remove;
 pub mod rpc;
 pub mod schema;
+pub mod snapshot;
 pub mod storage;
 pub mod upload;
 pub mod verify;
@@ -36,7 +42,10 @@ pub(crate) use self::{
         CheckCommand,
     },
     call::CallCommand,
+    codegen::CodegenCommand,
     decode::DecodeCommand,
+    events::EventsCommand,
+    fetch::FetchCommand,
     info::{
         ExtendedContractInfo,
         InfoCommand,
@@ -49,11 +58,16 @@ pub(crate) use self::{
         GenerateSchemaCommand,
         VerifySchemaCommand,
     },
+    snapshot::SnapshotCommand,
     storage::StorageCommand,
     upload::UploadCommand,
     verify::VerifyCommand,
 };
 
+use self::signer::{
+    FromSignerSource,
+    SignerSource,
+};
 use crate::{
     anyhow,
     PathBuf,
@@ -73,9 +87,29 @@ use contract_build::{
 pub(crate) use contract_extrinsics::ErrorVariant;
 use contract_extrinsics::{
     pallet_contracts_primitives::ContractResult,
+    query_ss58_prefix,
+    url_to_string,
+    ArgsFormat,
     BalanceVariant,
+    ContractArtifacts,
+    ContractMessageTranscoder,
+    CustomTypes,
+    Multisig,
+    MultisigStatus,
+    Renderer,
     TokenMetadata,
 };
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::RpcClient,
+    },
+    ext::codec::{
+        Decode,
+        Encode,
+    },
+    Config,
+};
 
 use std::{
     fmt::{
@@ -84,13 +118,16 @@ use std::{
     },
     io::{
         self,
+        Read,
         Write,
     },
+    path::Path,
     str::FromStr,
 };
 
 /// Arguments required for creating and sending an extrinsic to a Substrate node.
 #[derive(Clone, Debug, clap::Args)]
+#[clap(group(clap::ArgGroup::new("signer").required(true).multiple(false)))]
 pub struct CLIExtrinsicOpts {
     /// Path to a contract build artifact file: a raw `.wasm` file, a `.contract` bundle,
     /// or a `.json` metadata file.
@@ -104,8 +141,29 @@ pub struct CLIExtrinsicOpts {
     /// e.g.
     /// - for a dev account "//Alice"
     /// - with a password "//Alice///SECRET_PASSWORD"
-    #[clap(name = "suri", long, short)]
-    suri: String,
+    #[clap(name = "suri", long, short, group = "signer")]
+    suri: Option<String>,
+    /// Name of an environment variable holding the secret key URI of the account
+    /// deploying the contract.
+    #[clap(long, group = "signer")]
+    suri_env: Option<String>,
+    /// Path to a file holding the secret key URI of the account deploying the
+    /// contract.
+    #[clap(long, value_parser, group = "signer")]
+    suri_file: Option<PathBuf>,
+    /// Path to a JSON keystore file exported from Polkadot-JS. The password is prompted
+    /// for.
+    #[clap(long, value_parser, group = "signer")]
+    keystore: Option<PathBuf>,
+    /// External program to delegate signing to, e.g. a bridge to a hardware security
+    /// module.
+    ///
+    /// The program is invoked with `public` to print the hex encoded public key of the
+    /// signing account, and with `sign` to read a hex encoded payload from stdin and
+    /// print the hex encoded signature. The command is split into the program and its
+    /// arguments following shell quoting rules.
+    #[clap(long, group = "signer")]
+    signer_command: Option<String>,
     #[clap(flatten)]
     verbosity: VerbosityFlags,
     /// Submit the extrinsic for on-chain execution.
@@ -121,9 +179,39 @@ pub struct CLIExtrinsicOpts {
     /// Before submitting a transaction, do not ask the user for confirmation.
     #[clap(short('y'), long)]
     skip_confirm: bool,
+    /// Number of approvals required to dispatch the extrinsic on behalf of the multisig
+    /// account.
+    #[clap(long, requires = "multisig_signatories")]
+    multisig_threshold: Option<u16>,
+    /// Comma separated accounts of all signatories of the multisig account, including
+    /// the signer.
+    #[clap(long, value_delimiter = ',', requires = "multisig_threshold")]
+    multisig_signatories: Vec<String>,
+    /// Account on behalf of which the extrinsic is submitted via `Proxy::proxy`. The
+    /// signer must be registered as a proxy of that account.
+    #[clap(long)]
+    proxy_for: Option<String>,
+    /// Nonce of the extrinsic, instead of querying it from the node. Use to submit
+    /// several extrinsics from the same account before the previous ones are included
+    /// in a block.
+    #[clap(long)]
+    nonce: Option<u64>,
+    /// Tip paid to the block author to increase the priority of the extrinsic.
+    #[clap(long)]
+    tip: Option<String>,
+    /// Number of blocks after which the extrinsic expires, instead of being valid
+    /// forever.
+    #[clap(long)]
+    mortality: Option<u64>,
+    /// Id of the asset to pay the fees and the tip in via `ChargeAssetTxPayment`,
+    /// instead of the native token.
+    #[clap(long)]
+    fee_asset: Option<String>,
     /// Arguments required for communicating with a Substrate node.
     #[clap(flatten)]
     chain_cli_opts: CLIChainOpts,
+    #[clap(flatten)]
+    custom_types_cli_opts: CLICustomTypesOpts,
 }
 
 impl CLIExtrinsicOpts {
@@ -131,11 +219,151 @@ impl CLIExtrinsicOpts {
     pub fn verbosity(&self) -> Result<Verbosity> {
         TryFrom::try_from(&self.verbosity)
     }
+
+    /// Returns the source of the signing key.
+    pub fn signer_source(&self) -> SignerSource {
+        if let Some(suri) = &self.suri {
+            SignerSource::Suri(suri.clone())
+        } else if let Some(var) = &self.suri_env {
+            SignerSource::SuriEnv(var.clone())
+        } else if let Some(path) = &self.suri_file {
+            SignerSource::SuriFile(path.clone())
+        } else if let Some(path) = &self.keystore {
+            SignerSource::Keystore(path.clone())
+        } else if let Some(command) = &self.signer_command {
+            SignerSource::External(command.clone())
+        } else {
+            unreachable!("clap requires one of the signer arguments")
+        }
+    }
+
+    /// Loads the signer from the source specified on the command line.
+    pub fn signer<Signer: FromSignerSource>(&self) -> Result<Signer> {
+        Signer::from_source(&self.signer_source())
+    }
+
+    /// Returns an error if the extrinsic is submitted for a multisig account without a
+    /// fixed `gas_limit` and `proof_size`. The gas limit is part of the call which
+    /// every signatory approves, so it must not depend on each signatory's dry-run.
+    pub fn ensure_multisig_weight(
+        &self,
+        gas_limit: Option<u64>,
+        proof_size: Option<u64>,
+    ) -> Result<()> {
+        if self.multisig_threshold.is_some()
+            && (gas_limit.is_none() || proof_size.is_none())
+        {
+            anyhow::bail!(
+                "--gas and --proof-size are required with --multisig-threshold, so that \
+                all signatories approve the same call"
+            )
+        }
+        Ok(())
+    }
+
+    /// Returns the multisig account the extrinsic is submitted for, if specified.
+    pub fn multisig<AccountId>(
+        &self,
+        signer: &AccountId,
+    ) -> Result<Option<Multisig<AccountId>>>
+    where
+        AccountId: FromStr + Encode + Decode + Ord + Clone,
+        <AccountId as FromStr>::Err: Display,
+    {
+        let Some(threshold) = self.multisig_threshold else {
+            return Ok(None)
+        };
+        let signatories = self
+            .multisig_signatories
+            .iter()
+            .map(|s| parse_account(s))
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| anyhow!("Failed to parse multisig_signatories option: {e}"))?;
+        Ok(Some(Multisig::new(threshold, signatories, signer)?))
+    }
+
+    /// Loads the transcoder of the contract artifacts specified on the command line.
+    pub fn contract_transcoder(&self) -> Result<ContractMessageTranscoder> {
+        let transcoder = ContractArtifacts::from_manifest_or_file(
+            self.manifest_path.as_ref(),
+            self.file.as_ref(),
+        )?
+        .contract_transcoder()?;
+        self.custom_types_cli_opts.apply(transcoder)
+    }
+
+    /// Loads the custom types configuration specified on the command line, if any.
+    pub fn custom_types(&self) -> Result<Option<CustomTypes>> {
+        self.custom_types_cli_opts.load()
+    }
+
+    /// Returns the tip paid to the block author, if specified.
+    ///
+    /// With a `--fee-asset` the tip is paid in the asset, so it must be given as a raw
+    /// integer, as the native token's decimals don't apply to it.
+    pub fn tip(&self, token_metadata: &TokenMetadata) -> Result<Option<u128>> {
+        self.tip
+            .as_deref()
+            .map(|tip| {
+                if self.fee_asset.is_none() {
+                    return parse_balance(tip, token_metadata)
+                }
+                match BalanceVariant::<u128>::from_str(tip)? {
+                    BalanceVariant::Default(tip) => Ok(tip),
+                    BalanceVariant::Denominated(_) => {
+                        Err(anyhow!(
+                            "the tip is paid in the fee asset, so it must be given as \
+                            a raw integer without a token symbol"
+                        ))
+                    }
+                }
+            })
+            .transpose()
+            .map_err(|e| anyhow!("Failed to parse tip option: {e}"))
+    }
+
+    /// Returns the asset to pay the fees in, if specified.
+    pub fn fee_asset<AssetId>(&self) -> Result<Option<AssetId>>
+    where
+        AssetId: FromStr,
+        <AssetId as FromStr>::Err: Display,
+    {
+        self.fee_asset
+            .as_deref()
+            .map(AssetId::from_str)
+            .transpose()
+            .map_err(|e| anyhow!("Failed to parse fee_asset option: {e}"))
+    }
+
+    /// Returns the account the extrinsic is submitted on behalf of, if specified.
+    pub fn proxy_for<AccountId>(&self) -> Result<Option<AccountId>>
+    where
+        AccountId: FromStr,
+        <AccountId as FromStr>::Err: Display,
+    {
+        self.proxy_for
+            .as_deref()
+            .map(parse_account)
+            .transpose()
+            .map_err(|e| anyhow!("Failed to parse proxy_for option: {e}"))
+    }
 }
 
 /// Arguments required for communicating with a Substrate node.
 #[derive(Clone, Debug, clap::Args)]
 pub struct CLIChainOpts {
+    #[clap(flatten)]
+    node: CLINodeOpts,
+    /// SS58 address format prefix the accounts are displayed with, instead of the
+    /// `ss58Format` of the node.
+    #[clap(long, value_parser = clap::value_parser!(u16).range(..16_384))]
+    ss58_prefix: Option<u16>,
+}
+
+/// Arguments for connecting to a Substrate node, for commands which define how the
+/// accounts are displayed themselves.
+#[derive(Clone, Debug, clap::Args)]
+pub struct CLINodeOpts {
     /// Websockets url of a Substrate node.
     #[clap(
         name = "url",
@@ -152,7 +380,7 @@ pub struct CLIChainOpts {
     chain: Option<ProductionChain>,
 }
 
-impl CLIChainOpts {
+impl CLINodeOpts {
     pub fn chain(&self) -> Chain {
         if let Some(chain) = &self.chain {
             Chain::Production(chain.clone())
@@ -164,6 +392,109 @@ impl CLIChainOpts {
     }
 }
 
+impl CLIChainOpts {
+    pub fn chain(&self) -> Chain {
+        self.node.chain()
+    }
+
+    /// Returns the SS58 address format prefix specified on the command line, or else
+    /// queried from the node.
+    pub async fn ss58_prefix<C: Config>(&self) -> Result<u16> {
+        match self.ss58_prefix {
+            Some(prefix) => Ok(prefix),
+            None => query_ss58_prefix::<C>(&self.chain().url()).await,
+        }
+    }
+}
+
+/// Arguments for transcoding the custom types of a chain.
+#[derive(Clone, Debug, clap::Args)]
+pub struct CLICustomTypesOpts {
+    /// Path to a TOML file mapping the paths of custom types to the built-in codecs
+    /// they are transcoded with, e.g. `ss58` with a prefix, `h160`, `fixed-point` or
+    /// `enum`.
+    #[clap(long, value_parser, global = true, env = "CONTRACT_CUSTOM_TYPES")]
+    custom_types: Option<PathBuf>,
+}
+
+impl CLICustomTypesOpts {
+    /// Loads the custom types configuration, if specified.
+    pub fn load(&self) -> Result<Option<CustomTypes>> {
+        let Some(path) = &self.custom_types else {
+            return Ok(None)
+        };
+        let config = std::fs::read_to_string(path).with_context(|| {
+            format!("Failed to read custom types from {}", path.display())
+        })?;
+        let custom_types = toml::from_str(&config).with_context(|| {
+            format!("Failed to parse custom types from {}", path.display())
+        })?;
+        Ok(Some(custom_types))
+    }
+
+    /// Applies the custom types configuration, if specified, to the transcoder.
+    pub fn apply(
+        &self,
+        transcoder: ContractMessageTranscoder,
+    ) -> Result<ContractMessageTranscoder> {
+        match self.load()? {
+            Some(custom_types) => transcoder.with_custom_types(&custom_types),
+            None => Ok(transcoder),
+        }
+    }
+}
+
+/// Arguments of a contract message or constructor, given positionally, by label or in
+/// a file.
+#[derive(Clone, Debug, clap::Args)]
+pub struct CLIArgsOpts {
+    /// The arguments of the contract message or constructor.
+    #[clap(long, num_args = 0..)]
+    pub args: Vec<String>,
+    /// The format of the arguments, `scon` or `json`. With `json` the return value of a
+    /// dry-run `call` is displayed as JSON as well.
+    #[clap(long, default_value = "scon")]
+    pub args_format: ArgsFormat,
+    /// Read the arguments from a file, or from stdin if `-`, instead of `--args`: either
+    /// a sequence with one value per argument, or a map keyed by argument label.
+    #[clap(long, value_parser, conflicts_with = "args")]
+    args_file: Option<PathBuf>,
+    /// An argument keyed by its label, e.g. `--arg to=5Grw...`, instead of `--args`.
+    /// `Option` arguments which are left out default to `None`.
+    #[clap(
+        long = "arg",
+        value_parser = parse_named_arg,
+        conflicts_with_all = ["args", "args_file"]
+    )]
+    named_args: Vec<(String, String)>,
+}
+
+impl CLIArgsOpts {
+    /// Encodes the selector of the message or constructor `name` followed by the
+    /// arguments, read from the args file, the labeled arguments or the positional
+    /// arguments.
+    pub fn encode(
+        &self,
+        transcoder: &ContractMessageTranscoder,
+        name: &str,
+    ) -> Result<Vec<u8>> {
+        if let Some(ref path) = self.args_file {
+            let document = read_args_file(path)?;
+            transcoder.encode_args_document(name, &document, self.args_format)
+        } else if !self.named_args.is_empty() {
+            transcoder.encode_named(
+                name,
+                self.named_args
+                    .iter()
+                    .map(|(label, arg)| (label.as_str(), arg)),
+                self.args_format,
+            )
+        } else {
+            transcoder.encode_with_format(name, &self.args, self.args_format)
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Chain {
     Production(ProductionChain),
@@ -273,6 +604,101 @@ pub fn prompt_confirm_tx<F: FnOnce()>(show_details: F) -> Result<()> {
     }
 }
 
+/// Print the estimated fee of the transaction in the confirmation prompt.
+///
+/// The fee is estimated in the native token, so with an asset the fee is paid in it is
+/// labeled as the equivalent in the native token.
+pub fn display_fee_estimate<AssetId: Debug>(
+    fee: &Result<u128, ErrorVariant>,
+    token_metadata: &TokenMetadata,
+    fee_asset: Option<&AssetId>,
+) {
+    let fee = match fee {
+        Ok(fee) => {
+            let fee = BalanceVariant::<u128>::from(*fee, Some(token_metadata))
+                .map(|fee| fee.to_string())
+                .unwrap_or_else(|_| fee.to_string());
+            match fee_asset {
+                Some(asset_id) => {
+                    format!("{fee} (native token equivalent, paid in asset {asset_id:?})")
+                }
+                None => fee,
+            }
+        }
+        Err(err) => format!("unavailable: {err}"),
+    };
+    name_value_println!("Estimated fee", fee, DEFAULT_KEY_COL_WIDTH);
+}
+
+/// The renderer of decoded values in the human-readable output of a command, which
+/// denominates balances in the token of the chain if the `token_metadata` is given.
+pub fn renderer(token_metadata: Option<&TokenMetadata>) -> Renderer {
+    match token_metadata {
+        Some(token_metadata) => {
+            let token_metadata = token_metadata.clone();
+            Renderer::default().balance_formatter(move |balance| {
+                BalanceVariant::<u128>::from(balance, Some(&token_metadata))
+                    .map(|balance| balance.to_string())
+                    .unwrap_or_else(|_| balance.to_string())
+            })
+        }
+        None => Renderer::default(),
+    }
+}
+
+/// Print the multisig account the transaction is submitted for in the confirmation
+/// prompt.
+pub fn display_multisig_details<AccountId: Display>(multisig: &Multisig<AccountId>) {
+    name_value_println!(
+        "Multisig",
+        multisig.account_id().to_string(),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Threshold",
+        format!(
+            "{} of {}",
+            multisig.threshold(),
+            multisig.signatories().len()
+        ),
+        DEFAULT_KEY_COL_WIDTH
+    );
+}
+
+/// Print the state of the multisig operation after the transaction was submitted.
+pub fn display_multisig_status<AccountId: Display>(status: &MultisigStatus<AccountId>) {
+    let join = |accounts: &[AccountId]| {
+        accounts
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    name_value_println!(
+        "Multisig",
+        status.multisig_account.to_string(),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Call Hash",
+        format!("0x{}", hex::encode(status.call_hash)),
+        DEFAULT_KEY_COL_WIDTH
+    );
+    if let Some(timepoint) = status.timepoint {
+        name_value_println!(
+            "Timepoint",
+            format!("{}:{}", timepoint.height, timepoint.index),
+            DEFAULT_KEY_COL_WIDTH
+        );
+    }
+    name_value_println!("Approvals", join(&status.approvals), DEFAULT_KEY_COL_WIDTH);
+    if status.executed {
+        name_value_println!("Status", "Executed", DEFAULT_KEY_COL_WIDTH);
+    } else {
+        name_value_println!("Pending", join(&status.pending), DEFAULT_KEY_COL_WIDTH);
+    }
+}
+
 pub fn print_dry_running_status(msg: &str) {
     println!(
         "{:>width$} {} (skip with --skip-dry-run)",
@@ -292,9 +718,10 @@ pub fn print_gas_required_success(gas: Weight) {
 }
 
 /// Display contract information in a formatted way
-pub fn basic_display_format_extended_contract_info<Hash, Balance>(
-    info: &ExtendedContractInfo<Hash, Balance>,
+pub fn basic_display_format_extended_contract_info<AccountId, Hash, Balance>(
+    info: &ExtendedContractInfo<AccountId, Hash, Balance>,
 ) where
+    AccountId: Display,
     Hash: Debug,
     Balance: Debug,
 {
@@ -319,19 +746,47 @@ pub fn basic_display_format_extended_contract_info<Hash, Balance>(
         format!("{:?}", info.storage_total_deposit),
         MAX_KEY_COL_WIDTH
     );
+    name_value_println!(
+        "Deposit Account",
+        format!("{}", info.deposit_account),
+        MAX_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Code Owner",
+        format!("{}", info.code_owner),
+        MAX_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Code Deposit",
+        format!("{:?}", info.code_deposit),
+        MAX_KEY_COL_WIDTH
+    );
+    name_value_println!(
+        "Contracts With Code",
+        format!("{}", info.code_refcount),
+        MAX_KEY_COL_WIDTH
+    );
     name_value_println!(
         "Source Language",
         format!("{}", info.source_language),
         MAX_KEY_COL_WIDTH
     );
-}
-
-/// Display all contracts addresses in a formatted way
-pub fn display_all_contracts<AccountId>(contracts: &[AccountId])
-where
-    AccountId: Display,
-{
-    contracts.iter().for_each(|e: &AccountId| println!("{}", e))
+    if let Some(ref instantiation) = info.instantiation {
+        name_value_println!(
+            "Instantiated At",
+            format!(
+                "#{} ({:?})",
+                instantiation.block_number(),
+                instantiation.block_hash()
+            ),
+            MAX_KEY_COL_WIDTH
+        );
+        let deployer = instantiation
+            .deployer()
+            .map(|d| d.to_string())
+            .unwrap_or_else(|| "Unknown".to_string());
+        name_value_println!("Deployer", deployer, MAX_KEY_COL_WIDTH);
+    }
 }
 
 /// Parse a balance from string format
@@ -367,6 +822,74 @@ where
     Ok(arr.into())
 }
 
+/// Parse an argument keyed by its label, in the format `<label>=<value>`.
+pub fn parse_named_arg(arg: &str) -> Result<(String, String)> {
+    let (label, value) = arg.split_once('=').ok_or_else(|| {
+        anyhow!("Expected an argument as `<label>=<value>`, got `{arg}`")
+    })?;
+    Ok((label.trim().to_string(), value.to_string()))
+}
+
+/// Read the arguments of a contract call from the file at `path`, or from stdin if the
+/// path is `-`.
+pub fn read_args_file(path: &Path) -> Result<String> {
+    if path == Path::new("-") {
+        let mut args = String::new();
+        io::stdin()
+            .read_to_string(&mut args)
+            .context("Failed to read the arguments from stdin")?;
+        Ok(args)
+    } else {
+        std::fs::read_to_string(path).context(format!(
+            "Failed to read the arguments from {}",
+            path.display()
+        ))
+    }
+}
+
+/// A block identified either by its hash or by its number.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BlockRef {
+    Hash([u8; 32]),
+    Number(u64),
+}
+
+impl FromStr for BlockRef {
+    type Err = anyhow::Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        if input.starts_with("0x") {
+            let hash = parse_code_hash::<[u8; 32]>(input)
+                .context("Block hash should be 32 bytes in length")?;
+            Ok(BlockRef::Hash(hash))
+        } else {
+            let number = input
+                .parse()
+                .map_err(|e| anyhow!("Block number parsing failed: {e}"))?;
+            Ok(BlockRef::Number(number))
+        }
+    }
+}
+
+impl BlockRef {
+    /// Returns the hash of the block, looking up the hash of a block number on the node.
+    pub async fn block_hash<C: Config>(&self, url: &url::Url) -> Result<C::Hash>
+    where
+        C::Hash: From<[u8; 32]>,
+    {
+        match *self {
+            BlockRef::Hash(hash) => Ok(hash.into()),
+            BlockRef::Number(number) => {
+                let rpc_cli = RpcClient::from_url(url_to_string(url)).await?;
+                let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+                rpc.chain_get_block_hash(Some(number.into()))
+                    .await?
+                    .ok_or_else(|| anyhow!("Block #{number} not found"))
+            }
+        }
+    }
+}
+
 /// Prompt the user to confirm the upload of unverifiable code to the production chain.
 pub fn prompt_confirm_unverifiable_upload(chain: &str) -> Result<()> {
     println!("{}", "Confirm upload:".bright_white().bold());
@@ -407,6 +930,65 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn parse_block_ref_works() {
+        assert_eq!(BlockRef::from_str("42").unwrap(), BlockRef::Number(42));
+        assert_eq!(
+            BlockRef::from_str(
+                "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+            )
+            .unwrap(),
+            BlockRef::Hash(
+                parse_code_hash(
+                    "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+                )
+                .unwrap()
+            )
+        );
+        assert!(BlockRef::from_str("0x1234").is_err());
+        assert!(BlockRef::from_str("latest").is_err());
+    }
+
+    #[test]
+    fn tip_with_fee_asset_must_be_raw() {
+        #[derive(clap::Parser)]
+        struct Cli {
+            #[clap(flatten)]
+            opts: CLIExtrinsicOpts,
+        }
+        let tip = |args: &[&str]| {
+            let cli = <Cli as clap::Parser>::try_parse_from(
+                ["cargo-contract", "--suri", "//Alice"].iter().chain(args),
+            )
+            .unwrap();
+            let token_metadata = TokenMetadata {
+                token_decimals: 10,
+                symbol: "DOT".to_string(),
+            };
+            cli.opts.tip(&token_metadata)
+        };
+
+        assert_eq!(tip(&["--tip", "1DOT"]).unwrap(), Some(10_000_000_000));
+        assert_eq!(
+            tip(&["--tip", "1000", "--fee-asset", "1984"]).unwrap(),
+            Some(1000)
+        );
+        assert!(tip(&["--tip", "1DOT", "--fee-asset", "1984"]).is_err());
+    }
+
+    #[test]
+    fn parse_named_arg_works() {
+        assert_eq!(
+            parse_named_arg("to=5Grw").unwrap(),
+            ("to".to_string(), "5Grw".to_string())
+        );
+        assert_eq!(
+            parse_named_arg(r#"memo=Some("a=b")"#).unwrap(),
+            ("memo".to_string(), r#"Some("a=b")"#.to_string())
+        );
+        assert!(parse_named_arg("to").is_err());
+    }
+
     #[test]
     fn parse_code_hash_works() {
         // with 0x prefix
@@ -438,4 +1020,29 @@ mod tests {
         )
         .is_err())
     }
+
+    #[test]
+    fn load_custom_types_from_toml() -> Result<()> {
+        let mut file = tempfile::NamedTempFile::new()?;
+        writeln!(
+            file,
+            r#"
+            [types."sp_core::crypto::AccountId32"]
+            codec = "ss58"
+            prefix = 0
+
+            [types."my_contract::Status"]
+            codec = "enum"
+            names = {{ 0 = "Pending", 1 = "Active" }}
+            "#
+        )?;
+        let opts = CLICustomTypesOpts {
+            custom_types: Some(file.path().to_path_buf()),
+        };
+        assert!(!opts.load()?.expect("custom types are specified").is_empty());
+
+        writeln!(file, "[types.\"my_contract::Price\"]\ncodec = \"decimal\"")?;
+        assert!(opts.load().is_err());
+        Ok(())
+    }
 }
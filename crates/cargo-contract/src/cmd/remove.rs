@@ -28,6 +28,7 @@ use std::{
 
 use super::{
     config::SignerConfig,
+    display_multisig_status,
     parse_balance,
     parse_code_hash,
     CLIExtrinsicOpts,
@@ -40,6 +41,7 @@ use contract_extrinsics::{
     RemoveCommandBuilder,
     RemoveExec,
     TokenMetadata,
+    TrySigner,
 };
 use ink_env::Environment;
 use serde::Serialize;
@@ -49,6 +51,7 @@ use subxt::{
         ExtrinsicParams,
     },
     ext::{
+        codec::Decode,
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
@@ -86,8 +89,11 @@ impl RemoveCommand {
         &self,
     ) -> Result<(), ErrorVariant>
     where
-        <C as Config>::AccountId: IntoVisitor + FromStr + EncodeAsType,
+        <C as Config>::AccountId:
+            IntoVisitor + FromStr + EncodeAsType + Decode + Ord + Display + Serialize,
         <<C as Config>::AccountId as FromStr>::Err: Display,
+        <C as Config>::AssetId: FromStr,
+        <<C as Config>::AssetId as FromStr>::Err: Display,
         C::Balance: Into<u128>
             + From<u128>
             + Display
@@ -100,8 +106,8 @@ impl RemoveCommand {
             From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
         <C as Config>::Hash: IntoVisitor + EncodeAsType + From<[u8; 32]>,
     {
-        let signer = C::Signer::from_str(&self.extrinsic_cli_opts.suri)
-            .map_err(|_| anyhow::anyhow!("Failed to parse suri option"))?;
+        let signer: C::Signer = self.extrinsic_cli_opts.signer()?;
+        let multisig = self.extrinsic_cli_opts.multisig(&signer.account_id())?;
         let chain = self.extrinsic_cli_opts.chain_cli_opts.chain();
         let token_metadata = TokenMetadata::query::<C>(&chain.url()).await?;
         let storage_deposit_limit = self
@@ -124,6 +130,19 @@ impl RemoveCommand {
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
             .url(chain.url())
             .storage_deposit_limit(storage_deposit_limit)
+            .multisig(multisig)
+            .proxy_for(self.extrinsic_cli_opts.proxy_for()?)
+            .nonce(self.extrinsic_cli_opts.nonce)
+            .tip(self.extrinsic_cli_opts.tip(&token_metadata)?)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .fee_asset(self.extrinsic_cli_opts.fee_asset()?)
+            .custom_types(self.extrinsic_cli_opts.custom_types()?)
+            .ss58_prefix(Some(
+                self.extrinsic_cli_opts
+                    .chain_cli_opts
+                    .ss58_prefix::<C>()
+                    .await?,
+            ))
             .done();
 
         let remove_exec: RemoveExec<C, C, _> = RemoveCommandBuilder::new(extrinsic_opts)
@@ -145,29 +164,50 @@ impl RemoveCommand {
                 &token_metadata,
             )?
         };
-        if let Some(code_removed) = remove_result.code_removed {
-            let remove_result: <C as Config>::Hash = code_removed.code_hash;
-
-            if self.output_json() {
-                // Create a JSON object with the events and the removed code hash.
-                let json_object = serde_json::json!({
-                    "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
-                    "code_hash": remove_result,
-                });
-                let json_object = serde_json::to_string_pretty(&json_object)?;
-                println!("{}", json_object);
-            } else {
-                println!("{}", output_events);
-                name_value_println!("Code hash", format!("{remove_result:?}"));
+        let multisig_status = match remove_exec.opts().multisig() {
+            Some(multisig) => {
+                Some(
+                    multisig
+                        .status(remove_exec.client(), &remove_result.events)
+                        .await?,
+                )
             }
-            Result::<(), ErrorVariant>::Ok(())
-        } else {
+            None => None,
+        };
+        // The code is not removed yet if the removal is pending multisig approvals.
+        if remove_result.code_removed.is_none() && multisig_status.is_none() {
             let error_code_hash = hex::encode(remove_exec.final_code_hash());
-            Err(anyhow::anyhow!(
+            return Err(anyhow::anyhow!(
                 "Error removing the code for the supplied code hash: {}",
                 error_code_hash
             )
             .into())
         }
+        let code_hash: Option<<C as Config>::Hash> = remove_result
+            .code_removed
+            .map(|code_removed| code_removed.code_hash);
+        if self.output_json() {
+            // Create a JSON object with the events, the removed code hash and the state
+            // of the multisig operation.
+            let mut json_object = serde_json::json!({
+                "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
+            });
+            if let Some(code_hash) = code_hash {
+                json_object["code_hash"] = serde_json::json!(code_hash);
+            }
+            if let Some(status) = multisig_status {
+                json_object["multisig"] = serde_json::json!(status);
+            }
+            println!("{}", serde_json::to_string_pretty(&json_object)?);
+        } else {
+            println!("{}", output_events);
+            if let Some(code_hash) = code_hash {
+                name_value_println!("Code hash", format!("{code_hash:?}"));
+            }
+            if let Some(status) = multisig_status {
+                display_multisig_status(&status);
+            }
+        }
+        Ok(())
     }
 }
@@ -0,0 +1,574 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use base64::Engine as _;
+use crypto_secretbox::{
+    aead::Aead,
+    KeyInit,
+    XSalsa20Poly1305,
+};
+use serde::Deserialize;
+use std::{
+    fs,
+    io::Write,
+    path::{
+        Path,
+        PathBuf,
+    },
+    process::{
+        Command,
+        Stdio,
+    },
+    str::FromStr,
+};
+use subxt::{
+    ext::{
+        sp_core::{
+            self,
+            crypto::{
+                AccountId32,
+                ByteArray,
+                Ss58Codec,
+            },
+            Pair,
+        },
+        sp_runtime::{
+            traits::{
+                IdentifyAccount,
+                Verify,
+            },
+            MultiSignature,
+        },
+    },
+    tx::{
+        PairSigner,
+        Signer as SignerT,
+    },
+    Config,
+};
+
+/// The source from which the signing key for an extrinsic is obtained.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SignerSource {
+    /// A secret key URI passed directly.
+    Suri(String),
+    /// The name of an environment variable holding a secret key URI.
+    SuriEnv(String),
+    /// A file holding a secret key URI.
+    SuriFile(PathBuf),
+    /// A JSON keystore file exported from Polkadot-JS, the password is prompted.
+    Keystore(PathBuf),
+    /// An external program which signs payloads over stdin/stdout.
+    External(String),
+}
+
+impl SignerSource {
+    /// Resolves a secret key URI for the sources which provide one.
+    fn suri(&self) -> Result<Option<String>> {
+        match self {
+            SignerSource::Suri(suri) => Ok(Some(suri.clone())),
+            SignerSource::SuriEnv(var) => {
+                let suri = std::env::var(var).map_err(|e| {
+                    anyhow!("Failed to read secret key URI from env var `{var}`: {e}")
+                })?;
+                Ok(Some(suri.trim().to_string()))
+            }
+            SignerSource::SuriFile(path) => {
+                let suri = fs::read_to_string(path).with_context(|| {
+                    format!("Failed to read secret key URI from {}", path.display())
+                })?;
+                Ok(Some(suri.trim().to_string()))
+            }
+            SignerSource::Keystore(_) | SignerSource::External(_) => Ok(None),
+        }
+    }
+}
+
+/// Constructs a signer from a [`SignerSource`].
+pub trait FromSignerSource: Sized {
+    fn from_source(source: &SignerSource) -> Result<Self>;
+}
+
+/// A key pair type which can be loaded from a keystore or used by an external signer.
+pub trait SignerPair: Pair {
+    /// The key type name used in the `content` field of a Polkadot-JS keystore.
+    const KEYSTORE_TYPE: &'static str;
+
+    /// Constructs the key pair from the secret stored in a Polkadot-JS keystore.
+    fn from_keystore_secret(secret: &[u8]) -> Result<Self>;
+
+    /// Constructs a signature from its raw bytes.
+    fn signature_from_slice(bytes: &[u8]) -> Option<Self::Signature>;
+}
+
+impl SignerPair for sp_core::sr25519::Pair {
+    const KEYSTORE_TYPE: &'static str = "sr25519";
+
+    fn from_keystore_secret(secret: &[u8]) -> Result<Self> {
+        // Polkadot-JS stores the expanded secret key in its ed25519 compatible format.
+        let secret = schnorrkel::SecretKey::from_ed25519_bytes(secret)
+            .map_err(|e| anyhow!("Invalid sr25519 secret key in keystore: {e}"))?;
+        Ok(secret.into())
+    }
+
+    fn signature_from_slice(bytes: &[u8]) -> Option<Self::Signature> {
+        Self::Signature::try_from(bytes).ok()
+    }
+}
+
+impl SignerPair for sp_core::ecdsa::Pair {
+    const KEYSTORE_TYPE: &'static str = "ecdsa";
+
+    fn from_keystore_secret(secret: &[u8]) -> Result<Self> {
+        Self::from_seed_slice(secret)
+            .map_err(|e| anyhow!("Invalid ecdsa secret key in keystore: {e:?}"))
+    }
+
+    fn signature_from_slice(bytes: &[u8]) -> Option<Self::Signature> {
+        Self::Signature::try_from(bytes).ok()
+    }
+}
+
+/// A signer backed either by a key pair held in memory or by an external program.
+#[derive(Clone)]
+pub enum KeySigner<C: Config, P: SignerPair> {
+    /// Signs with a key pair loaded from a secret key URI or a keystore.
+    Pair(PairSigner<C, P>),
+    /// Delegates signing to an external program.
+    External(ExternalSigner<C, P>),
+}
+
+impl<C: Config, P: SignerPair> FromStr for KeySigner<C, P>
+where
+    <C as Config>::AccountId: From<AccountId32>,
+    <MultiSignature as Verify>::Signer: From<P::Public>,
+{
+    type Err = anyhow::Error;
+
+    /// Attempts to parse the Signer suri string
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let keypair = P::from_string(input, None)
+            .map_err(|_| anyhow!("Failed to parse suri option"))?;
+        Ok(Self::Pair(PairSigner::new(keypair)))
+    }
+}
+
+impl<C: Config, P: SignerPair> FromSignerSource for KeySigner<C, P>
+where
+    <C as Config>::AccountId: From<AccountId32>,
+    <MultiSignature as Verify>::Signer: From<P::Public>,
+{
+    fn from_source(source: &SignerSource) -> Result<Self> {
+        if let Some(suri) = source.suri()? {
+            return Self::from_str(&suri)
+        }
+        match source {
+            SignerSource::Keystore(path) => {
+                let password = rpassword::prompt_password(format!(
+                    "Password for keystore {}: ",
+                    path.display()
+                ))?;
+                let keypair = load_keystore::<P>(path, &password)?;
+                Ok(Self::Pair(PairSigner::new(keypair)))
+            }
+            SignerSource::External(command) => {
+                Ok(Self::External(ExternalSigner::new(command)?))
+            }
+            _ => unreachable!("secret key URI sources are handled above"),
+        }
+    }
+}
+
+impl<C: Config, P: SignerPair> KeySigner<C, P>
+where
+    P::Signature: Into<C::Signature>,
+{
+    pub(crate) fn signer_account_id(&self) -> C::AccountId {
+        match self {
+            KeySigner::Pair(signer) => signer.account_id().clone(),
+            KeySigner::External(signer) => signer.account_id.clone(),
+        }
+    }
+
+    pub(crate) fn try_sign_payload(&self, signer_payload: &[u8]) -> Result<C::Signature> {
+        match self {
+            KeySigner::Pair(signer) => Ok(signer.sign(signer_payload)),
+            KeySigner::External(signer) => Ok(signer.sign(signer_payload)?.into()),
+        }
+    }
+}
+
+/// Implements [`TrySigner`] for the [`KeySigner`] of a chain configuration, so that
+/// failures of an external signer are returned as errors.
+///
+/// A generic implementation would conflict with the implementation of [`TrySigner`]
+/// for every `subxt::tx::Signer`.
+macro_rules! impl_try_signer {
+    ($config:ty) => {
+        impl<P: $crate::cmd::signer::SignerPair> contract_extrinsics::TrySigner<$config>
+            for $crate::cmd::signer::KeySigner<$config, P>
+        where
+            P::Signature: Into<<$config as subxt::Config>::Signature>,
+        {
+            fn account_id(&self) -> <$config as subxt::Config>::AccountId {
+                self.signer_account_id()
+            }
+
+            fn address(&self) -> <$config as subxt::Config>::Address {
+                self.signer_account_id().into()
+            }
+
+            fn try_sign(
+                &self,
+                signer_payload: &[u8],
+            ) -> anyhow::Result<<$config as subxt::Config>::Signature> {
+                self.try_sign_payload(signer_payload)
+            }
+        }
+    };
+}
+pub(crate) use impl_try_signer;
+
+/// A signer delegating to an external program, e.g. a bridge to a hardware module.
+///
+/// The program is invoked once per operation with a single argument:
+/// - `public`: prints the hex encoded public key of the signing account to stdout.
+/// - `sign`: reads the hex encoded payload from stdin and prints the hex encoded
+///   signature to stdout.
+#[derive(Clone, Debug)]
+pub struct ExternalSigner<C: Config, P: SignerPair> {
+    program: String,
+    args: Vec<String>,
+    public: P::Public,
+    account_id: C::AccountId,
+}
+
+impl<C: Config, P: SignerPair> ExternalSigner<C, P>
+where
+    <C as Config>::AccountId: From<AccountId32>,
+    <MultiSignature as Verify>::Signer: From<P::Public>,
+{
+    /// Creates a new external signer and queries the public key of its account.
+    pub fn new(command: &str) -> Result<Self> {
+        let mut parts = shell_words::split(command)
+            .context("Failed to parse the external signer command")?
+            .into_iter();
+        let program = parts
+            .next()
+            .ok_or_else(|| anyhow!("The external signer command must not be empty"))?;
+        let args = parts.collect::<Vec<_>>();
+
+        let output = run_external(&program, &args, "public", None)?;
+        let public = P::Public::from_slice(&output).map_err(|_| {
+            anyhow!(
+                "External signer returned a public key of invalid length {}",
+                output.len()
+            )
+        })?;
+        let account_id =
+            <MultiSignature as Verify>::Signer::from(public.clone()).into_account();
+        Ok(Self {
+            program,
+            args,
+            public,
+            account_id: account_id.into(),
+        })
+    }
+}
+
+impl<C: Config, P: SignerPair> ExternalSigner<C, P> {
+    /// Requests a signature for the payload and verifies it against the public key.
+    fn sign(&self, signer_payload: &[u8]) -> Result<P::Signature> {
+        let output =
+            run_external(&self.program, &self.args, "sign", Some(signer_payload))?;
+        let signature = P::signature_from_slice(&output).ok_or_else(|| {
+            anyhow!(
+                "External signer returned a signature of invalid length {}",
+                output.len()
+            )
+        })?;
+        if !P::verify(&signature, signer_payload, &self.public) {
+            anyhow::bail!("External signer returned an invalid signature")
+        }
+        Ok(signature)
+    }
+}
+
+/// Runs the external signer program and returns the hex decoded bytes of its output.
+fn run_external(
+    program: &str,
+    args: &[String],
+    op: &str,
+    input: Option<&[u8]>,
+) -> Result<Vec<u8>> {
+    let mut child = Command::new(program)
+        .args(args)
+        .arg(op)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .with_context(|| format!("Failed to spawn external signer `{program}`"))?;
+    {
+        let mut stdin = child
+            .stdin
+            .take()
+            .expect("stdin is piped for the external signer");
+        if let Some(input) = input {
+            writeln!(stdin, "{}", hex::encode(input))?;
+        }
+    }
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "External signer `{program} {op}` exited with {}",
+            output.status
+        )
+    }
+    let stdout = String::from_utf8(output.stdout)
+        .context("External signer output is not valid UTF-8")?;
+    contract_build::util::decode_hex(stdout.trim())
+        .context("External signer output is not valid hex")
+}
+
+/// The JSON format of an account exported from Polkadot-JS.
+#[derive(Debug, Deserialize)]
+struct KeystoreJson {
+    encoded: String,
+    encoding: KeystoreEncoding,
+    address: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct KeystoreEncoding {
+    content: Vec<String>,
+    #[serde(rename = "type")]
+    ty: Vec<String>,
+}
+
+const NONCE_LENGTH: usize = 24;
+const SCRYPT_LENGTH: usize = 32 + 3 * 4;
+const PKCS8_HEADER: [u8; 16] = [48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: [u8; 5] = [161, 35, 3, 33, 0];
+
+/// Loads a key pair from a Polkadot-JS JSON keystore file.
+pub fn load_keystore<P: SignerPair>(path: &Path, password: &str) -> Result<P>
+where
+    <MultiSignature as Verify>::Signer: From<P::Public>,
+{
+    let file = fs::File::open(path)
+        .with_context(|| format!("Failed to open keystore {}", path.display()))?;
+    let json: KeystoreJson = serde_json::from_reader(file)
+        .with_context(|| format!("Failed to parse keystore {}", path.display()))?;
+    decode_keystore(&json, password)
+}
+
+fn decode_keystore<P: SignerPair>(json: &KeystoreJson, password: &str) -> Result<P>
+where
+    <MultiSignature as Verify>::Signer: From<P::Public>,
+{
+    if !json.encoding.content.iter().any(|c| c == "pkcs8") {
+        anyhow::bail!("Unsupported keystore content {:?}", json.encoding.content)
+    }
+    if !json.encoding.content.iter().any(|c| c == P::KEYSTORE_TYPE) {
+        anyhow::bail!(
+            "Keystore holds a {:?} key, but the chain config requires {}",
+            json.encoding.content,
+            P::KEYSTORE_TYPE
+        )
+    }
+
+    let mut encoded = base64::engine::general_purpose::STANDARD
+        .decode(&json.encoded)
+        .context("Keystore `encoded` field is not valid base64")?;
+    let is_encrypted = json.encoding.ty.iter().any(|t| t == "xsalsa20-poly1305");
+    if is_encrypted {
+        let key = if json.encoding.ty.iter().any(|t| t == "scrypt") {
+            let key = scrypt_key(&encoded, password)?;
+            encoded.drain(..SCRYPT_LENGTH);
+            key
+        } else {
+            let mut key = [0u8; 32];
+            let len = password.len().min(32);
+            key[..len].copy_from_slice(&password.as_bytes()[..len]);
+            key
+        };
+        if encoded.len() < NONCE_LENGTH {
+            anyhow::bail!("Keystore `encoded` field is too short")
+        }
+        let (nonce, cipher_text) = encoded.split_at(NONCE_LENGTH);
+        encoded = XSalsa20Poly1305::new(&key.into())
+            .decrypt(nonce.into(), cipher_text)
+            .map_err(|_| {
+                anyhow!("Unable to decrypt keystore, is the password correct?")
+            })?;
+    }
+
+    let secret = pkcs8_secret(&encoded)?;
+    let pair = P::from_keystore_secret(secret)?;
+    let account_id: AccountId32 =
+        <MultiSignature as Verify>::Signer::from(pair.public()).into_account();
+    let expected = AccountId32::from_ss58check(&json.address)
+        .map_err(|e| anyhow!("Invalid keystore address {}: {e:?}", json.address))?;
+    if account_id != expected {
+        anyhow::bail!(
+            "Decoded key does not match the keystore address {}",
+            json.address
+        )
+    }
+    Ok(pair)
+}
+
+/// Derives the encryption key from the password, using the scrypt parameters stored in
+/// front of the encrypted data.
+fn scrypt_key(encoded: &[u8], password: &str) -> Result<[u8; 32]> {
+    if encoded.len() < SCRYPT_LENGTH {
+        anyhow::bail!("Keystore `encoded` field is too short")
+    }
+    let salt = &encoded[..32];
+    let read_u32 = |offset: usize| {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(&encoded[offset..offset + 4]);
+        u32::from_le_bytes(bytes)
+    };
+    let (n, p, r) = (read_u32(32), read_u32(36), read_u32(40));
+    if !n.is_power_of_two() {
+        anyhow::bail!("Invalid scrypt parameter N = {n} in keystore")
+    }
+    let params = scrypt::Params::new(n.trailing_zeros() as u8, r, p, 32)
+        .map_err(|e| anyhow!("Invalid scrypt parameters in keystore: {e}"))?;
+    let mut key = [0u8; 32];
+    scrypt::scrypt(password.as_bytes(), salt, &params, &mut key)
+        .map_err(|e| anyhow!("Failed to derive keystore key: {e}"))?;
+    Ok(key)
+}
+
+/// Extracts the secret key from the PKCS8 encoded key pair.
+fn pkcs8_secret(decoded: &[u8]) -> Result<&[u8]> {
+    if !decoded.starts_with(&PKCS8_HEADER) {
+        anyhow::bail!("Invalid PKCS8 header found in keystore")
+    }
+    let body = &decoded[PKCS8_HEADER.len()..];
+    // The secret is either a 64 byte expanded key or a 32 byte seed.
+    [64, 32]
+        .into_iter()
+        .find(|len| body.get(*len..*len + PKCS8_DIVIDER.len()) == Some(&PKCS8_DIVIDER))
+        .map(|len| &body[..len])
+        .ok_or_else(|| anyhow!("Invalid PKCS8 divider found in keystore"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use contract_extrinsics::TrySigner;
+    use sp_core::sr25519;
+
+    fn pkcs8(secret: &[u8], public: &[u8]) -> Vec<u8> {
+        [&PKCS8_HEADER[..], secret, &PKCS8_DIVIDER[..], public].concat()
+    }
+
+    #[test]
+    fn decode_unencrypted_sr25519_keystore_works() {
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let secret: schnorrkel::Keypair = pair.clone().into();
+        let encoded = pkcs8(&secret.secret.to_ed25519_bytes(), &pair.public().0);
+        let json = KeystoreJson {
+            encoded: base64::engine::general_purpose::STANDARD.encode(encoded),
+            encoding: KeystoreEncoding {
+                content: vec!["pkcs8".into(), "sr25519".into()],
+                ty: vec!["none".into()],
+            },
+            address: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+        };
+
+        let decoded = decode_keystore::<sr25519::Pair>(&json, "").unwrap();
+
+        assert_eq!(decoded.public(), pair.public());
+    }
+
+    #[test]
+    fn decode_keystore_with_wrong_address_fails() {
+        let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+        let secret: schnorrkel::Keypair = pair.clone().into();
+        let encoded = pkcs8(&secret.secret.to_ed25519_bytes(), &pair.public().0);
+        let json = KeystoreJson {
+            encoded: base64::engine::general_purpose::STANDARD.encode(encoded),
+            encoding: KeystoreEncoding {
+                content: vec!["pkcs8".into(), "sr25519".into()],
+                ty: vec!["none".into()],
+            },
+            // Bob
+            address: "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty".into(),
+        };
+
+        assert!(decode_keystore::<sr25519::Pair>(&json, "").is_err());
+    }
+
+    #[test]
+    fn decode_keystore_with_mismatching_key_type_fails() {
+        let json = KeystoreJson {
+            encoded: String::new(),
+            encoding: KeystoreEncoding {
+                content: vec!["pkcs8".into(), "ed25519".into()],
+                ty: vec!["none".into()],
+            },
+            address: "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY".into(),
+        };
+
+        let err = decode_keystore::<sr25519::Pair>(&json, "")
+            .err()
+            .expect("ed25519 keystore must be rejected");
+
+        assert!(err.to_string().contains("requires sr25519"));
+    }
+
+    #[test]
+    fn suri_from_env_works() {
+        std::env::set_var("CARGO_CONTRACT_TEST_SURI", "//Alice\n");
+        let source = SignerSource::SuriEnv("CARGO_CONTRACT_TEST_SURI".into());
+
+        assert_eq!(source.suri().unwrap(), Some("//Alice".to_string()));
+    }
+
+    #[test]
+    fn failing_external_signer_returns_error() {
+        let public = sr25519::Pair::from_string("//Alice", None)
+            .unwrap()
+            .public();
+        let script = format!(
+            "if [ \"$0\" = public ]; then echo {}; else read payload; exit 1; fi",
+            hex::encode(public.0)
+        );
+        let command = format!("sh -c '{script}'");
+        let signer = KeySigner::<crate::cmd::config::Substrate, sr25519::Pair>::External(
+            ExternalSigner::new(&command).unwrap(),
+        );
+
+        assert_eq!(
+            TrySigner::account_id(&signer),
+            AccountId32::from(public.0).into()
+        );
+        let err = signer
+            .try_sign(b"payload")
+            .expect_err("a failing external signer must return an error");
+        assert!(err.to_string().contains("exited with"));
+    }
+}
@@ -0,0 +1,183 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    Context,
+    Result,
+};
+use colored::Colorize;
+use contract_extrinsics::{
+    ContractSnapshot,
+    ContractStorage,
+    ContractStorageRpc,
+    ErrorVariant,
+};
+use ink_env::Environment;
+use serde::Serialize;
+use std::{
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    str::FromStr,
+};
+use subxt::{
+    ext::scale_decode::IntoVisitor,
+    Config,
+};
+
+use crate::call_with_config;
+
+use super::{
+    parse_account,
+    BlockRef,
+    CLIChainOpts,
+};
+
+#[derive(Debug, clap::Args)]
+#[clap(
+    name = "snapshot",
+    about = "Export and import snapshots of the on-chain state of a contract"
+)]
+pub struct SnapshotCommand {
+    #[clap(subcommand)]
+    cmd: SnapshotSubcommand,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum SnapshotSubcommand {
+    /// Export the storage, contract info and code of a contract into a snapshot file.
+    #[clap(name = "export")]
+    Export(ExportSnapshotCommand),
+    /// Seed the state of a snapshot into the genesis of a raw chain spec.
+    #[clap(name = "import")]
+    Import(ImportSnapshotCommand),
+}
+
+impl SnapshotCommand {
+    pub async fn handle(&self) -> Result<(), ErrorVariant> {
+        match &self.cmd {
+            SnapshotSubcommand::Export(export) => export.handle().await,
+            SnapshotSubcommand::Import(import) => Ok(import.run()?),
+        }
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct ExportSnapshotCommand {
+    /// The address of the contract to export the state of.
+    #[clap(name = "contract", long, env = "CONTRACT")]
+    contract: String,
+    /// Export the state as it was at the given block hash or number, instead of at the
+    /// best block.
+    #[clap(long)]
+    at: Option<BlockRef>,
+    /// The file to write the snapshot to, by default the snapshot is printed to stdout.
+    #[clap(short, long, value_parser)]
+    output: Option<PathBuf>,
+    /// Arguments required for communicating with a Substrate node.
+    #[clap(flatten)]
+    chain_cli_opts: CLIChainOpts,
+}
+
+impl ExportSnapshotCommand {
+    async fn handle(&self) -> Result<(), ErrorVariant> {
+        call_with_config!(self, run, self.chain_cli_opts.chain().config())
+    }
+
+    async fn run<C: Config + Environment>(&self) -> Result<(), ErrorVariant>
+    where
+        <C as Config>::AccountId: Display + IntoVisitor + AsRef<[u8]> + FromStr,
+        <<C as Config>::AccountId as FromStr>::Err:
+            Into<Box<dyn std::error::Error>> + Display,
+        C::Balance: Serialize + IntoVisitor,
+        <C as Config>::Hash: IntoVisitor + From<[u8; 32]>,
+    {
+        let url = self.chain_cli_opts.chain().url();
+        let rpc = ContractStorageRpc::<C>::new(&url).await?;
+        let contract_storage = ContractStorage::<C, C>::new(rpc);
+        let contract = parse_account(&self.contract)?;
+        let block_hash = match self.at {
+            Some(ref at) => Some(at.block_hash::<C>(&url).await?),
+            None => None,
+        };
+
+        let snapshot = contract_storage
+            .load_contract_snapshot(&contract, block_hash)
+            .await?;
+        match self.output {
+            Some(ref path) => {
+                snapshot.save(path)?;
+                eprintln!(
+                    "{} {} storage entries of contract {} to {}",
+                    "Exported".green().bold(),
+                    snapshot.storage.iter().count(),
+                    snapshot.contract,
+                    path.display()
+                );
+            }
+            None => println!("{}", serde_json::to_string_pretty(&snapshot)?),
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, clap::Args)]
+struct ImportSnapshotCommand {
+    /// Path to the snapshot file.
+    #[clap(value_parser)]
+    snapshot: PathBuf,
+    /// Path to a raw chain spec to seed the snapshot into. If not specified, only the
+    /// `genesis.raw` entries of the snapshot are printed.
+    #[clap(long, value_parser)]
+    chain_spec: Option<PathBuf>,
+    /// The file to write the patched chain spec to, by default it is printed to stdout.
+    #[clap(short, long, value_parser, requires = "chain_spec")]
+    output: Option<PathBuf>,
+}
+
+impl ImportSnapshotCommand {
+    fn run(&self) -> Result<()> {
+        let snapshot = ContractSnapshot::load(&self.snapshot)?;
+        let Some(ref chain_spec_path) = self.chain_spec else {
+            let patch = serde_json::json!({
+                "genesis": { "raw": snapshot.to_raw_genesis() }
+            });
+            println!("{}", serde_json::to_string_pretty(&patch)?);
+            return Ok(())
+        };
+
+        let chain_spec = fs::read_to_string(chain_spec_path).context(format!(
+            "Failed to read chain spec {}",
+            chain_spec_path.display()
+        ))?;
+        let mut chain_spec: serde_json::Value = serde_json::from_str(&chain_spec)?;
+        snapshot.patch_chain_spec(&mut chain_spec)?;
+        let chain_spec = serde_json::to_string_pretty(&chain_spec)?;
+        match self.output {
+            Some(ref path) => {
+                fs::write(path, chain_spec)?;
+                eprintln!(
+                    "{} contract {} into {}",
+                    "Imported".green().bold(),
+                    snapshot.contract,
+                    path.display()
+                );
+            }
+            None => println!("{chain_spec}"),
+        }
+        Ok(())
+    }
+}
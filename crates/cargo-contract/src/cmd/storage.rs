@@ -23,9 +23,12 @@ use comfy_table::{
 use contract_extrinsics::{
     ContractArtifacts,
     ContractStorage,
+    ContractStorageDiff,
+    ContractStorageEntry,
     ContractStorageLayout,
     ContractStorageRpc,
     ErrorVariant,
+    TokenMetadata,
 };
 use ink_env::Environment;
 use serde::Serialize;
@@ -43,7 +46,10 @@ use crate::call_with_config;
 
 use super::{
     parse_account,
+    renderer,
+    BlockRef,
     CLIChainOpts,
+    CLICustomTypesOpts,
 };
 
 #[derive(Debug, clap::Args)]
@@ -63,6 +69,10 @@ pub struct StorageCommand {
     /// Export the instantiate output in JSON format.
     #[clap(name = "output-json", long, conflicts_with = "raw")]
     output_json: bool,
+    /// Display the decoded storage as plain data, e.g. balances as integers and
+    /// timestamps in milliseconds, instead of in a human-readable form.
+    #[clap(long)]
+    plain: bool,
     /// Path to a contract build artifact file: a raw `.wasm` file, a `.contract` bundle,
     /// or a `.json` metadata file.
     #[clap(value_parser, conflicts_with = "manifest_path")]
@@ -74,9 +84,35 @@ pub struct StorageCommand {
     /// contracts::palletVersion()).
     #[clap(long, short)]
     version: bool,
+    /// Inspect the storage as it was at the given block hash or number, instead of at
+    /// the best block.
+    #[clap(long, conflicts_with = "version")]
+    at: Option<BlockRef>,
+    /// Show the storage entries added, removed or changed between the two given block
+    /// hashes or numbers.
+    #[clap(
+        long,
+        num_args = 2,
+        value_names = ["FROM", "TO"],
+        conflicts_with_all = ["version", "raw", "at"]
+    )]
+    diff: Option<Vec<BlockRef>>,
+    /// Show the storage entries added, removed or changed between the contract and the
+    /// given contract. Both contracts are decoded with the same metadata.
+    #[clap(long, conflicts_with_all = ["version", "raw"])]
+    diff_contract: Option<String>,
+    /// Fetch only the storage cell at the given path, e.g. `root.balances`, instead of
+    /// the whole storage.
+    #[clap(long, conflicts_with_all = ["version", "raw", "diff", "diff_contract"])]
+    path: Option<String>,
+    /// The key of the `Mapping`, or the index of the `StorageVec`, at `--path`.
+    #[clap(long, requires = "path")]
+    key: Option<String>,
     /// Arguments required for communicating with a Substrate node.
     #[clap(flatten)]
     chain_cli_opts: CLIChainOpts,
+    #[clap(flatten)]
+    custom_types_cli_opts: CLICustomTypesOpts,
 }
 
 impl StorageCommand {
@@ -90,7 +126,7 @@ impl StorageCommand {
         <<C as Config>::AccountId as FromStr>::Err:
             Into<Box<(dyn std::error::Error)>> + Display,
         C::Balance: Serialize + IntoVisitor,
-        <C as Config>::Hash: IntoVisitor,
+        <C as Config>::Hash: IntoVisitor + From<[u8; 32]>,
     {
         let rpc =
             ContractStorageRpc::<C>::new(&self.chain_cli_opts.chain().url()).await?;
@@ -102,16 +138,114 @@ impl StorageCommand {
 
         // Contract arg shall be always present in this case, it is enforced by
         // clap configuration
-        let contract = self
+        let contract: <C as Config>::AccountId = self
             .contract
             .as_ref()
             .map(|c| parse_account(c))
             .transpose()?
             .expect("Contract argument shall be present");
+        let block_hash = match self.at {
+            Some(ref at) => {
+                Some(
+                    at.block_hash::<C>(&self.chain_cli_opts.chain().url())
+                        .await?,
+                )
+            }
+            None => None,
+        };
+        let renderer = if self.plain || self.output_json || self.raw {
+            None
+        } else {
+            let token_metadata =
+                TokenMetadata::query::<C>(&self.chain_cli_opts.chain().url()).await?;
+            Some(renderer(Some(&token_metadata)))
+        };
+        let ss58_prefix = self.chain_cli_opts.ss58_prefix::<C>().await?;
+        let contract_transcoder = |artifacts: ContractArtifacts| {
+            let transcoder = self.custom_types_cli_opts.apply(
+                artifacts
+                    .contract_transcoder()?
+                    .with_ss58_prefix(ss58_prefix)?,
+            )?;
+            anyhow::Ok(match renderer {
+                Some(ref renderer) => transcoder.with_renderer(renderer.clone()),
+                None => transcoder,
+            })
+        };
+
+        if self.diff.is_some() || self.diff_contract.is_some() {
+            let other_contract = self
+                .diff_contract
+                .as_ref()
+                .map(|c| parse_account(c))
+                .transpose()?
+                .unwrap_or_else(|| contract.clone());
+            let (from, to) = match self.diff {
+                Some(ref diff) => {
+                    let url = self.chain_cli_opts.chain().url();
+                    (
+                        Some(diff[0].block_hash::<C>(&url).await?),
+                        Some(diff[1].block_hash::<C>(&url).await?),
+                    )
+                }
+                None => {
+                    // load both contracts from the same state
+                    let block_hash = match block_hash {
+                        Some(block_hash) => block_hash,
+                        None => storage_layout.best_block().await?,
+                    };
+                    (Some(block_hash), Some(block_hash))
+                }
+            };
+            let artifacts = ContractArtifacts::from_manifest_or_file(
+                self.manifest_path.as_ref(),
+                self.file.as_ref(),
+            )?;
+            let transcoder = contract_transcoder(artifacts)?;
+            let old = storage_layout
+                .load_contract_storage_with_layout(&contract, &transcoder, from)
+                .await?;
+            let new = storage_layout
+                .load_contract_storage_with_layout(&other_contract, &transcoder, to)
+                .await?;
+            let diff = ContractStorageDiff::new(&old, &new);
+            if self.output_json {
+                println!("{json}", json = serde_json::to_string_pretty(&diff)?);
+            } else if diff.is_empty() {
+                println!("{}", "No storage changes".bright_white().bold());
+            } else {
+                StorageDiffTable::new(&diff).display();
+            }
+            return Ok(())
+        }
+
+        if let Some(ref path) = self.path {
+            let artifacts = ContractArtifacts::from_manifest_or_file(
+                self.manifest_path.as_ref(),
+                self.file.as_ref(),
+            )?;
+            let transcoder = contract_transcoder(artifacts)?;
+            let entry = storage_layout
+                .load_contract_storage_entry(
+                    &contract,
+                    &transcoder,
+                    path,
+                    self.key.as_deref(),
+                    block_hash,
+                )
+                .await?;
+            if self.output_json {
+                println!("{json}", json = serde_json::to_string_pretty(&entry)?);
+            } else {
+                StorageEntryTable::new(&entry).display();
+            }
+            return Ok(())
+        }
 
         if self.raw {
-            let storage_data =
-                storage_layout.load_contract_storage_data(&contract).await?;
+            let storage_data = storage_layout
+                .load_contract_storage_data(&contract, block_hash)
+                .await?;
             println!(
                 "{json}",
                 json = serde_json::to_string_pretty(&storage_data)?
@@ -126,9 +260,9 @@ impl StorageCommand {
 
         match contract_artifacts {
             Ok(contract_artifacts) => {
-                let transcoder = contract_artifacts.contract_transcoder()?;
+                let transcoder = contract_transcoder(contract_artifacts)?;
                 let contract_storage = storage_layout
-                    .load_contract_storage_with_layout(&contract, &transcoder)
+                    .load_contract_storage_with_layout(&contract, &transcoder, block_hash)
                     .await?;
                 if self.output_json {
                     println!(
@@ -145,8 +279,9 @@ impl StorageCommand {
                     "{} Displaying raw storage: no valid contract metadata artifacts found",
                     "Info:".cyan().bold(),
                 );
-                let storage_data =
-                    storage_layout.load_contract_storage_data(&contract).await?;
+                let storage_data = storage_layout
+                    .load_contract_storage_data(&contract, block_hash)
+                    .await?;
                 println!(
                     "{json}",
                     json = serde_json::to_string_pretty(&storage_data)?
@@ -204,3 +339,77 @@ impl StorageDisplayTable {
         println!("{}", self.0);
     }
 }
+
+struct StorageEntryTable(Table);
+
+impl StorageEntryTable {
+    const KEY_LABEL: &'static str = "Root Key";
+    const PATH_LABEL: &'static str = "Path";
+    const ENTRY_LABEL: &'static str = "Key";
+    const VALUE_LABEL: &'static str = "Value";
+
+    fn new(entry: &ContractStorageEntry) -> Self {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Self::KEY_LABEL,
+            Self::PATH_LABEL,
+            Self::ENTRY_LABEL,
+            Self::VALUE_LABEL,
+        ]);
+        table.add_row(vec![
+            entry.root_key(),
+            entry.path(),
+            entry.key().map(ToString::to_string).unwrap_or_default(),
+            entry
+                .value()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| "None".to_string()),
+        ]);
+        Self(table)
+    }
+
+    fn display(&self) {
+        println!("{}", self.0);
+    }
+}
+
+struct StorageDiffTable(Table);
+
+impl StorageDiffTable {
+    const KEY_LABEL: &'static str = "Root Key";
+    const PARENT_LABEL: &'static str = "Parent";
+    const ENTRY_LABEL: &'static str = "Key";
+    const CHANGE_LABEL: &'static str = "Change";
+    const OLD_VALUE_LABEL: &'static str = "Old Value";
+    const NEW_VALUE_LABEL: &'static str = "New Value";
+
+    fn new(diff: &ContractStorageDiff) -> Self {
+        let mut table = Table::new();
+        table.set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(vec![
+            Self::KEY_LABEL,
+            Self::PARENT_LABEL,
+            Self::ENTRY_LABEL,
+            Self::CHANGE_LABEL,
+            Self::OLD_VALUE_LABEL,
+            Self::NEW_VALUE_LABEL,
+        ]);
+        for entry in diff.iter() {
+            let display = |v: Option<&_>| v.map(ToString::to_string).unwrap_or_default();
+            table.add_row(vec![
+                entry.root_key(),
+                entry.parent(),
+                display(entry.key()),
+                entry.change().to_string(),
+                display(entry.change().old_value()),
+                display(entry.change().new_value()),
+            ]);
+        }
+        Self(table)
+    }
+
+    fn display(&self) {
+        println!("{}", self.0);
+    }
+}
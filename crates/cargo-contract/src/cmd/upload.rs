@@ -29,6 +29,7 @@ use std::{
 use super::{
     config::SignerConfig,
     display_dry_run_result_warning,
+    display_multisig_status,
     parse_balance,
     prompt_confirm_unverifiable_upload,
     CLIExtrinsicOpts,
@@ -39,6 +40,7 @@ use contract_extrinsics::{
     DisplayEvents,
     ExtrinsicOptsBuilder,
     TokenMetadata,
+    TrySigner,
     UploadCommandBuilder,
     UploadExec,
 };
@@ -50,6 +52,7 @@ use subxt::{
         ExtrinsicParams,
     },
     ext::{
+        codec::Decode,
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
@@ -84,8 +87,11 @@ impl UploadCommand {
         &self,
     ) -> Result<(), ErrorVariant>
     where
-        <C as Config>::AccountId: IntoVisitor + FromStr + EncodeAsType,
+        <C as Config>::AccountId:
+            IntoVisitor + FromStr + EncodeAsType + Decode + Ord + Display + Serialize,
         <<C as Config>::AccountId as FromStr>::Err: Display,
+        <C as Config>::AssetId: FromStr,
+        <<C as Config>::AssetId as FromStr>::Err: Display,
         C::Balance: Into<u128>
             + From<u128>
             + Display
@@ -98,8 +104,8 @@ impl UploadCommand {
             From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
         <C as Config>::Hash: IntoVisitor + EncodeAsType + From<[u8; 32]>,
     {
-        let signer = C::Signer::from_str(&self.extrinsic_cli_opts.suri)
-            .map_err(|_| anyhow::anyhow!("Failed to parse suri option"))?;
+        let signer: C::Signer = self.extrinsic_cli_opts.signer()?;
+        let multisig = self.extrinsic_cli_opts.multisig(&signer.account_id())?;
         let chain = self.extrinsic_cli_opts.chain_cli_opts.chain();
         let token_metadata = TokenMetadata::query::<C>(&chain.url()).await?;
         let storage_deposit_limit = self
@@ -116,6 +122,19 @@ impl UploadCommand {
             .manifest_path(self.extrinsic_cli_opts.manifest_path.clone())
             .url(chain.url())
             .storage_deposit_limit(storage_deposit_limit)
+            .multisig(multisig)
+            .proxy_for(self.extrinsic_cli_opts.proxy_for()?)
+            .nonce(self.extrinsic_cli_opts.nonce)
+            .tip(self.extrinsic_cli_opts.tip(&token_metadata)?)
+            .mortality(self.extrinsic_cli_opts.mortality)
+            .fee_asset(self.extrinsic_cli_opts.fee_asset()?)
+            .custom_types(self.extrinsic_cli_opts.custom_types()?)
+            .ss58_prefix(Some(
+                self.extrinsic_cli_opts
+                    .chain_cli_opts
+                    .ss58_prefix::<C>()
+                    .await?,
+            ))
             .done();
 
         let upload_exec: UploadExec<C, C, _> =
@@ -167,26 +186,49 @@ impl UploadCommand {
                     &token_metadata,
                 )?
             };
-            if let Some(code_stored) = upload_result.code_stored {
-                let code_hash: <C as Config>::Hash = code_stored.code_hash;
-                if self.output_json() {
-                    // Create a JSON object with the events and the code hash.
-                    let json_object = serde_json::json!({
-                        "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
-                        "code_hash": code_hash,
-                    });
-                    println!("{}", serde_json::to_string_pretty(&json_object)?);
-                } else {
-                    println!("{}", output_events);
-                    name_value_println!("Code hash", format!("{:?}", code_hash));
+            let multisig_status = match upload_exec.opts().multisig() {
+                Some(multisig) => {
+                    Some(
+                        multisig
+                            .status(upload_exec.client(), &upload_result.events)
+                            .await?,
+                    )
                 }
-            } else {
+                None => None,
+            };
+            // The code is not stored yet if the upload is pending multisig approvals.
+            if upload_result.code_stored.is_none() && multisig_status.is_none() {
                 let code_hash = hex::encode(code_hash);
                 return Err(anyhow::anyhow!(
                     "This contract has already been uploaded with code hash: 0x{code_hash}"
                 )
                 .into())
             }
+            let code_hash: Option<<C as Config>::Hash> = upload_result
+                .code_stored
+                .map(|code_stored| code_stored.code_hash);
+            if self.output_json() {
+                // Create a JSON object with the events, the code hash and the state of
+                // the multisig operation.
+                let mut json_object = serde_json::json!({
+                    "events": serde_json::from_str::<serde_json::Value>(&output_events)?,
+                });
+                if let Some(code_hash) = code_hash {
+                    json_object["code_hash"] = serde_json::json!(code_hash);
+                }
+                if let Some(status) = multisig_status {
+                    json_object["multisig"] = serde_json::json!(status);
+                }
+                println!("{}", serde_json::to_string_pretty(&json_object)?);
+            } else {
+                println!("{}", output_events);
+                if let Some(code_hash) = code_hash {
+                    name_value_println!("Code hash", format!("{:?}", code_hash));
+                }
+                if let Some(status) = multisig_status {
+                    display_multisig_status(&status);
+                }
+            }
         }
         Ok(())
     }
@@ -22,13 +22,17 @@ use self::cmd::{
     BuildCommand,
     CallCommand,
     CheckCommand,
+    CodegenCommand,
     DecodeCommand,
     ErrorVariant,
+    EventsCommand,
+    FetchCommand,
     GenerateSchemaCommand,
     InfoCommand,
     InstantiateCommand,
     RemoveCommand,
     RpcCommand,
+    SnapshotCommand,
     StorageCommand,
     UploadCommand,
     VerifyCommand,
@@ -136,6 +140,9 @@ enum Command {
     /// Decodes a contracts input or output data (supplied in hex-encoding)
     #[clap(name = "decode")]
     Decode(DecodeCommand),
+    /// Generate a typed client of a contract from its metadata.
+    #[clap(name = "codegen")]
+    Codegen(CodegenCommand),
     /// Remove contract code
     #[clap(name = "remove")]
     Remove(RemoveCommand),
@@ -145,6 +152,16 @@ enum Command {
     /// Inspect the on-chain storage of a contract.
     #[clap(name = "storage")]
     Storage(StorageCommand),
+    /// Download the code of a contract and bundle it with its metadata.
+    #[clap(name = "fetch")]
+    Fetch(FetchCommand),
+    /// Export and import snapshots of the on-chain state of a contract.
+    #[clap(name = "snapshot")]
+    Snapshot(SnapshotCommand),
+    /// Display the events of contracts, following new blocks or replaying a block
+    /// range.
+    #[clap(name = "events")]
+    Events(EventsCommand),
     /// Verifies that a given contract binary matches the build result of the specified
     /// workspace.
     #[clap(name = "verify")]
@@ -224,7 +241,10 @@ fn exec(cmd: Command) -> Result<()> {
             })
         }
         Command::Encode(encode) => encode.run().map_err(format_err),
-        Command::Decode(decode) => decode.run().map_err(format_err),
+        Command::Decode(decode) => {
+            runtime.block_on(async { decode.handle().await.map_err(format_err) })
+        }
+        Command::Codegen(codegen) => codegen.run().map_err(format_err),
         Command::Remove(remove) => {
             runtime.block_on(async {
                 remove
@@ -239,6 +259,15 @@ fn exec(cmd: Command) -> Result<()> {
         Command::Storage(storage) => {
             runtime.block_on(async { storage.handle().await.map_err(format_err) })
         }
+        Command::Fetch(fetch) => {
+            runtime.block_on(async { fetch.handle().await.map_err(format_err) })
+        }
+        Command::Snapshot(snapshot) => {
+            runtime.block_on(async { snapshot.handle().await.map_err(format_err) })
+        }
+        Command::Events(events) => {
+            runtime.block_on(async { events.handle().await.map_err(format_err) })
+        }
         Command::Verify(verify) => {
             let result = verify.run().map_err(format_err)?;
 
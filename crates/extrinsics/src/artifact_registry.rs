@@ -0,0 +1,340 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::ContractArtifacts;
+use anyhow::{
+    Context,
+    Result,
+};
+use contract_metadata::ContractMetadata;
+use contract_transcode::{
+    ContractMessageTranscoder,
+    CustomTypes,
+    Renderer,
+};
+use std::path::Path;
+
+/// A local registry of contract artifacts, used to decode the events emitted by
+/// contracts other than the called one.
+#[derive(Default)]
+pub struct ArtifactRegistry {
+    transcoders: Vec<ContractMessageTranscoder>,
+    /// The code hashes of the contracts, by the index of their transcoder.
+    code_hashes: Vec<[u8; 32]>,
+    ss58_prefix: Option<u16>,
+}
+
+impl ArtifactRegistry {
+    /// Loads the metadata of all `.contract` bundles and `.json` metadata files found in
+    /// the directory and its subdirectories.
+    ///
+    /// Files which are not valid contract metadata are skipped.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let mut registry = Self::default();
+        registry.load_dir(dir.as_ref())?;
+        tracing::debug!(
+            "loaded {} contract artifacts into the registry",
+            registry.transcoders.len()
+        );
+        Ok(registry)
+    }
+
+    /// Add the artifacts of a contract to the registry, e.g. of the contract in the
+    /// current directory.
+    pub fn with_artifacts(self, artifacts: &ContractArtifacts) -> Result<Self> {
+        let mut this = self;
+        this.code_hashes.push(artifacts.code_hash()?);
+        this.transcoders.push(artifacts.contract_transcoder()?);
+        Ok(this)
+    }
+
+    /// Render the values decoded with the transcoders of the registry, see
+    /// [`ContractMessageTranscoder::with_renderer`].
+    pub fn with_renderer(self, renderer: Renderer) -> Self {
+        Self {
+            transcoders: self
+                .transcoders
+                .into_iter()
+                .map(|transcoder| transcoder.with_renderer(renderer.clone()))
+                .collect(),
+            ..self
+        }
+    }
+
+    /// Transcode the custom types with the codecs configured for them, see
+    /// [`ContractMessageTranscoder::with_custom_types`].
+    pub fn with_custom_types(self, custom_types: &CustomTypes) -> Result<Self> {
+        Ok(Self {
+            transcoders: self
+                .transcoders
+                .into_iter()
+                .map(|transcoder| transcoder.with_custom_types(custom_types))
+                .collect::<Result<_>>()?,
+            ..self
+        })
+    }
+
+    /// Display the accounts, in the contract events as well as in the events of the
+    /// pallets, as SS58 addresses with the given address format prefix, see
+    /// [`ContractMessageTranscoder::with_ss58_prefix`].
+    pub fn with_ss58_prefix(self, prefix: u16) -> Result<Self> {
+        Ok(Self {
+            transcoders: self
+                .transcoders
+                .into_iter()
+                .map(|transcoder| transcoder.with_ss58_prefix(prefix))
+                .collect::<Result<_>>()?,
+            ss58_prefix: Some(prefix),
+            ..self
+        })
+    }
+
+    /// Returns the SS58 address format prefix the accounts are displayed with, if set.
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        self.ss58_prefix
+    }
+
+    fn load_dir(&mut self, dir: &Path) -> Result<()> {
+        let entries = std::fs::read_dir(dir)
+            .with_context(|| format!("Failed to read directory {}", dir.display()))?;
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                self.load_dir(&path)?;
+                continue
+            }
+            let is_artifact = path
+                .extension()
+                .is_some_and(|ext| ext == "contract" || ext == "json");
+            if !is_artifact {
+                continue
+            }
+            let artifact = ContractMetadata::load(&path).and_then(|metadata| {
+                let code_hash = metadata.source.hash.0;
+                let transcoder = ContractMessageTranscoder::try_from(metadata)?;
+                Ok((code_hash, transcoder))
+            });
+            match artifact {
+                Ok((code_hash, transcoder)) => {
+                    self.code_hashes.push(code_hash);
+                    self.transcoders.push(transcoder)
+                }
+                Err(err) => {
+                    tracing::debug!(
+                        "skipping {} from the artifact registry: {err:?}",
+                        path.display()
+                    )
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the transcoder of the contract metadata defining the event with the
+    /// signature topic, if any.
+    pub fn event_transcoder<Hash: AsRef<[u8]>>(
+        &self,
+        event_sig_topic: &Hash,
+    ) -> Option<&ContractMessageTranscoder> {
+        self.transcoders
+            .iter()
+            .find(|transcoder| defines_event(transcoder, event_sig_topic))
+    }
+
+    /// Returns the transcoder of the contract with the code hash, if any.
+    pub fn code_transcoder<Hash: AsRef<[u8]>>(
+        &self,
+        code_hash: &Hash,
+    ) -> Option<&ContractMessageTranscoder> {
+        self.code_hashes
+            .iter()
+            .position(|hash| hash == code_hash.as_ref())
+            .map(|index| &self.transcoders[index])
+    }
+}
+
+/// Returns whether the contract metadata of the transcoder defines the event with the
+/// signature topic.
+pub(crate) fn defines_event<Hash: AsRef<[u8]>>(
+    transcoder: &ContractMessageTranscoder,
+    event_sig_topic: &Hash,
+) -> bool {
+    transcoder.metadata().spec().events().iter().any(|event| {
+        event
+            .signature_topic()
+            .is_some_and(|topic| topic.as_bytes() == event_sig_topic.as_ref())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::{
+        metadata::{
+            layout::{
+                Layout,
+                LayoutKey,
+                RootLayout,
+            },
+            ConstructorSpec,
+            ContractSpec,
+            EnvironmentSpec,
+            EventSpec,
+            InkProject,
+            LangError,
+            MessageSpec,
+            ReturnTypeSpec,
+            TypeSpec,
+        },
+        storage::traits::StorageLayout,
+    };
+    use serde_json::json;
+    use std::fs;
+
+    /// Returns the JSON metadata of a contract defining a single event with the
+    /// signature topic.
+    fn metadata_json(code_hash: [u8; 32], event_sig_topic: [u8; 32]) -> String {
+        let layout = RootLayout::new(
+            LayoutKey::from(0u32),
+            <u32 as StorageLayout>::layout(&0),
+            scale_info::meta_type::<u32>(),
+        );
+        let spec = ContractSpec::new()
+            .constructors(vec![ConstructorSpec::from_label("new")
+                .selector([0; 4])
+                .payable(false)
+                .args(Vec::new())
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<()>()))
+                .docs(Vec::new())
+                .done()])
+            .messages(vec![MessageSpec::from_label("get")
+                .selector([1; 4])
+                .mutates(false)
+                .payable(false)
+                .args(Vec::new())
+                .returns(ReturnTypeSpec::new(TypeSpec::of_type::<()>()))
+                .done()])
+            .events(vec![EventSpec::new("Transferred")
+                .module_path("erc20")
+                .signature_topic(Some(event_sig_topic))
+                .args(Vec::new())
+                .docs(Vec::new())
+                .done()])
+            .environment(
+                EnvironmentSpec::new()
+                    .account_id(TypeSpec::of_type::<[u8; 32]>())
+                    .balance(TypeSpec::of_type::<u128>())
+                    .hash(TypeSpec::of_type::<[u8; 32]>())
+                    .timestamp(TypeSpec::of_type::<u64>())
+                    .block_number(TypeSpec::of_type::<u32>())
+                    .chain_extension(TypeSpec::of_type::<()>())
+                    .max_event_topics(4)
+                    .static_buffer_size(16384)
+                    .done(),
+            )
+            .lang_error(TypeSpec::with_name_segs::<LangError, _>(
+                ["ink", "LangError"].into_iter().map(AsRef::as_ref),
+            ))
+            .done();
+        let project = InkProject::new(Layout::Root(layout), spec);
+
+        let mut metadata = json!({
+            "source": {
+                "hash": format!("0x{}", hex::encode(code_hash)),
+                "language": "ink! 5.0.0",
+                "compiler": "rustc 1.78.0",
+            },
+            "contract": {
+                "name": "erc20",
+                "version": "5.0.0",
+                "authors": ["Use Ink <ink@use.ink>"],
+            },
+        });
+        let serde_json::Value::Object(abi) = serde_json::to_value(project).unwrap()
+        else {
+            panic!("the ink! metadata is a JSON object")
+        };
+        metadata.as_object_mut().unwrap().extend(abi);
+        metadata.to_string()
+    }
+
+    #[test]
+    fn load_finds_artifacts_in_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("erc20.json"),
+            metadata_json([1; 32], [11; 32]),
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("target")).unwrap();
+        fs::write(
+            dir.path().join("target").join("flipper.contract"),
+            metadata_json([2; 32], [22; 32]),
+        )
+        .unwrap();
+
+        let registry = ArtifactRegistry::load(dir.path()).unwrap();
+
+        assert_eq!(registry.transcoders.len(), 2);
+        assert!(registry.code_transcoder(&[1u8; 32]).is_some());
+        assert!(registry.code_transcoder(&[2u8; 32]).is_some());
+        assert!(registry.code_transcoder(&[3u8; 32]).is_none());
+    }
+
+    #[test]
+    fn load_skips_files_which_are_no_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("erc20.json"),
+            metadata_json([1; 32], [11; 32]),
+        )
+        .unwrap();
+        fs::write(dir.path().join("package.json"), r#"{ "name": "frontend" }"#).unwrap();
+        fs::write(dir.path().join("erc20.wasm"), [0, 97, 115, 109]).unwrap();
+        fs::write(dir.path().join("README.md"), "# erc20").unwrap();
+
+        let registry = ArtifactRegistry::load(dir.path()).unwrap();
+
+        assert_eq!(registry.transcoders.len(), 1);
+        assert_eq!(registry.code_hashes, vec![[1; 32]]);
+    }
+
+    #[test]
+    fn event_transcoder_is_found_by_signature_topic() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("erc20.json"),
+            metadata_json([1; 32], [11; 32]),
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("flipper.json"),
+            metadata_json([2; 32], [22; 32]),
+        )
+        .unwrap();
+
+        let registry = ArtifactRegistry::load(dir.path()).unwrap();
+
+        let transcoder = registry
+            .event_transcoder(&[22u8; 32])
+            .expect("the event is defined by the flipper metadata");
+        assert!(std::ptr::eq(
+            transcoder,
+            registry.code_transcoder(&[2u8; 32]).unwrap()
+        ));
+        assert!(registry.event_transcoder(&[33u8; 32]).is_none());
+    }
+}
@@ -82,6 +82,12 @@ impl TokenMetadata {
     pub async fn query<C: Config>(url: &Url) -> Result<Self> {
         let rpc_cli = RpcClient::from_url(url_to_string(url)).await?;
         let rpc = LegacyRpcMethods::<C>::new(rpc_cli.clone());
+        Self::fetch(&rpc).await
+    }
+
+    /// Fetch [TokenMetadata] from the node's system properties with an existing RPC
+    /// client.
+    pub async fn fetch<C: Config>(rpc: &LegacyRpcMethods<C>) -> Result<Self> {
         let sys_props = rpc.system_properties().await?;
 
         let default_decimals = json!(12);
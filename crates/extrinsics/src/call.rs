@@ -15,11 +15,14 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    estimate_fee,
     pallet_contracts_primitives::ContractExecResult,
-    state_call,
-    submit_extrinsic,
+    state_call_at,
+    submit_call,
+    ArgsFormat,
     ContractMessageTranscoder,
     ErrorVariant,
+    TrySigner,
 };
 use crate::{
     check_env_types,
@@ -33,6 +36,7 @@ use anyhow::{
 };
 use ink_env::Environment;
 use scale::Encode;
+use serde::Serialize;
 use sp_weights::Weight;
 
 use subxt::{
@@ -49,7 +53,6 @@ use subxt::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
     Config,
     OnlineClient,
 };
@@ -59,6 +62,10 @@ pub struct CallCommandBuilder<C: Config, E: Environment, Signer: Clone> {
     contract: C::AccountId,
     message: String,
     args: Vec<String>,
+    args_format: ArgsFormat,
+    args_document: Option<String>,
+    named_args: Vec<(String, String)>,
+    call_data: Option<Vec<u8>>,
     extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
     gas_limit: Option<u64>,
     proof_size: Option<u64>,
@@ -68,7 +75,7 @@ pub struct CallCommandBuilder<C: Config, E: Environment, Signer: Clone> {
 impl<C: Config, E: Environment, Signer> CallCommandBuilder<C, E, Signer>
 where
     E::Balance: Default,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Returns a clean builder for [`CallExec`].
     pub fn new(
@@ -80,6 +87,10 @@ where
             contract,
             message: message.to_string(),
             args: Vec::new(),
+            args_format: ArgsFormat::default(),
+            args_document: None,
+            named_args: Vec::new(),
+            call_data: None,
             extrinsic_opts,
             gas_limit: None,
             proof_size: None,
@@ -94,6 +105,37 @@ where
         this
     }
 
+    /// Sets the format of the arguments, SCON by default.
+    pub fn args_format(self, args_format: ArgsFormat) -> Self {
+        let mut this = self;
+        this.args_format = args_format;
+        this
+    }
+
+    /// Sets a document holding all the arguments instead of `args`, e.g. the contents
+    /// of an args file, see [`ContractMessageTranscoder::encode_args_document`].
+    pub fn args_document(self, args_document: Option<String>) -> Self {
+        let mut this = self;
+        this.args_document = args_document;
+        this
+    }
+
+    /// Sets the arguments keyed by their labels instead of `args`, see
+    /// [`ContractMessageTranscoder::encode_named`].
+    pub fn named_args(self, named_args: Vec<(String, String)>) -> Self {
+        let mut this = self;
+        this.named_args = named_args;
+        this
+    }
+
+    /// Sets the already encoded call data of the message, i.e. the selector followed by
+    /// the arguments, instead of encoding the `args`, e.g. from a generated client.
+    pub fn call_data(self, call_data: Vec<u8>) -> Self {
+        let mut this = self;
+        this.call_data = Some(call_data);
+        this
+    }
+
     /// Sets the maximum amount of gas to be used for this command.
     pub fn gas_limit(self, gas_limit: Option<u64>) -> Self {
         let mut this = self;
@@ -126,9 +168,23 @@ where
     /// or an error in case of failure.
     pub async fn done(self) -> Result<CallExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
-
-        let call_data = transcoder.encode(&self.message, &self.args)?;
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
+
+        let call_data = if let Some(call_data) = self.call_data {
+            call_data
+        } else if let Some(ref document) = self.args_document {
+            transcoder.encode_args_document(&self.message, document, self.args_format)?
+        } else if !self.named_args.is_empty() {
+            transcoder.encode_named(
+                &self.message,
+                self.named_args
+                    .iter()
+                    .map(|(label, arg)| (label.as_str(), arg)),
+                self.args_format,
+            )?
+        } else {
+            transcoder.encode_with_format(&self.message, &self.args, self.args_format)?
+        };
         tracing::debug!("Message data: {:?}", hex::encode(&call_data));
 
         let url = self.extrinsic_opts.url();
@@ -171,9 +227,9 @@ impl<C: Config, E: Environment, Signer> CallExec<C, E, Signer>
 where
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
-    C::AccountId: EncodeAsType + IntoVisitor,
+    C::AccountId: EncodeAsType + IntoVisitor + Serialize,
     E::Balance: EncodeAsType,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Simulates a contract call without modifying the blockchain.
     ///
@@ -185,16 +241,25 @@ where
     /// Returns the dry run simulation result of type [`ContractExecResult`], which
     /// includes information about the simulated call, or an error in case of failure.
     pub async fn call_dry_run(&self) -> Result<ContractExecResult<E::Balance>> {
+        self.call_dry_run_at(None).await
+    }
+
+    /// Simulates a contract call against the state of the given block, or of the best
+    /// block if `None`.
+    pub async fn call_dry_run_at(
+        &self,
+        block_hash: Option<C::Hash>,
+    ) -> Result<ContractExecResult<E::Balance>> {
         let storage_deposit_limit = self.opts.storage_deposit_limit();
         let call_request = CallRequest {
-            origin: self.opts.signer().account_id(),
+            origin: self.opts.origin(),
             dest: self.contract.clone(),
             value: self.value,
             gas_limit: None,
             storage_deposit_limit,
             input_data: self.call_data.clone(),
         };
-        state_call(&self.rpc, "ContractsApi_call", call_request).await
+        state_call_at(&self.rpc, "ContractsApi_call", call_request, block_hash).await
     }
 
     /// Calls a contract on the blockchain with a specified gas limit.
@@ -232,21 +297,32 @@ where
             None => self.estimate_gas().await?,
         };
         tracing::debug!("calling contract {:?}", self.contract);
-        let storage_deposit_limit = self.opts.storage_deposit_limit();
+        let call = self.build_call(gas_limit);
+
+        let result = submit_call(&self.client, &self.rpc, &self.opts, &call).await?;
+
+        Ok(result)
+    }
+
+    /// Estimates the fee of the contract call extrinsic with the given gas limit, less
+    /// any tip, without modifying the blockchain.
+    pub async fn estimate_fee(&self, gas_limit: Weight) -> Result<u128, ErrorVariant> {
+        let call = self.build_call(gas_limit);
+        estimate_fee(&self.client, &self.rpc, &self.opts, &call).await
+    }
 
-        let call = Call::new(
+    fn build_call(
+        &self,
+        gas_limit: Weight,
+    ) -> subxt::tx::Payload<Call<C::AccountId, E::Balance>> {
+        Call::new(
             self.contract.clone().into(),
             self.value,
             gas_limit,
-            storage_deposit_limit,
+            self.opts.storage_deposit_limit(),
             self.call_data.clone(),
         )
-        .build();
-
-        let result =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
-
-        Ok(result)
+        .build()
     }
 
     /// Estimates the gas required for a contract call without modifying the blockchain.
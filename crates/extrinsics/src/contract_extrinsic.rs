@@ -0,0 +1,709 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::parent_hash;
+use crate::extrinsic_calls::Weight;
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use scale::{
+    Compact,
+    Decode,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    TypeDef,
+    Variant,
+};
+use sp_core::hashing::blake2_256;
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    ext::{
+        scale_decode::{
+            DecodeAsType,
+            IntoVisitor,
+        },
+        scale_value,
+    },
+    utils::MultiAddress,
+    Config,
+    Metadata,
+};
+
+/// The dispatchables of `pallet-contracts` which call or deploy contracts.
+const CONTRACT_CALLS: [&str; 4] = [
+    "call",
+    "instantiate",
+    "instantiate_with_code",
+    "upload_code",
+];
+
+/// An extrinsic decoded with the runtime metadata, with the calls to `pallet-contracts`
+/// it dispatches.
+#[derive(Debug)]
+pub struct ContractExtrinsic<AccountId, Balance, Hash> {
+    signer: Option<AccountId>,
+    calls: Vec<ContractCall<AccountId, Balance, Hash>>,
+}
+
+/// A call to `pallet-contracts`, dispatched directly by an extrinsic or nested in
+/// other calls, e.g. `Utility::batch`.
+#[derive(Debug)]
+pub struct ContractCall<AccountId, Balance, Hash> {
+    name: String,
+    dispatched_by: Vec<String>,
+    contract: Option<AccountId>,
+    code_hash: Option<Hash>,
+    value: Option<Balance>,
+    gas_limit: Option<sp_weights::Weight>,
+    storage_deposit_limit: Option<Balance>,
+    data: Option<Vec<u8>>,
+}
+
+impl<AccountId, Balance, Hash> ContractExtrinsic<AccountId, Balance, Hash>
+where
+    AccountId: IntoVisitor,
+    Balance: IntoVisitor,
+    Hash: IntoVisitor + From<[u8; 32]>,
+{
+    /// Decode an encoded extrinsic, with or without its length prefix, and find the
+    /// calls to `pallet-contracts` it dispatches, including the ones nested in calls
+    /// like `Utility::batch`, `Proxy::proxy` or `Multisig::as_multi`.
+    pub fn decode(extrinsic: &[u8], metadata: &Metadata) -> Result<Self> {
+        let mut input = strip_length_prefix(extrinsic);
+        let input = &mut input;
+        let types = metadata.types();
+        let extrinsic_metadata = metadata.extrinsic();
+
+        let version = u8::decode(input).context("Failed to decode extrinsic version")?;
+        let is_signed = version & 0b1000_0000 != 0;
+        let version = version & 0b0111_1111;
+        if version != extrinsic_metadata.version() {
+            anyhow::bail!(
+                "Unsupported extrinsic version {version}, expected version {}",
+                extrinsic_metadata.version()
+            )
+        }
+
+        let signer = if is_signed {
+            let address = take(input, extrinsic_metadata.address_ty(), types)
+                .context("Failed to decode the address of the signer")?;
+            skip(input, extrinsic_metadata.signature_ty(), types)
+                .context("Failed to decode the signature")?;
+            for extension in extrinsic_metadata.signed_extensions() {
+                skip(input, extension.extra_ty(), types).with_context(|| {
+                    format!(
+                        "Failed to decode the signed extension {}",
+                        extension.identifier()
+                    )
+                })?;
+            }
+            decode_signer(address, extrinsic_metadata.address_ty(), types)
+        } else {
+            None
+        };
+
+        let mut calls = Vec::new();
+        CallDecoder {
+            metadata,
+            call_ty: extrinsic_metadata.call_ty(),
+        }
+        .decode_call(input, &[], &mut calls)?;
+        if !input.is_empty() {
+            anyhow::bail!(
+                "{} bytes left after decoding the extrinsic, the runtime metadata may \
+                not match the extrinsic",
+                input.len()
+            )
+        }
+        Ok(Self { signer, calls })
+    }
+}
+
+impl<AccountId, Balance, Hash> ContractExtrinsic<AccountId, Balance, Hash> {
+    /// Returns the signer of the extrinsic, if signed.
+    pub fn signer(&self) -> Option<&AccountId> {
+        self.signer.as_ref()
+    }
+
+    /// Returns the calls to `pallet-contracts` dispatched by the extrinsic, in the order
+    /// they are dispatched.
+    pub fn calls(&self) -> &[ContractCall<AccountId, Balance, Hash>] {
+        &self.calls
+    }
+}
+
+impl<AccountId, Balance, Hash> ContractCall<AccountId, Balance, Hash> {
+    /// Returns the name of the dispatchable, e.g. `call` or `instantiate_with_code`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the calls dispatching the contract call, outermost first, e.g.
+    /// `Utility::batch`.
+    pub fn dispatched_by(&self) -> &[String] {
+        &self.dispatched_by
+    }
+
+    /// Returns the called contract of a `call`.
+    pub fn contract(&self) -> Option<&AccountId> {
+        self.contract.as_ref()
+    }
+
+    /// Returns the code hash of the instantiated or uploaded code. The code hash of
+    /// the code passed to `instantiate_with_code` and `upload_code` is computed.
+    pub fn code_hash(&self) -> Option<&Hash> {
+        self.code_hash.as_ref()
+    }
+
+    /// Returns the value transferred to the contract.
+    pub fn value(&self) -> Option<&Balance> {
+        self.value.as_ref()
+    }
+
+    /// Returns the gas limit of the call.
+    pub fn gas_limit(&self) -> Option<sp_weights::Weight> {
+        self.gas_limit
+    }
+
+    /// Returns the storage deposit limit of the call, if any.
+    pub fn storage_deposit_limit(&self) -> Option<&Balance> {
+        self.storage_deposit_limit.as_ref()
+    }
+
+    /// Returns the input data of the contract message or constructor.
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.as_deref()
+    }
+
+    /// Returns whether the call instantiates a contract, so that its data is the input
+    /// of a constructor.
+    pub fn is_instantiation(&self) -> bool {
+        self.name.starts_with("instantiate")
+    }
+}
+
+/// Decodes runtime calls, recursing into the calls they dispatch.
+struct CallDecoder<'a> {
+    metadata: &'a Metadata,
+    /// The type of the runtime call enum.
+    call_ty: u32,
+}
+
+impl CallDecoder<'_> {
+    fn decode_call<AccountId, Balance, Hash>(
+        &self,
+        input: &mut &[u8],
+        dispatched_by: &[String],
+        calls: &mut Vec<ContractCall<AccountId, Balance, Hash>>,
+    ) -> Result<()>
+    where
+        AccountId: IntoVisitor,
+        Balance: IntoVisitor,
+        Hash: IntoVisitor + From<[u8; 32]>,
+    {
+        let pallet_index = u8::decode(input).context("Failed to decode pallet index")?;
+        let call_index = u8::decode(input).context("Failed to decode call index")?;
+        let pallet = self
+            .metadata
+            .pallet_by_index(pallet_index)
+            .ok_or_else(|| anyhow!("Pallet with index {pallet_index} not found"))?;
+        let variant = pallet.call_variant_by_index(call_index).ok_or_else(|| {
+            anyhow!(
+                "Call with index {call_index} of {} not found",
+                pallet.name()
+            )
+        })?;
+
+        if pallet.name() == "Contracts" && CONTRACT_CALLS.contains(&variant.name.as_str())
+        {
+            let call = self
+                .decode_contract_call(input, variant, dispatched_by)
+                .with_context(|| {
+                    format!("Failed to decode Contracts::{}", variant.name)
+                })?;
+            calls.push(call);
+            return Ok(())
+        }
+
+        let mut outer_calls = dispatched_by.to_vec();
+        outer_calls.push(format!("{}::{}", pallet.name(), variant.name));
+        for field in &variant.fields {
+            let ty = field.ty.id;
+            if ty == self.call_ty {
+                self.decode_call(input, &outer_calls, calls)?;
+            } else if self.is_call_sequence(ty) {
+                let len = Compact::<u32>::decode(input)?.0;
+                for _ in 0..len {
+                    self.decode_call(input, &outer_calls, calls)?;
+                }
+            } else {
+                skip(input, ty, self.metadata.types()).with_context(|| {
+                    format!(
+                        "Failed to decode the arguments of {}",
+                        outer_calls.join(" > ")
+                    )
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn decode_contract_call<AccountId, Balance, Hash>(
+        &self,
+        input: &mut &[u8],
+        variant: &Variant<PortableForm>,
+        dispatched_by: &[String],
+    ) -> Result<ContractCall<AccountId, Balance, Hash>>
+    where
+        AccountId: IntoVisitor,
+        Balance: IntoVisitor,
+        Hash: IntoVisitor + From<[u8; 32]>,
+    {
+        let types = self.metadata.types();
+        let mut call = ContractCall {
+            name: variant.name.clone(),
+            dispatched_by: dispatched_by.to_vec(),
+            contract: None,
+            code_hash: None,
+            value: None,
+            gas_limit: None,
+            storage_deposit_limit: None,
+            data: None,
+        };
+        for field in &variant.fields {
+            let ty = field.ty.id;
+            match field.name.as_deref() {
+                Some("dest") => {
+                    let MultiAddress::Id(contract) =
+                        decode::<MultiAddress<AccountId, ()>>(input, ty, types)?
+                    else {
+                        anyhow::bail!("Unsupported address of the called contract")
+                    };
+                    call.contract = Some(contract);
+                }
+                Some("value") => call.value = Some(decode(input, ty, types)?),
+                Some("gas_limit") => {
+                    call.gas_limit = Some(decode::<Weight>(input, ty, types)?.into())
+                }
+                Some("storage_deposit_limit") => {
+                    call.storage_deposit_limit = decode(input, ty, types)?
+                }
+                Some("code_hash") => call.code_hash = Some(decode(input, ty, types)?),
+                Some("code") => {
+                    let code = decode::<Vec<u8>>(input, ty, types)?;
+                    call.code_hash = Some(blake2_256(&code).into());
+                }
+                Some("data") => call.data = Some(decode(input, ty, types)?),
+                _ => skip(input, ty, types)?,
+            }
+        }
+        Ok(call)
+    }
+
+    /// Returns whether the type is a sequence of runtime calls, e.g. the calls of
+    /// `Utility::batch`.
+    fn is_call_sequence(&self, ty: u32) -> bool {
+        matches!(
+            self.metadata.types().resolve(ty).map(|ty| &ty.type_def),
+            Some(TypeDef::Sequence(seq)) if seq.type_param.id == self.call_ty
+        )
+    }
+}
+
+/// Fetch the encoded extrinsic with the index in the block with the number, together
+/// with the hash of the parent block, i.e. of the state the extrinsic was applied to.
+pub async fn fetch_extrinsic<C: Config>(
+    rpc: &LegacyRpcMethods<C>,
+    block_number: u64,
+    index: usize,
+) -> Result<(Vec<u8>, C::Hash)> {
+    let hash = rpc
+        .chain_get_block_hash(Some(block_number.into()))
+        .await?
+        .ok_or_else(|| anyhow!("Block #{block_number} not found"))?;
+    let block = rpc
+        .chain_get_block(Some(hash))
+        .await?
+        .ok_or_else(|| anyhow!("Block #{block_number} not found"))?;
+    let parent_hash = parent_hash::<C>(&block.block.header)?;
+    let extrinsic = block
+        .block
+        .extrinsics
+        .into_iter()
+        .nth(index)
+        .ok_or_else(|| {
+            anyhow!("Extrinsic #{index} not found in block #{block_number}")
+        })?;
+    Ok((extrinsic.0, parent_hash))
+}
+
+/// Strips the compact encoded length prefix of an extrinsic, if present.
+fn strip_length_prefix(extrinsic: &[u8]) -> &[u8] {
+    let mut input = extrinsic;
+    match Compact::<u32>::decode(&mut input) {
+        Ok(len) if len.0 as usize == input.len() => input,
+        _ => extrinsic,
+    }
+}
+
+fn decode<T: DecodeAsType>(
+    input: &mut &[u8],
+    ty: u32,
+    types: &PortableRegistry,
+) -> Result<T> {
+    Ok(T::decode_as_type(input, &ty, types)?)
+}
+
+fn skip(input: &mut &[u8], ty: u32, types: &PortableRegistry) -> Result<()> {
+    scale_value::scale::decode_as_type(input, &ty, types)?;
+    Ok(())
+}
+
+/// Skips a value of the type, returning its encoded bytes.
+fn take<'a>(input: &mut &'a [u8], ty: u32, types: &PortableRegistry) -> Result<&'a [u8]> {
+    let start = *input;
+    skip(input, ty, types)?;
+    Ok(&start[..start.len() - input.len()])
+}
+
+/// Decodes the account of the signer from the address, which is either a
+/// `MultiAddress` or the account itself.
+fn decode_signer<AccountId: IntoVisitor>(
+    address: &[u8],
+    ty: u32,
+    types: &PortableRegistry,
+) -> Option<AccountId> {
+    match decode::<MultiAddress<AccountId, ()>>(&mut &address[..], ty, types) {
+        Ok(MultiAddress::Id(account)) => Some(account),
+        Ok(_) => None,
+        Err(_) => decode(&mut &address[..], ty, types).ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extrinsic_calls;
+    use frame_metadata::v15::{
+        CustomMetadata,
+        ExtrinsicMetadata,
+        OuterEnums,
+        PalletCallMetadata,
+        PalletMetadata,
+        RuntimeMetadataV15,
+    };
+    use scale::Encode;
+    use scale_info::{
+        meta_type,
+        TypeInfo,
+    };
+    use subxt::{
+        backend::RuntimeVersion,
+        config::SubstrateExtrinsicParamsBuilder,
+        tx::TxPayload,
+        utils::{
+            AccountId32,
+            H256,
+        },
+        OfflineClient,
+        SubstrateConfig,
+    };
+    use subxt_signer::sr25519::dev;
+
+    type Extrinsic = ContractExtrinsic<AccountId32, u128, H256>;
+
+    /// The calls of a runtime with `pallet-utility`, `pallet-proxy`, `pallet-multisig`
+    /// and `pallet-contracts`, reduced to the dispatchables used by the tests.
+    #[allow(dead_code)]
+    #[derive(Encode, TypeInfo)]
+    enum RuntimeCall {
+        #[codec(index = 1)]
+        Utility(UtilityCall),
+        #[codec(index = 2)]
+        Proxy(ProxyCall),
+        #[codec(index = 3)]
+        Multisig(MultisigCall),
+        #[codec(index = 8)]
+        Contracts(ContractsCall),
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum UtilityCall {
+        batch { calls: Vec<RuntimeCall> },
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum ProxyCall {
+        proxy {
+            real: MultiAddress<AccountId32, ()>,
+            force_proxy_type: Option<u8>,
+            call: Box<RuntimeCall>,
+        },
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum MultisigCall {
+        as_multi {
+            threshold: u16,
+            other_signatories: Vec<AccountId32>,
+            maybe_timepoint: Option<Timepoint>,
+            call: Box<RuntimeCall>,
+            max_weight: sp_weights::Weight,
+        },
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Timepoint {
+        height: u32,
+        index: u32,
+    }
+
+    #[allow(dead_code, non_camel_case_types)]
+    #[derive(Encode, TypeInfo)]
+    enum ContractsCall {
+        call {
+            dest: MultiAddress<AccountId32, ()>,
+            #[codec(compact)]
+            value: u128,
+            gas_limit: sp_weights::Weight,
+            storage_deposit_limit: Option<Compact<u128>>,
+            data: Vec<u8>,
+        },
+        instantiate_with_code {
+            #[codec(compact)]
+            value: u128,
+            gas_limit: sp_weights::Weight,
+            storage_deposit_limit: Option<Compact<u128>>,
+            code: Vec<u8>,
+            data: Vec<u8>,
+            salt: Vec<u8>,
+        },
+    }
+
+    fn pallet<Call: TypeInfo + 'static>(name: &'static str, index: u8) -> PalletMetadata {
+        PalletMetadata {
+            name,
+            storage: None,
+            calls: Some(PalletCallMetadata {
+                ty: meta_type::<Call>(),
+            }),
+            event: None,
+            constants: vec![],
+            error: None,
+            index,
+            docs: vec![],
+        }
+    }
+
+    fn runtime_metadata() -> Metadata {
+        let metadata = RuntimeMetadataV15::new(
+            vec![
+                pallet::<UtilityCall>("Utility", 1),
+                pallet::<ProxyCall>("Proxy", 2),
+                pallet::<MultisigCall>("Multisig", 3),
+                pallet::<ContractsCall>("Contracts", 8),
+            ],
+            ExtrinsicMetadata {
+                version: 4,
+                address_ty: meta_type::<MultiAddress<AccountId32, ()>>(),
+                call_ty: meta_type::<RuntimeCall>(),
+                signature_ty: meta_type::<[u8; 64]>(),
+                extra_ty: meta_type::<()>(),
+                signed_extensions: vec![],
+            },
+            meta_type::<()>(),
+            vec![],
+            OuterEnums {
+                call_enum_ty: meta_type::<RuntimeCall>(),
+                event_enum_ty: meta_type::<()>(),
+                error_enum_ty: meta_type::<()>(),
+            },
+            CustomMetadata {
+                map: Default::default(),
+            },
+        );
+        subxt::metadata::types::Metadata::try_from(metadata)
+            .expect("the metadata must be valid")
+            .into()
+    }
+
+    fn unsigned_extrinsic(call: RuntimeCall) -> Vec<u8> {
+        (4u8, call).encode().encode()
+    }
+
+    #[test]
+    fn decode_signed_contract_call() {
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        let metadata =
+            Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode");
+        let client = OfflineClient::<SubstrateConfig>::new(
+            Default::default(),
+            RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            },
+            metadata.clone(),
+        );
+        let contract = AccountId32([7; 32]);
+        let payload = extrinsic_calls::Call::new(
+            MultiAddress::Id(contract.clone()),
+            100,
+            sp_weights::Weight::from_parts(1_000, 2_000),
+            Some(50),
+            vec![1, 2, 3, 4],
+        )
+        .build();
+        let extrinsic = client
+            .tx()
+            .create_signed_offline(
+                &payload,
+                &dev::alice(),
+                SubstrateExtrinsicParamsBuilder::new().nonce(0).build(),
+            )
+            .expect("the extrinsic must be signed");
+
+        let decoded = Extrinsic::decode(extrinsic.encoded(), &metadata)
+            .expect("the extrinsic must decode");
+
+        assert_eq!(decoded.signer(), Some(&dev::alice().public_key().into()));
+        let [call] = decoded.calls() else {
+            panic!("expected exactly one contract call")
+        };
+        assert_eq!(call.name(), "call");
+        assert!(call.dispatched_by().is_empty());
+        assert_eq!(call.contract(), Some(&contract));
+        assert_eq!(call.value(), Some(&100));
+        assert_eq!(
+            call.gas_limit(),
+            Some(sp_weights::Weight::from_parts(1_000, 2_000))
+        );
+        assert_eq!(call.storage_deposit_limit(), Some(&50));
+        assert_eq!(call.data(), Some(&[1, 2, 3, 4][..]));
+        assert!(!call.is_instantiation());
+
+        let call_data = payload
+            .encode_call_data(&metadata)
+            .expect("the call must encode");
+        assert!(
+            Extrinsic::decode(&call_data, &metadata).is_err(),
+            "a call without the extrinsic version must not decode"
+        );
+    }
+
+    #[test]
+    fn decode_contract_calls_nested_in_batch_and_proxy() {
+        let metadata = runtime_metadata();
+        let real = AccountId32([1; 32]);
+        let contract = AccountId32([2; 32]);
+        let code = vec![0, 97, 115, 109];
+        let extrinsic = unsigned_extrinsic(RuntimeCall::Utility(UtilityCall::batch {
+            calls: vec![
+                RuntimeCall::Contracts(ContractsCall::instantiate_with_code {
+                    value: 1,
+                    gas_limit: sp_weights::Weight::from_parts(10, 20),
+                    storage_deposit_limit: None,
+                    code: code.clone(),
+                    data: vec![0xaa],
+                    salt: vec![],
+                }),
+                RuntimeCall::Proxy(ProxyCall::proxy {
+                    real: MultiAddress::Id(real),
+                    force_proxy_type: None,
+                    call: Box::new(RuntimeCall::Contracts(ContractsCall::call {
+                        dest: MultiAddress::Id(contract.clone()),
+                        value: 2,
+                        gas_limit: sp_weights::Weight::from_parts(30, 40),
+                        storage_deposit_limit: Some(Compact(3)),
+                        data: vec![0xbb],
+                    })),
+                }),
+            ],
+        }));
+
+        let decoded =
+            Extrinsic::decode(&extrinsic, &metadata).expect("the extrinsic must decode");
+
+        assert_eq!(decoded.signer(), None);
+        let [instantiate, call] = decoded.calls() else {
+            panic!("expected exactly two contract calls")
+        };
+        assert_eq!(instantiate.name(), "instantiate_with_code");
+        assert_eq!(instantiate.dispatched_by(), ["Utility::batch"]);
+        assert_eq!(instantiate.code_hash(), Some(&H256(blake2_256(&code))));
+        assert_eq!(instantiate.value(), Some(&1));
+        assert_eq!(instantiate.storage_deposit_limit(), None);
+        assert_eq!(instantiate.data(), Some(&[0xaa][..]));
+        assert!(instantiate.is_instantiation());
+
+        assert_eq!(call.name(), "call");
+        assert_eq!(call.dispatched_by(), ["Utility::batch", "Proxy::proxy"]);
+        assert_eq!(call.contract(), Some(&contract));
+        assert_eq!(call.value(), Some(&2));
+        assert_eq!(
+            call.gas_limit(),
+            Some(sp_weights::Weight::from_parts(30, 40))
+        );
+        assert_eq!(call.storage_deposit_limit(), Some(&3));
+        assert_eq!(call.data(), Some(&[0xbb][..]));
+    }
+
+    #[test]
+    fn decode_contract_call_nested_in_multisig() {
+        let metadata = runtime_metadata();
+        let contract = AccountId32([3; 32]);
+        let extrinsic =
+            unsigned_extrinsic(RuntimeCall::Multisig(MultisigCall::as_multi {
+                threshold: 2,
+                other_signatories: vec![AccountId32([4; 32]), AccountId32([5; 32])],
+                maybe_timepoint: Some(Timepoint {
+                    height: 100,
+                    index: 1,
+                }),
+                call: Box::new(RuntimeCall::Contracts(ContractsCall::call {
+                    dest: MultiAddress::Id(contract.clone()),
+                    value: 4,
+                    gas_limit: sp_weights::Weight::from_parts(50, 60),
+                    storage_deposit_limit: None,
+                    data: vec![0xcc],
+                })),
+                max_weight: sp_weights::Weight::from_parts(1_000, 2_000),
+            }));
+
+        let decoded =
+            Extrinsic::decode(&extrinsic, &metadata).expect("the extrinsic must decode");
+
+        let [call] = decoded.calls() else {
+            panic!("expected exactly one contract call")
+        };
+        assert_eq!(call.name(), "call");
+        assert_eq!(call.dispatched_by(), ["Multisig::as_multi"]);
+        assert_eq!(call.contract(), Some(&contract));
+        assert_eq!(call.value(), Some(&4));
+        assert_eq!(
+            call.gas_limit(),
+            Some(sp_weights::Weight::from_parts(50, 60))
+        );
+        assert_eq!(call.data(), Some(&[0xcc][..]));
+    }
+}
@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::get_best_block;
+use super::{
+    events::ContractInstantiated,
+    get_best_block,
+};
 use anyhow::{
     anyhow,
     Result,
@@ -30,6 +33,7 @@ use scale::Decode;
 use std::option::Option;
 use subxt::{
     backend::legacy::LegacyRpcMethods,
+    config::Header,
     dynamic::DecodedValueThunk,
     ext::{
         scale_decode::{
@@ -46,8 +50,8 @@ use subxt::{
 /// Return the account data for an account ID.
 async fn get_account_balance<C: Config, E: Environment>(
     account: &C::AccountId,
-    rpc: &LegacyRpcMethods<C>,
     client: &OnlineClient<C>,
+    block_hash: C::Hash,
 ) -> Result<AccountData<E::Balance>>
 where
     C::AccountId: AsRef<[u8]>,
@@ -55,11 +59,10 @@ where
 {
     let storage_query =
         subxt::dynamic::storage("System", "Account", vec![Value::from_bytes(account)]);
-    let best_block = get_best_block(rpc).await?;
 
     let account = client
         .storage()
-        .at(best_block)
+        .at(block_hash)
         .fetch(&storage_query)
         .await?
         .ok_or_else(|| anyhow::anyhow!("Failed to fetch account data"))?;
@@ -73,13 +76,29 @@ pub async fn fetch_contract_info<C: Config, E: Environment>(
     contract: &C::AccountId,
     rpc: &LegacyRpcMethods<C>,
     client: &OnlineClient<C>,
-) -> Result<ContractInfo<C::Hash, E::Balance>>
+) -> Result<ContractInfo<C::Hash, E::Balance, C::AccountId>>
+where
+    C::AccountId: AsRef<[u8]> + Display + IntoVisitor,
+    C::Hash: IntoVisitor,
+    E::Balance: IntoVisitor,
+{
+    fetch_contract_info_at::<C, E>(contract, rpc, client, None).await
+}
+
+/// Fetch the contract info from the storage at the given block, or at the best block if
+/// `None`.
+pub async fn fetch_contract_info_at<C: Config, E: Environment>(
+    contract: &C::AccountId,
+    rpc: &LegacyRpcMethods<C>,
+    client: &OnlineClient<C>,
+    block_hash: Option<C::Hash>,
+) -> Result<ContractInfo<C::Hash, E::Balance, C::AccountId>>
 where
     C::AccountId: AsRef<[u8]> + Display + IntoVisitor,
     C::Hash: IntoVisitor,
     E::Balance: IntoVisitor,
 {
-    let best_block = get_best_block(rpc).await?;
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
 
     let contract_info_address = dynamic(
         "Contracts",
@@ -88,7 +107,7 @@ where
     );
     let contract_info_value = client
         .storage()
-        .at(best_block)
+        .at(block_hash)
         .fetch(&contract_info_address)
         .await?
         .ok_or_else(|| {
@@ -103,7 +122,7 @@ where
     let deposit_account = contract_info_raw.get_deposit_account();
 
     let deposit_account_data =
-        get_account_balance::<C, E>(deposit_account, rpc, client).await?;
+        get_account_balance::<C, E>(deposit_account, client, block_hash).await?;
     Ok(contract_info_raw.into_contract_info(deposit_account_data))
 }
 
@@ -160,7 +179,7 @@ where
     pub fn into_contract_info(
         self,
         deposit: AccountData<E::Balance>,
-    ) -> ContractInfo<C::Hash, E::Balance> {
+    ) -> ContractInfo<C::Hash, E::Balance, C::AccountId> {
         let total_deposit = if self.deposit_on_main_account {
             deposit.reserved
         } else {
@@ -173,6 +192,7 @@ where
             storage_items: self.contract_info.storage_items,
             storage_items_deposit: self.contract_info.storage_item_deposit,
             storage_total_deposit: total_deposit,
+            deposit_account: self.deposit_account,
         }
     }
 
@@ -184,24 +204,20 @@ where
 }
 
 #[derive(Debug, PartialEq, serde::Serialize)]
-pub struct ContractInfo<Hash, Balance> {
+pub struct ContractInfo<Hash, Balance, AccountId> {
     trie_id: TrieId,
     code_hash: Hash,
     storage_items: u32,
     storage_items_deposit: Balance,
     storage_total_deposit: Balance,
+    deposit_account: AccountId,
 }
 
-impl<Hash, Balance> ContractInfo<Hash, Balance>
+impl<Hash, Balance, AccountId> ContractInfo<Hash, Balance, AccountId>
 where
     Hash: serde::Serialize,
     Balance: serde::Serialize + Copy,
 {
-    /// Convert and return contract info in JSON format.
-    pub fn to_json(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(self)?)
-    }
-
     /// Return the trie_id of the contract.
     pub fn trie_id(&self) -> &TrieId {
         &self.trie_id
@@ -226,6 +242,23 @@ where
     pub fn storage_total_deposit(&self) -> Balance {
         self.storage_total_deposit
     }
+
+    /// Return the account holding the storage deposit of the contract.
+    pub fn deposit_account(&self) -> &AccountId {
+        &self.deposit_account
+    }
+}
+
+impl<Hash, Balance, AccountId> ContractInfo<Hash, Balance, AccountId>
+where
+    Hash: serde::Serialize,
+    Balance: serde::Serialize,
+    AccountId: serde::Serialize,
+{
+    /// Convert and return contract info in JSON format.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
 }
 
 /// A contract's child trie id.
@@ -266,13 +299,27 @@ pub async fn fetch_wasm_code<C: Config>(
 where
     C::Hash: AsRef<[u8]> + Display + IntoVisitor,
 {
-    let best_block = get_best_block(rpc).await?;
+    fetch_wasm_code_at(client, rpc, hash, None).await
+}
+
+/// Fetch the contract wasm code from the storage at the given block, or at the best
+/// block if `None`.
+pub async fn fetch_wasm_code_at<C: Config>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    hash: &C::Hash,
+    block_hash: Option<C::Hash>,
+) -> Result<Vec<u8>>
+where
+    C::Hash: AsRef<[u8]> + Display + IntoVisitor,
+{
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
 
     let pristine_code_address =
         dynamic("Contracts", "PristineCode", vec![Value::from_bytes(hash)]);
     let pristine_code = client
         .storage()
-        .at(best_block)
+        .at(block_hash)
         .fetch(&pristine_code_address)
         .await?
         .ok_or_else(|| anyhow!("No WASM code was found for code hash {}", hash))?;
@@ -282,6 +329,17 @@ where
     pristine_code.map(|v| v.0)
 }
 
+/// Returns the given block hash, or the hash of the best block if `None`.
+async fn block_hash_or_best<C: Config>(
+    rpc: &LegacyRpcMethods<C>,
+    block_hash: Option<C::Hash>,
+) -> Result<C::Hash> {
+    match block_hash {
+        Some(block_hash) => Ok(block_hash),
+        None => Ok(get_best_block(rpc).await?),
+    }
+}
+
 /// Parse a contract account address from a storage key. Returns error if a key is
 /// malformated.
 fn parse_contract_account_address<C: Config>(
@@ -301,30 +359,291 @@ where
 }
 
 /// Fetch all contract addresses from the storage using the provided client.
+#[deprecated(note = "use `fetch_contracts_page_at` to fetch the contracts page by page")]
+#[allow(deprecated)]
 pub async fn fetch_all_contracts<C: Config>(
     client: &OnlineClient<C>,
     rpc: &LegacyRpcMethods<C>,
 ) -> Result<Vec<C::AccountId>>
 where
-    C::AccountId: Decode,
+    C::AccountId: AsRef<[u8]> + Decode,
 {
-    let best_block = get_best_block(rpc).await?;
-    let root_key =
-        subxt::dynamic::storage("Contracts", "ContractInfoOf", ()).to_root_bytes();
-    let mut keys = client
-        .storage()
-        .at(best_block)
-        .fetch_raw_keys(root_key.clone())
+    fetch_all_contracts_at(client, rpc, None).await
+}
+
+/// Fetch all contract addresses from the storage at the given block, or at the best
+/// block if `None`.
+#[deprecated(note = "use `fetch_contracts_page_at` to fetch the contracts page by page")]
+pub async fn fetch_all_contracts_at<C: Config>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    block_hash: Option<C::Hash>,
+) -> Result<Vec<C::AccountId>>
+where
+    C::AccountId: AsRef<[u8]> + Decode,
+{
+    const PAGE_SIZE: u32 = 1000;
+
+    // pin the block, so that all pages are fetched from the same state
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
+    let mut contracts: Vec<C::AccountId> = Vec::new();
+    loop {
+        let page = fetch_contracts_page_at(
+            client,
+            rpc,
+            contracts.last(),
+            PAGE_SIZE,
+            Some(block_hash),
+        )
+        .await?;
+        let is_last_page = page.len() < PAGE_SIZE as usize;
+        contracts.extend(page);
+        if is_last_page {
+            return Ok(contracts)
+        }
+    }
+}
+
+/// Fetch a page of contract addresses from the storage at the given block, or at the
+/// best block if `None`.
+///
+/// Returns at most `count` addresses in the order of their storage keys, starting after
+/// the `start_after` contract if given.
+pub async fn fetch_contracts_page_at<C: Config>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    start_after: Option<&C::AccountId>,
+    count: u32,
+    block_hash: Option<C::Hash>,
+) -> Result<Vec<C::AccountId>>
+where
+    C::AccountId: AsRef<[u8]> + Decode,
+{
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
+    let root_key = dynamic("Contracts", "ContractInfoOf", ()).to_root_bytes();
+    let start_key = start_after
+        .map(|contract| {
+            let address = dynamic(
+                "Contracts",
+                "ContractInfoOf",
+                vec![Value::from_bytes(contract)],
+            );
+            client.storage().address_bytes(&address)
+        })
+        .transpose()?;
+    let keys = rpc
+        .state_get_keys_paged(&root_key, count, start_key.as_deref(), Some(block_hash))
         .await?;
 
-    let mut contract_accounts = Vec::new();
-    while let Some(result) = keys.next().await {
-        let key = result?;
-        let contract_account = parse_contract_account_address::<C>(&key, root_key.len())?;
-        contract_accounts.push(contract_account);
+    keys.iter()
+        .map(|key| parse_contract_account_address::<C>(key, root_key.len()))
+        .collect()
+}
+
+/// Fetch the info about the code with the given hash from the storage at the given
+/// block, or at the best block if `None`.
+pub async fn fetch_code_info_at<C: Config, E: Environment>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    hash: &C::Hash,
+    block_hash: Option<C::Hash>,
+) -> Result<CodeInfo<C::AccountId, E::Balance>>
+where
+    C::AccountId: IntoVisitor,
+    C::Hash: AsRef<[u8]> + Display,
+    E::Balance: IntoVisitor,
+{
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
+
+    let code_info_address =
+        dynamic("Contracts", "CodeInfoOf", vec![Value::from_bytes(hash)]);
+    let code_info = client
+        .storage()
+        .at(block_hash)
+        .fetch(&code_info_address)
+        .await?
+        .ok_or_else(|| anyhow!("No code info was found for code hash {}", hash))?
+        .as_type::<CodeInfoOf<C::AccountId, E::Balance>>()
+        .map_err(|e| anyhow!("Code info could not be parsed: {e}"))?;
+
+    Ok(CodeInfo {
+        owner: code_info.owner,
+        deposit: code_info.deposit,
+        refcount: code_info.refcount,
+    })
+}
+
+/// Search the chain for the block in which the contract was instantiated, and the
+/// account which instantiated it.
+///
+/// The block is found by a binary search over the block numbers up to the given block,
+/// or the best block if `None`, for a block in which the contract exists while it did
+/// not exist in the block before. The deployer is then read from the `Instantiated`
+/// event of that block. If the contract was terminated and instantiated again at the
+/// same address, the search may find an earlier instantiation than the latest one.
+/// Requires a node which keeps the state of old blocks, i.e. an archive node.
+pub async fn fetch_contract_instantiation<C: Config>(
+    contract: &C::AccountId,
+    rpc: &LegacyRpcMethods<C>,
+    client: &OnlineClient<C>,
+    block_hash: Option<C::Hash>,
+) -> Result<ContractInstantiation<C::AccountId, C::Hash>>
+where
+    C::AccountId: AsRef<[u8]> + Display + IntoVisitor,
+{
+    let block_hash = block_hash_or_best(rpc, block_hash).await?;
+    let contract_info_address = client.storage().address_bytes(&dynamic(
+        "Contracts",
+        "ContractInfoOf",
+        vec![Value::from_bytes(contract)],
+    ))?;
+    let contract_exists = |block_hash: C::Hash| {
+        let address = contract_info_address.clone();
+        async move {
+            let info = client.storage().at(block_hash).fetch_raw(address).await?;
+            Ok::<_, anyhow::Error>(info.is_some())
+        }
+    };
+    let block_hash_of = |number: u64| {
+        async move {
+            rpc.chain_get_block_hash(Some(number.into()))
+                .await?
+                .ok_or_else(|| anyhow!("Block #{number} not found"))
+        }
+    };
+
+    if !contract_exists(block_hash).await? {
+        return Err(anyhow!(
+            "No contract information was found for account id {}",
+            contract
+        ))
+    }
+    let header = rpc
+        .chain_get_header(Some(block_hash))
+        .await?
+        .ok_or_else(|| anyhow!("Block {block_hash:?} not found"))?;
+
+    // The contract exists at block `high` and does not exist before block `low`.
+    let (mut low, mut high) = (0u64, header.number().into());
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if contract_exists(block_hash_of(mid).await?).await? {
+            high = mid
+        } else {
+            low = mid + 1
+        }
     }
 
-    Ok(contract_accounts)
+    let found_hash = block_hash_of(high).await?;
+    if high == 0 {
+        return Ok(ContractInstantiation {
+            block_number: high,
+            block_hash: found_hash,
+            deployer: None,
+        })
+    }
+    // The search always stops at a block in which the contract exists while it did not
+    // exist in the block before, so that block contains an `Instantiated` event of the
+    // contract, though not necessarily of its latest instantiation.
+    let deployer = instantiated_by(client, found_hash, contract)
+        .await?
+        .ok_or_else(|| {
+            anyhow!(
+                "No `Instantiated` event was found for the contract {} in block #{}",
+                contract,
+                high
+            )
+        })?;
+    Ok(ContractInstantiation {
+        block_number: high,
+        block_hash: found_hash,
+        deployer: Some(deployer),
+    })
+}
+
+/// Returns the account which instantiated the contract in the block, if any.
+async fn instantiated_by<C: Config>(
+    client: &OnlineClient<C>,
+    block_hash: C::Hash,
+    contract: &C::AccountId,
+) -> Result<Option<C::AccountId>>
+where
+    C::AccountId: AsRef<[u8]> + IntoVisitor,
+{
+    let events = client.events().at(block_hash).await?;
+    for event in events.find::<ContractInstantiated<C::AccountId>>() {
+        let event = event?;
+        if event.contract.as_ref() == contract.as_ref() {
+            return Ok(Some(event.deployer))
+        }
+    }
+    Ok(None)
+}
+
+/// The block in which a contract was instantiated and the account which instantiated it.
+#[derive(Debug, Eq, PartialEq, serde::Serialize)]
+pub struct ContractInstantiation<AccountId, Hash> {
+    block_number: u64,
+    block_hash: Hash,
+    deployer: Option<AccountId>,
+}
+
+impl<AccountId, Hash> ContractInstantiation<AccountId, Hash> {
+    /// Return the number of the block in which the contract was instantiated.
+    pub fn block_number(&self) -> u64 {
+        self.block_number
+    }
+
+    /// Return the hash of the block in which the contract was instantiated.
+    pub fn block_hash(&self) -> &Hash {
+        &self.block_hash
+    }
+
+    /// Return the account which instantiated the contract. `None` for contracts in the
+    /// genesis state.
+    pub fn deployer(&self) -> Option<&AccountId> {
+        self.deployer.as_ref()
+    }
+
+    /// Map the account which instantiated the contract, e.g. to format it for display.
+    pub fn map_deployer<T>(
+        self,
+        f: impl FnOnce(AccountId) -> T,
+    ) -> ContractInstantiation<T, Hash> {
+        ContractInstantiation {
+            block_number: self.block_number,
+            block_hash: self.block_hash,
+            deployer: self.deployer.map(f),
+        }
+    }
+}
+
+/// The info about an uploaded contract code.
+#[derive(Debug, Eq, PartialEq, serde::Serialize)]
+pub struct CodeInfo<AccountId, Balance> {
+    owner: AccountId,
+    deposit: Balance,
+    refcount: u64,
+}
+
+impl<AccountId, Balance> CodeInfo<AccountId, Balance>
+where
+    Balance: Copy,
+{
+    /// Return the account which uploaded the code and holds its deposit.
+    pub fn owner(&self) -> &AccountId {
+        &self.owner
+    }
+
+    /// Return the deposit reserved for storing the code.
+    pub fn deposit(&self) -> Balance {
+        self.deposit
+    }
+
+    /// Return the number of contracts instantiated from the code.
+    pub fn refcount(&self) -> u64 {
+        self.refcount
+    }
 }
 
 /// A struct used in the storage reads to access account info.
@@ -357,6 +676,15 @@ struct ContractInfoOf<Hash, Balance> {
     storage_item_deposit: Balance,
 }
 
+/// A struct used in the storage reads to access code info.
+#[derive(Debug, DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct CodeInfoOf<AccountId, Balance> {
+    owner: AccountId,
+    deposit: Balance,
+    refcount: u64,
+}
+
 /// A struct used in storage reads to access the deposit account from contract info.
 #[derive(Debug, DecodeAsType)]
 #[decode_as_type(crate_path = "subxt::ext::scale_decode")]
@@ -470,6 +798,7 @@ mod tests {
                 storage_items: contract_info_v11.storage_items,
                 storage_items_deposit: contract_info_v11.storage_item_deposit,
                 storage_total_deposit: account_data.free,
+                deposit_account: AccountId32([7u8; 32]),
             }
         );
     }
@@ -539,7 +868,49 @@ mod tests {
                 storage_items: contract_info_v15.storage_items,
                 storage_items_deposit: contract_info_v15.storage_item_deposit,
                 storage_total_deposit: account_data.reserved,
+                deposit_account: AccountId32([0u8; 32]),
             }
         );
     }
+
+    #[test]
+    fn code_info_v15_decode_works() {
+        #[subxt::subxt(runtime_metadata_path = "src/test_runtime_api/metadata_v15.scale")]
+        mod api_v15 {}
+
+        use api_v15::runtime_types::pallet_contracts::wasm::{
+            CodeInfo as CodeInfoV15,
+            Determinism,
+        };
+
+        let metadata_bytes = std::fs::read("src/test_runtime_api/metadata_v15.scale")
+            .expect("the metadata must be present");
+        let metadata =
+            Metadata::decode(&mut &*metadata_bytes).expect("the metadata must decode");
+        let code_info_type_id =
+            get_metadata_type_index("CodeInfo", "pallet_contracts::wasm", &metadata)
+                .expect("the code info type must be present in the metadata");
+
+        let code_info_v15 = CodeInfoV15 {
+            owner: AccountId32([7u8; 32]),
+            deposit: 100,
+            refcount: 3,
+            determinism: Determinism::Enforced,
+            code_len: 1024,
+        };
+
+        let code_info_thunk = DecodedValueThunk::decode_with_metadata(
+            &mut &*code_info_v15.encode(),
+            code_info_type_id as u32,
+            &metadata.into(),
+        )
+        .expect("the code info must be decoded");
+        let code_info = code_info_thunk
+            .as_type::<CodeInfoOf<AccountId32, u128>>()
+            .expect("the code info must be decoded as type");
+
+        assert_eq!(code_info.owner, code_info_v15.owner);
+        assert_eq!(code_info.deposit, code_info_v15.deposit);
+        assert_eq!(code_info.refcount, code_info_v15.refcount);
+    }
 }
@@ -0,0 +1,189 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use serde::{
+    Deserialize,
+    Serialize,
+};
+use serde_json::{
+    json,
+    Map,
+    Value as JsonValue,
+};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    path::Path,
+};
+use subxt::backend::legacy::rpc_methods::Bytes;
+
+use super::ContractStorageData;
+
+/// A portable snapshot of the on-chain state of a contract at a given block.
+///
+/// Holds the raw top-level storage entries of the contract (its account, contract info,
+/// code info and pristine code) together with its full child trie storage, so that the
+/// contract can be seeded into the genesis of a local dev node.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ContractSnapshot {
+    /// The address of the contract.
+    pub contract: String,
+    /// The hash of the block the snapshot was taken at.
+    pub block_hash: Bytes,
+    /// The storage version of the contracts pallet at the block, which determines the
+    /// encoding of the contract info, not the ink! version of the storage layout.
+    pub contracts_pallet_version: u16,
+    /// The child trie id of the contract.
+    pub trie_id: Bytes,
+    /// The code hash of the contract.
+    pub code_hash: Bytes,
+    /// The raw top-level storage entries of the contract.
+    pub top: BTreeMap<Bytes, Bytes>,
+    /// The raw key/value child trie storage of the contract.
+    pub storage: ContractStorageData,
+}
+
+impl ContractSnapshot {
+    /// Load a snapshot from a JSON file.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .context(format!("Failed to open snapshot {}", path.display()))?;
+        serde_json::from_reader(file)
+            .context(format!("Failed to deserialize snapshot {}", path.display()))
+    }
+
+    /// Write the snapshot as JSON to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let file = File::create(path)
+            .context(format!("Failed to create snapshot {}", path.display()))?;
+        serde_json::to_writer_pretty(file, self)?;
+        Ok(())
+    }
+
+    /// Return the entries of the snapshot in the format of the `genesis.raw` section of
+    /// a raw chain spec.
+    pub fn to_raw_genesis(&self) -> JsonValue {
+        let top = self
+            .top
+            .iter()
+            .map(|(k, v)| (to_hex(k), json!(to_hex(v))))
+            .collect::<Map<_, _>>();
+        let child = self
+            .storage
+            .iter()
+            .map(|(k, v)| (to_hex(k), json!(to_hex(v))))
+            .collect::<Map<_, _>>();
+        json!({
+            "top": top,
+            "childrenDefault": { to_hex(&self.trie_id): child },
+        })
+    }
+
+    /// Merge the entries of the snapshot into the `genesis.raw` section of a raw chain
+    /// spec, overwriting existing entries with the same keys.
+    pub fn patch_chain_spec(&self, chain_spec: &mut JsonValue) -> Result<()> {
+        let raw = chain_spec
+            .pointer_mut("/genesis/raw")
+            .and_then(JsonValue::as_object_mut)
+            .ok_or_else(|| {
+                anyhow!(
+                    "The chain spec has no `genesis.raw` section, export it with `--raw`"
+                )
+            })?;
+        let JsonValue::Object(patch) = self.to_raw_genesis() else {
+            unreachable!("the raw genesis is always an object; qed")
+        };
+        for (section, entries) in patch {
+            merge_objects(raw.entry(section).or_insert_with(|| json!({})), entries)?;
+        }
+        Ok(())
+    }
+}
+
+/// Recursively merge the `patch` object into the `target` object.
+fn merge_objects(target: &mut JsonValue, patch: JsonValue) -> Result<()> {
+    let target = target
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Expected a JSON object in the chain spec"))?;
+    let JsonValue::Object(patch) = patch else {
+        return Err(anyhow!("Expected a JSON object in the snapshot"))
+    };
+    for (key, value) in patch {
+        match (target.get_mut(&key), value) {
+            (Some(existing @ JsonValue::Object(_)), value @ JsonValue::Object(_)) => {
+                merge_objects(existing, value)?
+            }
+            (_, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn to_hex(bytes: &Bytes) -> String {
+    format!("0x{}", hex::encode(&bytes.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn patch_chain_spec_works() {
+        let snapshot = ContractSnapshot {
+            contract: "5DVGLfDGBvqMr9nCg48g99oD8Mz3sruWmb6ek5UbWvDnbTgZ".to_string(),
+            block_hash: Bytes::from(vec![0; 32]),
+            contracts_pallet_version: 16,
+            trie_id: Bytes::from(vec![1, 2]),
+            code_hash: Bytes::from(vec![3; 32]),
+            top: BTreeMap::from([(Bytes::from(vec![4]), Bytes::from(vec![5]))]),
+            storage: ContractStorageData::new(BTreeMap::from([(
+                Bytes::from(vec![6]),
+                Bytes::from(vec![7]),
+            )])),
+        };
+        let mut chain_spec = json!({
+            "name": "Development",
+            "genesis": {
+                "raw": {
+                    "top": { "0x04": "0x00", "0xaa": "0xbb" },
+                    "childrenDefault": {}
+                }
+            }
+        });
+
+        snapshot
+            .patch_chain_spec(&mut chain_spec)
+            .expect("chain spec shall be patched");
+
+        assert_eq!(
+            chain_spec["genesis"]["raw"],
+            json!({
+                "top": { "0x04": "0x05", "0xaa": "0xbb" },
+                "childrenDefault": { "0x0102": { "0x06": "0x07" } }
+            })
+        );
+        assert!(snapshot.patch_chain_spec(&mut json!({})).is_err());
+    }
+}
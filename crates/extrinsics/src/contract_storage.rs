@@ -20,10 +20,15 @@ use anyhow::{
 };
 use contract_transcode::{
     ContractMessageTranscoder,
+    Hex,
+    Map,
+    Seq,
+    Tuple,
     Value,
 };
 use ink_env::Environment;
 use ink_metadata::layout::{
+    Discriminant,
     Layout,
     StructLayout,
 };
@@ -31,16 +36,19 @@ use itertools::Itertools;
 use scale::{
     Decode,
     Encode,
+    Input,
 };
 use scale_info::{
     form::PortableForm,
     Type,
 };
 use serde::{
+    Deserialize,
     Serialize,
     Serializer,
 };
 use sp_core::{
+    hashing::blake2_128,
     hexdisplay::AsBytesRef,
     storage::ChildInfo,
 };
@@ -52,6 +60,7 @@ use std::{
         Formatter,
     },
     marker::PhantomData,
+    str::FromStr,
 };
 use subxt::{
     backend::{
@@ -64,15 +73,19 @@ use subxt::{
             RpcClient,
         },
     },
+    dynamic::Value as DynamicValue,
     ext::scale_decode::IntoVisitor,
+    storage::dynamic,
     Config,
     OnlineClient,
 };
 
 use super::{
-    fetch_contract_info,
+    fetch_contract_info_at,
+    get_best_block,
     url_to_string,
     ContractInfo,
+    ContractSnapshot,
     TrieId,
 };
 
@@ -110,12 +123,23 @@ where
             })
     }
 
-    /// Load the raw key/value storage for a given contract.
+    /// Fetch the hash of the best block, e.g. to load several storages from the same
+    /// state.
+    pub async fn best_block(&self) -> Result<C::Hash> {
+        Ok(get_best_block(&self.rpc.rpc_methods).await?)
+    }
+
+    /// Load the raw key/value storage for a given contract at the given block, or at the
+    /// best block if `None`.
     pub async fn load_contract_storage_data(
         &self,
         contract_account: &C::AccountId,
+        block_hash: Option<C::Hash>,
     ) -> Result<ContractStorageData> {
-        let contract_info = self.rpc.fetch_contract_info::<E>(contract_account).await?;
+        let contract_info = self
+            .rpc
+            .fetch_contract_info::<E>(contract_account, block_hash)
+            .await?;
         let trie_id = contract_info.trie_id();
 
         let mut storage_keys = Vec::new();
@@ -129,11 +153,14 @@ where
                     None,
                     KEYS_COUNT,
                     storage_keys.last().map(|k: &Bytes| k.as_bytes_ref()),
-                    None,
+                    block_hash,
                 )
                 .await?;
             let keys_count = keys.len();
-            let mut values = self.rpc.fetch_storage_entries(trie_id, &keys, None).await?;
+            let mut values = self
+                .rpc
+                .fetch_storage_entries(trie_id, &keys, block_hash)
+                .await?;
             assert_eq!(
                 keys_count,
                 values.len(),
@@ -161,14 +188,94 @@ where
         &self,
         contract_account: &C::AccountId,
         decoder: &ContractMessageTranscoder,
+        block_hash: Option<C::Hash>,
     ) -> Result<ContractStorageLayout> {
-        let data = self.load_contract_storage_data(contract_account).await?;
+        let data = self
+            .load_contract_storage_data(contract_account, block_hash)
+            .await?;
         ContractStorageLayout::new(data, decoder)
     }
+
+    /// Load a snapshot of the full on-chain state of a given contract at the given
+    /// block, or at the best block if `None`.
+    pub async fn load_contract_snapshot(
+        &self,
+        contract_account: &C::AccountId,
+        block_hash: Option<C::Hash>,
+    ) -> Result<ContractSnapshot> {
+        let block_hash = match block_hash {
+            Some(block_hash) => block_hash,
+            None => get_best_block(&self.rpc.rpc_methods).await?,
+        };
+        let contract_info = self
+            .rpc
+            .fetch_contract_info::<E>(contract_account, Some(block_hash))
+            .await?;
+        let storage = self
+            .load_contract_storage_data(contract_account, Some(block_hash))
+            .await?;
+
+        let storage_at = self.rpc.client.storage().at(block_hash);
+        let contract_key = vec![DynamicValue::from_bytes(contract_account)];
+        let code_key = vec![DynamicValue::from_bytes(contract_info.code_hash())];
+        let addresses = [
+            dynamic("System", "Account", contract_key.clone()),
+            dynamic("Contracts", "ContractInfoOf", contract_key),
+            dynamic("Contracts", "CodeInfoOf", code_key.clone()),
+            dynamic("Contracts", "PristineCode", code_key),
+        ];
+        let mut top = BTreeMap::new();
+        for address in addresses {
+            let key = self.rpc.client.storage().address_bytes(&address)?;
+            // `CodeInfoOf` is not present in older versions of the contracts pallet.
+            if let Some(value) = storage_at.fetch_raw(key.clone()).await? {
+                top.insert(Bytes::from(key), Bytes::from(value));
+            }
+        }
+
+        Ok(ContractSnapshot {
+            contract: contract_account.to_string(),
+            block_hash: Bytes::from(block_hash.as_ref().to_vec()),
+            contracts_pallet_version: storage_at.storage_version("Contracts").await?,
+            trie_id: Bytes::from(contract_info.trie_id().as_ref().to_vec()),
+            code_hash: Bytes::from(contract_info.code_hash().as_ref().to_vec()),
+            top,
+            storage,
+        })
+    }
+
+    /// Load a single storage entry of a given contract, addressed by its storage path
+    /// and, for a `Mapping` or a `StorageVec`, by its key or index.
+    ///
+    /// Only the storage entry itself is fetched, so this is suitable for contracts with
+    /// large `Mapping`s.
+    pub async fn load_contract_storage_entry(
+        &self,
+        contract_account: &C::AccountId,
+        decoder: &ContractMessageTranscoder,
+        path: &str,
+        key: Option<&str>,
+        block_hash: Option<C::Hash>,
+    ) -> Result<ContractStorageEntry> {
+        let entry_key = StorageEntryKey::new(decoder, path, key)?;
+        let contract_info = self
+            .rpc
+            .fetch_contract_info::<E>(contract_account, block_hash)
+            .await?;
+        let value = self
+            .rpc
+            .fetch_contract_storage(
+                contract_info.trie_id(),
+                &Bytes::from(entry_key.storage_key().to_vec()),
+                block_hash,
+            )
+            .await?;
+        entry_key.decode(value, decoder)
+    }
 }
 
 /// Represents the raw key/value storage for the contract.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 pub struct ContractStorageData(BTreeMap<Bytes, Bytes>);
 
 impl ContractStorageData {
@@ -176,10 +283,15 @@ impl ContractStorageData {
     pub fn new(data: BTreeMap<Bytes, Bytes>) -> Self {
         Self(data)
     }
+
+    /// Iterate all raw key-value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = (&Bytes, &Bytes)> {
+        self.0.iter()
+    }
 }
 
 /// Represents the RootLayout storage entry for the contract.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 pub struct RootKeyEntry {
     #[serde(serialize_with = "RootKeyEntry::key_as_hex")]
     pub root_key: u32,
@@ -339,6 +451,60 @@ impl Display for Packed {
     }
 }
 
+/// Represents a storage cell which could not be decoded with the contract metadata.
+#[derive(Serialize, Debug)]
+pub struct Undecodable {
+    #[serde(serialize_with = "RootKeyEntry::key_as_hex")]
+    root_key: u32,
+    /// The path of the cell, empty if the root key is not part of the metadata.
+    path: Vec<String>,
+    /// The raw values of the cell with their raw `Mapping` key or `StorageVec` index.
+    entries: Vec<(Option<Bytes>, Bytes)>,
+    /// The reason why the cell could not be decoded.
+    error: String,
+}
+
+impl Undecodable {
+    /// Create new `Undecodable`.
+    pub fn new(
+        root_key: u32,
+        path: Vec<String>,
+        entries: Vec<(Option<Bytes>, Bytes)>,
+        error: String,
+    ) -> Undecodable {
+        Undecodable {
+            root_key,
+            path,
+            entries,
+            error,
+        }
+    }
+
+    /// Return the reason why the cell could not be decoded.
+    pub fn error(&self) -> &str {
+        &self.error
+    }
+
+    /// Iterate the raw key-value pairs.
+    pub fn iter(&self) -> impl Iterator<Item = &(Option<Bytes>, Bytes)> {
+        self.entries.iter()
+    }
+}
+
+impl Display for Undecodable {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "Undecodable {{ {} }}", self.error)?;
+        for (k, v) in self.entries.iter() {
+            writeln!(f)?;
+            match k {
+                Some(k) => write!(f, "Undecodable {{ {} => {} }}", to_hex(k), to_hex(v))?,
+                None => write!(f, "Undecodable {{ {} }}", to_hex(v))?,
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Represents the storage cell value.
 #[derive(Serialize, Debug)]
 pub enum ContractStorageCell {
@@ -346,31 +512,69 @@ pub enum ContractStorageCell {
     Lazy(Lazy),
     StorageVec(StorageVec),
     Packed(Packed),
+    Undecodable(Undecodable),
 }
 
 impl ContractStorageCell {
-    fn root(&self) -> &RootKeyEntry {
-        match self {
+    fn root_key_and_path(&self) -> (u32, &[String]) {
+        let root = match self {
             Self::Mapping(mapping) => mapping.root(),
             Self::Lazy(lazy) => lazy.root(),
             Self::StorageVec(storage_vec) => storage_vec.root(),
             Self::Packed(packed) => packed.root(),
-        }
+            Self::Undecodable(undecodable) => {
+                return (undecodable.root_key, &undecodable.path)
+            }
+        };
+        (root.root_key, &root.path)
     }
 
     /// Return the `RootKeyEntry` path as a string.
     pub fn path(&self) -> String {
-        self.root().path.join("::")
+        self.root_key_and_path().1.join("::")
     }
 
     /// Return the parent.
     pub fn parent(&self) -> String {
-        self.root().path.last().cloned().unwrap_or_default()
+        self.root_key_and_path()
+            .1
+            .last()
+            .cloned()
+            .unwrap_or_default()
     }
 
     /// Return the root_key as a hex-encoded string.
     pub fn root_key(&self) -> String {
-        hex::encode(self.root().root_key.encode())
+        hex::encode(self.root_key_and_path().0.encode())
+    }
+
+    /// Return the values stored in the cell, each with its `Mapping` key or
+    /// `StorageVec` index. `Lazy` and `Packed` cells hold a single value without a key.
+    /// The values of `Undecodable` cells are returned as raw bytes.
+    fn entries(&self) -> Vec<(Option<Value>, Value)> {
+        match self {
+            Self::Mapping(mapping) => {
+                mapping
+                    .iter()
+                    .map(|(k, v)| (Some(k.clone()), v.clone()))
+                    .collect()
+            }
+            Self::StorageVec(storage_vec) => {
+                storage_vec
+                    .values()
+                    .enumerate()
+                    .map(|(i, v)| (Some(Value::UInt(i as u128)), v.clone()))
+                    .collect()
+            }
+            Self::Lazy(lazy) => vec![(None, lazy.value().clone())],
+            Self::Packed(packed) => vec![(None, packed.value().clone())],
+            Self::Undecodable(undecodable) => {
+                undecodable
+                    .iter()
+                    .map(|(k, v)| (k.as_ref().map(raw_value), raw_value(v)))
+                    .collect()
+            }
+        }
     }
 }
 
@@ -381,6 +585,51 @@ impl Display for ContractStorageCell {
             Self::Lazy(lazy) => lazy.fmt(f),
             Self::StorageVec(storage_vec) => storage_vec.fmt(f),
             Self::Packed(value) => value.fmt(f),
+            Self::Undecodable(undecodable) => undecodable.fmt(f),
+        }
+    }
+}
+
+/// The kind of a storage cell, determined by the type of its root layout.
+pub enum CellKind {
+    /// A `Mapping` with keys of the given type.
+    Mapping { key_type_id: u32 },
+    /// A `StorageVec`, its elements are keyed by their `u32` index.
+    StorageVec,
+    /// A `Lazy` value.
+    Lazy,
+    /// A value stored under the root key together with the other fields of its
+    /// layout.
+    Packed,
+}
+
+impl CellKind {
+    /// The storage types of `ink_storage` are identified by their type path, all other
+    /// types are stored packed.
+    pub fn new(root: &RootKeyEntry, decoder: &ContractMessageTranscoder) -> Result<Self> {
+        let type_def = decoder
+            .metadata()
+            .registry()
+            .resolve(root.type_id)
+            .ok_or(anyhow!("Type {} not found in the registry", root.type_id))?;
+        let path = type_def
+            .path
+            .segments
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        match path.as_slice() {
+            ["ink_storage", "lazy", "mapping", "Mapping"] => {
+                let key_type_id = ContractStorageLayout::param_type_id(type_def, "K")
+                    .ok_or(anyhow!(
+                        "The key type of the Mapping {} is missing",
+                        root.type_id
+                    ))?;
+                Ok(Self::Mapping { key_type_id })
+            }
+            ["ink_storage", "lazy", "vec", "StorageVec"] => Ok(Self::StorageVec),
+            ["ink_storage", "lazy", "Lazy"] => Ok(Self::Lazy),
+            _ => Ok(Self::Packed),
         }
     }
 }
@@ -394,15 +643,15 @@ pub struct ContractStorageLayout {
 impl ContractStorageLayout {
     /// Create a representation of contract storage based on raw storage entries and
     /// metadata.
+    ///
+    /// The values are decoded by walking the storage layout of the metadata. Storage
+    /// cells which can not be decoded are represented as
+    /// [`ContractStorageCell::Undecodable`] holding the raw values.
     pub fn new(
         data: ContractStorageData,
         decoder: &ContractMessageTranscoder,
     ) -> Result<Self> {
-        let layout = decoder.metadata().layout();
-        let registry = decoder.metadata().registry();
-        let mut path_stack = vec!["root".to_string()];
-        let mut root_key_entries: Vec<RootKeyEntry> = Vec::new();
-        Self::collect_root_key_entries(layout, &mut path_stack, &mut root_key_entries);
+        let root_layouts = Self::root_layouts(decoder);
 
         let mut cells = data
             .0
@@ -413,75 +662,32 @@ impl ContractStorageLayout {
             })
             .into_group_map()
             .into_iter()
-            .map(|(root_key, mut data)| {
-                let root_key_entry = root_key_entries
-                    .iter()
-                    .find(|e| e.root_key == root_key)
-                    .ok_or(anyhow!(
-                        "Root key {} not found for the RootLayout",
-                        root_key
-                    ))?;
-                let type_def = registry.resolve(root_key_entry.type_id).ok_or(
-                    anyhow!("Type {} not found in the registry", root_key_entry.type_id),
-                )?;
-                let root = RootKeyEntry {
-                    path: root_key_entry.path.clone(),
-                    type_id: root_key_entry.type_id,
-                    root_key,
+            .map(|(root_key, data)| {
+                let Some((root, layout)) =
+                    root_layouts.iter().find(|(e, _)| e.root_key == root_key)
+                else {
+                    let error =
+                        format!("Root key {root_key} not found for the RootLayout");
+                    return ContractStorageCell::Undecodable(Undecodable::new(
+                        root_key,
+                        Vec::new(),
+                        data,
+                        error,
+                    ))
                 };
-                match type_def.path.to_string().as_str() {
-                    "ink_storage::lazy::mapping::Mapping" => {
-                        let key_type_id = Self::param_type_id(type_def, "K")
-                            .ok_or(anyhow!("Param `K` not found in type registry"))?;
-                        let value_type_id = Self::param_type_id(type_def, "V")
-                            .ok_or(anyhow!("Param `V` not found in type registry"))?;
-                        let value = Self::decode_to_mapping(
-                            data,
-                            key_type_id,
-                            value_type_id,
-                            decoder,
-                        )?;
-                        Ok(ContractStorageCell::Mapping(Mapping::new(root, value)))
-                    }
-                    "ink_storage::lazy::vec::StorageVec" => {
-                        // Sort by the key to get the Vec in the right order.
-                        data.sort_by(|a, b| a.0.cmp(&b.0));
-                        // First item is the `StorageVec` len.
-                        let raw_len = data
-                            .first()
-                            .ok_or(anyhow!("Length of the StorageVec not found"))?
-                            .1
-                            .clone();
-                        let len = u32::decode(&mut raw_len.as_bytes_ref())?;
-                        let value_type_id = Self::param_type_id(type_def, "V")
-                            .ok_or(anyhow!("Param `V` not found in type registry"))?;
-                        let value =
-                            Self::decode_to_vec(&data[1..], value_type_id, decoder)?;
-                        Ok(ContractStorageCell::StorageVec(StorageVec::new(
-                            root, len, value,
-                        )))
-                    }
-                    "ink_storage::lazy::Lazy" => {
-                        let value_type_id = Self::param_type_id(type_def, "V")
-                            .ok_or(anyhow!("Param `V` not found in type registry"))?;
-                        let raw_value =
-                            data.first().ok_or(anyhow!("Empty storage cell"))?.1.clone();
-                        let value = decoder
-                            .decode(value_type_id, &mut raw_value.as_bytes_ref())?;
-                        Ok(ContractStorageCell::Lazy(Lazy::new(root, value)))
-                    }
-                    _ => {
-                        let raw_value =
-                            data.first().ok_or(anyhow!("Empty storage cell"))?.1.clone();
-                        let value = decoder
-                            .decode(root.type_id, &mut raw_value.as_bytes_ref())?;
-                        Ok(ContractStorageCell::Packed(Packed::new(root, value)))
-                    }
-                }
+                Self::decode_cell(root, layout, &data, decoder).unwrap_or_else(|err| {
+                    ContractStorageCell::Undecodable(Undecodable::new(
+                        root_key,
+                        root.path.clone(),
+                        data,
+                        err.to_string(),
+                    ))
+                })
             })
-            .collect::<Result<Vec<_>>>()?;
+            .collect::<Vec<_>>();
 
-        cells.sort_by_key(|k| k.path());
+        // Cells nested in a `Lazy` share its path, keep them in the order of their keys.
+        cells.sort_by_key(|k| (k.path(), k.root_key_and_path().0));
 
         Ok(Self { cells })
     }
@@ -491,47 +697,213 @@ impl ContractStorageLayout {
         self.cells.iter()
     }
 
-    fn decode_to_mapping(
-        data: Vec<(Option<Bytes>, Bytes)>,
-        key_type_id: u32,
-        value_type_id: u32,
+    fn decode_cell(
+        root: &RootKeyEntry,
+        layout: &Layout<PortableForm>,
+        data: &[(Option<Bytes>, Bytes)],
         decoder: &ContractMessageTranscoder,
-    ) -> Result<Vec<(Value, Value)>> {
-        data.into_iter()
-            .map(|(k, v)| {
-                let k = k.ok_or(anyhow!("The Mapping key is missing in the map"))?;
-                let key = decoder.decode(key_type_id, &mut k.as_bytes_ref())?;
-                let value = decoder.decode(value_type_id, &mut v.as_bytes_ref())?;
-                Ok((key, value))
+    ) -> Result<ContractStorageCell> {
+        let decode_value =
+            |v: &Bytes| Self::decode_layout(layout, &mut v.as_bytes_ref(), decoder);
+        let root = root.clone();
+        match CellKind::new(&root, decoder)? {
+            CellKind::Mapping { key_type_id } => {
+                let value = data
+                    .iter()
+                    .map(|(k, v)| {
+                        let k = k
+                            .as_ref()
+                            .ok_or(anyhow!("The Mapping key is missing in the map"))?;
+                        let key = decoder.decode(key_type_id, &mut k.as_bytes_ref())?;
+                        Ok((key, decode_value(v)?))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(ContractStorageCell::Mapping(Mapping::new(root, value)))
+            }
+            CellKind::StorageVec => {
+                let mut len = None;
+                let mut elements = Vec::new();
+                for (k, v) in data {
+                    match k {
+                        Some(k) => {
+                            let index = u32::decode(&mut k.as_bytes_ref())?;
+                            elements.push((index, decode_value(v)?));
+                        }
+                        None => len = Some(u32::decode(&mut v.as_bytes_ref())?),
+                    }
+                }
+                let len = len.ok_or(anyhow!("Length of the StorageVec not found"))?;
+                // Sort by the decoded index, the SCALE encoded keys are little endian.
+                elements.sort_by_key(|(index, _)| *index);
+                let value = elements.into_iter().map(|(_, v)| v).collect();
+                Ok(ContractStorageCell::StorageVec(StorageVec::new(
+                    root, len, value,
+                )))
+            }
+            kind @ (CellKind::Lazy | CellKind::Packed) => {
+                let [(None, raw_value)] = data else {
+                    return Err(anyhow!(
+                        "Expected a single value without a key in the storage cell"
+                    ))
+                };
+                let value = decode_value(raw_value)?;
+                match kind {
+                    CellKind::Lazy => {
+                        Ok(ContractStorageCell::Lazy(Lazy::new(root, value)))
+                    }
+                    _ => Ok(ContractStorageCell::Packed(Packed::new(root, value))),
+                }
+            }
+        }
+    }
+
+    /// Decode a value stored under a single storage key by walking its layout.
+    ///
+    /// Nested `Root` layouts, e.g. a `Lazy` or a `Mapping` field of a struct, are
+    /// stored under their own root key and hence take no space in the value.
+    fn decode_layout(
+        layout: &Layout<PortableForm>,
+        input: &mut &[u8],
+        decoder: &ContractMessageTranscoder,
+    ) -> Result<Value> {
+        match layout {
+            Layout::Leaf(leaf) => decoder.decode(leaf.ty().id, input),
+            Layout::Root(root) => {
+                let ident = decoder
+                    .metadata()
+                    .registry()
+                    .resolve(root.ty().id)
+                    .and_then(|ty| ty.path.ident())
+                    .unwrap_or_default();
+                Ok(Value::Literal(ident))
+            }
+            Layout::Hash(hash) => Self::decode_layout(hash.layout(), input, decoder),
+            Layout::Array(array) => {
+                let elems = (0..array.len())
+                    .map(|_| Self::decode_layout(array.layout(), input, decoder))
+                    .collect::<Result<_>>()?;
+                Ok(Value::Seq(Seq::new(elems)))
+            }
+            Layout::Struct(struct_layout) => {
+                Self::decode_struct_layout(struct_layout, input, decoder)
+            }
+            Layout::Enum(enum_layout) => {
+                let discriminant = input.read_byte()?;
+                let variant = enum_layout
+                    .variants()
+                    .get(&Discriminant::from(discriminant as usize))
+                    .ok_or(anyhow!(
+                        "No variant of {} found with discriminant {discriminant}",
+                        enum_layout.name()
+                    ))?;
+                Self::decode_struct_layout(variant, input, decoder)
+            }
+        }
+    }
+
+    fn decode_struct_layout(
+        struct_layout: &StructLayout<PortableForm>,
+        input: &mut &[u8],
+        decoder: &ContractMessageTranscoder,
+    ) -> Result<Value> {
+        let ident = Some(struct_layout.name().as_str());
+        let fields = struct_layout.fields();
+        // Fields of tuple structs are named by their index.
+        if fields.iter().all(|f| f.name().parse::<usize>().is_ok()) {
+            let values = fields
+                .iter()
+                .map(|f| Self::decode_layout(f.layout(), input, decoder))
+                .collect::<Result<_>>()?;
+            return Ok(Value::Tuple(Tuple::new(ident, values)))
+        }
+        let map = fields
+            .iter()
+            .map(|f| {
+                let value = Self::decode_layout(f.layout(), input, decoder)?;
+                Ok((Value::String(f.name().to_string()), value))
             })
+            .collect::<Result<_>>()?;
+        Ok(Value::Map(Map::new(ident, map)))
+    }
+
+    /// Return the root key entries of all the storage cells described by the metadata.
+    pub fn root_key_entries(decoder: &ContractMessageTranscoder) -> Vec<RootKeyEntry> {
+        Self::root_layouts(decoder)
+            .into_iter()
+            .map(|(entry, _)| entry)
             .collect()
     }
 
-    fn decode_to_vec(
-        data: &[(Option<Bytes>, Bytes)],
-        value_type_id: u32,
+    /// Return the root key entries of all the storage cells described by the metadata,
+    /// each with the layout of the values stored under the root key.
+    fn root_layouts(
         decoder: &ContractMessageTranscoder,
-    ) -> Result<Vec<Value>> {
-        data.iter()
-            .map(|(_, v)| {
-                let value = decoder.decode(value_type_id, &mut v.as_bytes_ref())?;
-                Ok(value)
+    ) -> Vec<(RootKeyEntry, &Layout<PortableForm>)> {
+        let mut path_stack = vec!["root".to_string()];
+        let mut root_layouts = Vec::new();
+        Self::collect_root_key_entries(
+            decoder.metadata().layout(),
+            &mut path_stack,
+            &mut root_layouts,
+        );
+        root_layouts
+    }
+
+    /// Find the root key entry of the storage cell at the given path.
+    ///
+    /// The path segments are separated by `.` or `::`. Struct and enum names may be
+    /// omitted, e.g. `root.balances` is a shorthand for `root::Erc20::balances`.
+    fn find_root_key_entry(
+        decoder: &ContractMessageTranscoder,
+        path: &str,
+    ) -> Result<RootKeyEntry> {
+        let segments = path
+            .split(['.', ':'])
+            .filter(|s| !s.is_empty())
+            .collect::<Vec<_>>();
+        let entries = Self::root_key_entries(decoder);
+        if let Some(entry) = entries.iter().find(|e| e.path == segments) {
+            return Ok(entry.clone())
+        }
+        let matches = entries
+            .iter()
+            .filter(|e| {
+                let mut path = e.path.iter();
+                e.path.first().map(String::as_str) == segments.first().copied()
+                    && e.path.last().map(String::as_str) == segments.last().copied()
+                    && segments.iter().all(|s| path.any(|p| p.as_str() == *s))
             })
-            .collect()
+            .collect::<Vec<_>>();
+        match matches.as_slice() {
+            [entry] => Ok((*entry).clone()),
+            [] => {
+                Err(anyhow!(
+                    "No storage cell found at path `{path}`. Should be one of: {}",
+                    entries.iter().map(|e| e.path.join("::")).join(", ")
+                ))
+            }
+            _ => {
+                Err(anyhow!(
+                    "Storage path `{path}` is ambiguous, it matches: {}",
+                    matches.iter().map(|e| e.path.join("::")).join(", ")
+                ))
+            }
+        }
     }
 
-    fn collect_root_key_entries(
-        layout: &Layout<PortableForm>,
+    fn collect_root_key_entries<'a>(
+        layout: &'a Layout<PortableForm>,
         path: &mut Vec<String>,
-        entries: &mut Vec<RootKeyEntry>,
+        entries: &mut Vec<(RootKeyEntry, &'a Layout<PortableForm>)>,
     ) {
         match layout {
             Layout::Root(root) => {
-                entries.push(RootKeyEntry {
+                let entry = RootKeyEntry {
                     root_key: *root.root_key().key(),
                     path: path.clone(),
                     type_id: root.ty().id,
-                });
+                };
+                entries.push((entry, root.layout()));
                 Self::collect_root_key_entries(root.layout(), path, entries);
             }
             Layout::Struct(struct_layout) => {
@@ -546,17 +918,20 @@ impl ContractStorageLayout {
                 }
                 path.pop();
             }
-            Layout::Hash(_) => {
-                unimplemented!("Layout::Hash is not currently be constructed")
+            Layout::Hash(hash) => {
+                Self::collect_root_key_entries(hash.layout(), path, entries)
             }
-            Layout::Array(_) | Layout::Leaf(_) => {}
+            Layout::Array(array) => {
+                Self::collect_root_key_entries(array.layout(), path, entries)
+            }
+            Layout::Leaf(_) => {}
         }
     }
 
-    fn struct_entries(
-        struct_layout: &StructLayout<PortableForm>,
+    fn struct_entries<'a>(
+        struct_layout: &'a StructLayout<PortableForm>,
         path: &mut Vec<String>,
-        entries: &mut Vec<RootKeyEntry>,
+        entries: &mut Vec<(RootKeyEntry, &'a Layout<PortableForm>)>,
     ) {
         let struct_label = struct_layout.name().to_string();
         path.push(struct_label);
@@ -604,6 +979,302 @@ impl ContractStorageLayout {
     }
 }
 
+/// The location and type of a single storage entry of the contract, computed from the
+/// contract metadata.
+pub struct StorageEntryKey {
+    root: RootKeyEntry,
+    key: Option<Value>,
+    storage_key: Vec<u8>,
+}
+
+impl StorageEntryKey {
+    /// Compute the storage key of the entry at the given path.
+    ///
+    /// For a `Mapping` the `key` is a SCON value of the mapping key type, for a
+    /// `StorageVec` it is the index of the element. `Lazy` and `Packed` cells have no
+    /// key.
+    pub fn new(
+        decoder: &ContractMessageTranscoder,
+        path: &str,
+        key: Option<&str>,
+    ) -> Result<Self> {
+        let root = ContractStorageLayout::find_root_key_entry(decoder, path)?;
+        let mut mapping_key = Vec::new();
+        let key = match (CellKind::new(&root, decoder)?, key) {
+            (CellKind::Mapping { key_type_id }, Some(key)) => {
+                let encoded_key = decoder.encode_value(key_type_id, key)?;
+                let key = decoder.decode(key_type_id, &mut &encoded_key[..])?;
+                mapping_key.extend(encoded_key);
+                Some(key)
+            }
+            (CellKind::StorageVec, Some(index)) => {
+                let index = index
+                    .parse::<u32>()
+                    .map_err(|e| anyhow!("Invalid StorageVec index: {e}"))?;
+                mapping_key.extend(index.encode());
+                Some(Value::UInt(index.into()))
+            }
+            (CellKind::Mapping { .. }, None) => {
+                return Err(anyhow!("A key is required for the Mapping at path `{path}`"))
+            }
+            (CellKind::StorageVec, None) => {
+                return Err(anyhow!(
+                    "An index is required for the StorageVec at path `{path}`"
+                ))
+            }
+            (CellKind::Lazy | CellKind::Packed, None) => None,
+            (CellKind::Lazy | CellKind::Packed, Some(_)) => {
+                return Err(anyhow!(
+                    "The storage cell at path `{path}` is neither a Mapping nor a StorageVec, it has no keys"
+                ))
+            }
+        };
+        let storage_key = Self::child_trie_key(root.root_key, &mapping_key);
+        Ok(Self {
+            root,
+            key,
+            storage_key,
+        })
+    }
+
+    /// Compute the key in the contract child trie of the entry with the given SCALE
+    /// encoded `key` under the `root_key`. The `key` is empty for `Lazy` and `Packed`
+    /// cells.
+    pub fn child_trie_key(root_key: u32, key: &[u8]) -> Vec<u8> {
+        let raw_key = [&root_key.encode()[..], key].concat();
+        [&blake2_128(&raw_key)[..], &raw_key].concat()
+    }
+
+    /// Return the key of the entry in the contract child trie.
+    pub fn storage_key(&self) -> &[u8] {
+        &self.storage_key
+    }
+
+    /// Decode the raw value fetched for the storage key.
+    pub fn decode(
+        self,
+        value: Option<Bytes>,
+        decoder: &ContractMessageTranscoder,
+    ) -> Result<ContractStorageEntry> {
+        let layouts = ContractStorageLayout::root_layouts(decoder);
+        let (_, layout) = layouts
+            .iter()
+            .find(|(e, _)| e.root_key == self.root.root_key)
+            .ok_or(anyhow!(
+                "Root key {} not found for the RootLayout",
+                self.root.root_key
+            ))?;
+        let value = value
+            .map(|v| {
+                ContractStorageLayout::decode_layout(
+                    layout,
+                    &mut v.as_bytes_ref(),
+                    decoder,
+                )
+            })
+            .transpose()?;
+        Ok(ContractStorageEntry {
+            root: self.root,
+            key: self.key,
+            value,
+        })
+    }
+}
+
+/// Represents a single storage entry of the contract.
+#[derive(Serialize, Debug)]
+pub struct ContractStorageEntry {
+    #[serde(flatten)]
+    root: RootKeyEntry,
+    /// The `Mapping` key or `StorageVec` index of the entry, `None` for `Lazy` and
+    /// `Packed` cells.
+    key: Option<Value>,
+    /// The decoded value, `None` if there is no value stored for the key.
+    value: Option<Value>,
+}
+
+impl ContractStorageEntry {
+    /// Return the root key entry of the cell.
+    pub fn root(&self) -> &RootKeyEntry {
+        &self.root
+    }
+
+    /// Return the `RootKeyEntry` path as a string.
+    pub fn path(&self) -> String {
+        self.root.path.join("::")
+    }
+
+    /// Return the root_key as a hex-encoded string.
+    pub fn root_key(&self) -> String {
+        hex::encode(self.root.root_key.encode())
+    }
+
+    /// Return the `Mapping` key or `StorageVec` index of the entry.
+    pub fn key(&self) -> Option<&Value> {
+        self.key.as_ref()
+    }
+
+    /// Return the decoded value of the entry.
+    pub fn value(&self) -> Option<&Value> {
+        self.value.as_ref()
+    }
+}
+
+/// Represents the change of a single storage entry.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "change", rename_all = "lowercase")]
+pub enum StorageChange {
+    Added { new: Value },
+    Removed { old: Value },
+    Changed { old: Value, new: Value },
+}
+
+impl StorageChange {
+    /// Return the value before the change, if any.
+    pub fn old_value(&self) -> Option<&Value> {
+        match self {
+            Self::Added { .. } => None,
+            Self::Removed { old } | Self::Changed { old, .. } => Some(old),
+        }
+    }
+
+    /// Return the value after the change, if any.
+    pub fn new_value(&self) -> Option<&Value> {
+        match self {
+            Self::Removed { .. } => None,
+            Self::Added { new } | Self::Changed { new, .. } => Some(new),
+        }
+    }
+}
+
+impl Display for StorageChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Added { .. } => write!(f, "Added"),
+            Self::Removed { .. } => write!(f, "Removed"),
+            Self::Changed { .. } => write!(f, "Changed"),
+        }
+    }
+}
+
+/// Represents a storage entry that differs between two contract storages.
+#[derive(Serialize, Debug)]
+pub struct StorageDiffEntry {
+    #[serde(serialize_with = "RootKeyEntry::key_as_hex")]
+    root_key: u32,
+    path: Vec<String>,
+    /// The `Mapping` key or `StorageVec` index of the entry, `None` for `Lazy` and
+    /// `Packed` cells.
+    key: Option<Value>,
+    #[serde(flatten)]
+    change: StorageChange,
+}
+
+impl StorageDiffEntry {
+    /// Return the `RootKeyEntry` path as a string.
+    pub fn path(&self) -> String {
+        self.path.join("::")
+    }
+
+    /// Return the parent.
+    pub fn parent(&self) -> String {
+        self.path.last().cloned().unwrap_or_default()
+    }
+
+    /// Return the root_key as a hex-encoded string.
+    pub fn root_key(&self) -> String {
+        hex::encode(self.root_key.encode())
+    }
+
+    /// Return the `Mapping` key or `StorageVec` index of the entry.
+    pub fn key(&self) -> Option<&Value> {
+        self.key.as_ref()
+    }
+
+    /// Return the change of the entry.
+    pub fn change(&self) -> &StorageChange {
+        &self.change
+    }
+}
+
+/// Represents the added, removed and changed entries between two decoded contract
+/// storages, e.g. of the same contract at two different blocks.
+#[derive(Serialize, Debug)]
+pub struct ContractStorageDiff {
+    entries: Vec<StorageDiffEntry>,
+}
+
+impl ContractStorageDiff {
+    /// Compare the `old` storage with the `new` storage entry by entry.
+    pub fn new(old: &ContractStorageLayout, new: &ContractStorageLayout) -> Self {
+        let mut old_entries = Self::entries(old);
+        let mut entries = Vec::new();
+        for ((root_key, key), (path, new_value)) in Self::entries(new) {
+            let change = match old_entries.remove(&(root_key, key.clone())) {
+                Some((_, old_value)) if old_value == new_value => continue,
+                Some((_, old_value)) => {
+                    StorageChange::Changed {
+                        old: old_value,
+                        new: new_value,
+                    }
+                }
+                None => StorageChange::Added { new: new_value },
+            };
+            entries.push(StorageDiffEntry {
+                root_key,
+                path,
+                key,
+                change,
+            });
+        }
+        entries.extend(old_entries.into_iter().map(
+            |((root_key, key), (path, old_value))| {
+                StorageDiffEntry {
+                    root_key,
+                    path,
+                    key,
+                    change: StorageChange::Removed { old: old_value },
+                }
+            },
+        ));
+        entries.sort_by(|a, b| (a.path(), &a.key).cmp(&(b.path(), &b.key)));
+        Self { entries }
+    }
+
+    /// Return the iterator over the differing entries.
+    pub fn iter(&self) -> impl Iterator<Item = &StorageDiffEntry> {
+        self.entries.iter()
+    }
+
+    /// Return `true` if both storages are equal.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn entries(
+        layout: &ContractStorageLayout,
+    ) -> BTreeMap<(u32, Option<Value>), (Vec<String>, Value)> {
+        layout
+            .iter()
+            .flat_map(|cell| {
+                let (root_key, path) = cell.root_key_and_path();
+                cell.entries()
+                    .into_iter()
+                    .map(move |(key, value)| ((root_key, key), (path.to_vec(), value)))
+            })
+            .collect()
+    }
+}
+
+fn to_hex(bytes: &Bytes) -> String {
+    format!("0x{}", hex::encode(&bytes.0))
+}
+
+/// Represent raw bytes as a hex [`Value`].
+fn raw_value(bytes: &Bytes) -> Value {
+    Value::Hex(Hex::from_str(&to_hex(bytes)).expect("valid hex encoded bytes; qed"))
+}
+
 /// Methods for querying contracts over RPC.
 pub struct ContractStorageRpc<C: Config> {
     rpc_client: RpcClient,
@@ -633,11 +1304,18 @@ where
     pub async fn fetch_contract_info<E: Environment>(
         &self,
         contract: &C::AccountId,
-    ) -> Result<ContractInfo<C::Hash, E::Balance>>
+        block_hash: Option<C::Hash>,
+    ) -> Result<ContractInfo<C::Hash, E::Balance, C::AccountId>>
     where
         E::Balance: IntoVisitor,
     {
-        fetch_contract_info::<C, E>(contract, &self.rpc_methods, &self.client).await
+        fetch_contract_info_at::<C, E>(
+            contract,
+            &self.rpc_methods,
+            &self.client,
+            block_hash,
+        )
+        .await
     }
 
     /// Fetch the contract storage at the given key.
@@ -15,10 +15,17 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::contract_storage::{
+    ContractStorageCell,
     ContractStorageData,
+    ContractStorageDiff,
     ContractStorageLayout,
+    StorageChange,
+    StorageEntryKey,
+};
+use contract_transcode::{
+    ContractMessageTranscoder,
+    Value,
 };
-use contract_transcode::ContractMessageTranscoder;
 
 use ink::{
     metadata::{
@@ -46,12 +53,14 @@ use ink::{
         },
         Lazy,
         Mapping,
+        StorageVec,
     },
     ConstructorResult,
     MessageResult,
 };
 
 use scale::Encode;
+use sp_core::hashing::blake2_128;
 use std::collections::BTreeMap;
 use subxt::backend::legacy::rpc_methods::Bytes;
 
@@ -134,6 +143,46 @@ fn storage_decode_simple_type_works() {
     assert_eq!(cell.to_string(), format!("Data {{ a: {} }}", value.a));
 }
 
+#[test]
+fn storage_decode_generic_type_with_key_param_is_packed() {
+    let root_key_encoded = Encode::encode(&ROOT_KEY);
+    // A type parameter named `K` does not make a type a `Mapping`.
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Entry<K, V> {
+        key: K,
+        value: V,
+    }
+
+    let Struct(data_layout) = <Entry<u8, i32> as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Entry<u8, i32>>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let key = [BASE_KEY_RAW.to_vec(), root_key_encoded].concat();
+    let value = Entry {
+        key: 1u8,
+        value: 16,
+    };
+
+    let mut map = BTreeMap::new();
+    map.insert(Bytes::from(key), encode_storage_value(&value));
+    let data = ContractStorageData::new(map);
+    let layout = ContractStorageLayout::new(data, &decoder)
+        .expect("Contract storage layout shall be created");
+
+    let cell = layout.iter().next().expect("Root cell shall be in layout");
+    assert!(matches!(cell, ContractStorageCell::Packed(_)));
+    assert_eq!(cell.to_string(), "Entry { key: 1, value: 16 }");
+}
+
 #[test]
 fn storage_decode_lazy_type_works() {
     let root_key_encoded = Encode::encode(&ROOT_KEY);
@@ -236,3 +285,267 @@ fn storage_decode_mapping_type_works() {
     );
     assert_eq!(cell.root_key(), hex::encode(lazy_type_root_encoded));
 }
+
+#[test]
+fn storage_decode_nested_lazy_type_works() {
+    const MAPPING_ROOT_KEY: u32 = 2;
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: Lazy<
+            Mapping<u8, u8, ManualKey<MAPPING_ROOT_KEY>>,
+            ManualKey<LAZY_TYPE_ROOT_KEY>,
+        >,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let key = [BASE_KEY_RAW.to_vec(), Encode::encode(&ROOT_KEY)].concat();
+    let lazy_type_key =
+        [BASE_KEY_RAW.to_vec(), Encode::encode(&LAZY_TYPE_ROOT_KEY)].concat();
+    let mapping_key = [
+        BASE_KEY_RAW.to_vec(),
+        Encode::encode(&MAPPING_ROOT_KEY),
+        Encode::encode(&4u8),
+    ]
+    .concat();
+
+    let mut map = BTreeMap::new();
+    map.insert(
+        Bytes::from(key),
+        encode_storage_value(&Data { a: Lazy::new() }),
+    );
+    map.insert(
+        Bytes::from(lazy_type_key),
+        encode_storage_value(&Mapping::<u8, u8>::new()),
+    );
+    map.insert(Bytes::from(mapping_key), encode_storage_value(&8u8));
+
+    let data = ContractStorageData::new(map);
+    let layout = ContractStorageLayout::new(data, &decoder)
+        .expect("Contract storage layout shall be created");
+    let cells = layout.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec!["Data { a: Lazy }", "Lazy { Mapping }", "Mapping { 4 => 8 }"]
+    );
+}
+
+#[test]
+fn storage_decode_storage_vec_type_works() {
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: StorageVec<u32, ManualKey<LAZY_TYPE_ROOT_KEY>>,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let key = [BASE_KEY_RAW.to_vec(), Encode::encode(&ROOT_KEY)].concat();
+    let len_key = [BASE_KEY_RAW.to_vec(), Encode::encode(&LAZY_TYPE_ROOT_KEY)].concat();
+
+    let mut map = BTreeMap::new();
+    map.insert(
+        Bytes::from(key),
+        encode_storage_value(&Data {
+            a: StorageVec::new(),
+        }),
+    );
+    map.insert(Bytes::from(len_key.clone()), encode_storage_value(&257u32));
+    // The raw key of index 256 sorts before the one of index 1, as the SCALE encoded
+    // indices are little endian.
+    for index in 0u32..257 {
+        let elem_key = [len_key.clone(), Encode::encode(&index)].concat();
+        map.insert(Bytes::from(elem_key), encode_storage_value(&index));
+    }
+
+    let data = ContractStorageData::new(map);
+    let layout = ContractStorageLayout::new(data, &decoder)
+        .expect("Contract storage layout shall be created");
+    let cell = layout
+        .iter()
+        .nth(1)
+        .expect("StorageVec cell shall be in layout");
+    let expected = (0u128..257).map(Value::UInt).collect::<Vec<_>>();
+    let cell = serde_json::to_value(cell).expect("Cell shall be serialized");
+    assert_eq!(cell["StorageVec"]["len"], 257);
+    assert_eq!(
+        cell["StorageVec"]["vec"],
+        serde_json::to_value(expected).expect("Values shall be serialized")
+    );
+}
+
+#[test]
+fn storage_decode_unknown_root_key_is_undecodable() {
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: i32,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let key = [BASE_KEY_RAW.to_vec(), Encode::encode(&ROOT_KEY)].concat();
+    let unknown_key = [BASE_KEY_RAW.to_vec(), Encode::encode(&42u32)].concat();
+
+    let mut map = BTreeMap::new();
+    map.insert(Bytes::from(key), encode_storage_value(&Data { a: 16 }));
+    map.insert(Bytes::from(unknown_key), Bytes::from(vec![1, 2]));
+
+    let data = ContractStorageData::new(map);
+    let layout = ContractStorageLayout::new(data, &decoder)
+        .expect("Contract storage layout shall be created");
+    let cells = layout.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec![
+            "Undecodable { Root key 42 not found for the RootLayout }\nUndecodable { 0x0102 }",
+            "Data { a: 16 }"
+        ]
+    );
+}
+
+#[test]
+fn storage_diff_works() {
+    let root_key_encoded = Encode::encode(&ROOT_KEY);
+    let lazy_type_root_encoded = Encode::encode(&LAZY_TYPE_ROOT_KEY);
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: Mapping<u8, u8, ManualKey<LAZY_TYPE_ROOT_KEY>>,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let layout = |items: &[(u8, u8)]| {
+        let value = Data { a: Mapping::new() };
+        let key = [BASE_KEY_RAW.to_vec(), root_key_encoded.clone()].concat();
+        let mut map = BTreeMap::new();
+        map.insert(Bytes::from(key), encode_storage_value(&value));
+        for (k, v) in items {
+            let mapping_key = [
+                BASE_KEY_RAW.to_vec(),
+                lazy_type_root_encoded.clone(),
+                Encode::encode(k),
+            ]
+            .concat();
+            map.insert(Bytes::from(mapping_key), encode_storage_value(v));
+        }
+        ContractStorageLayout::new(ContractStorageData::new(map), &decoder)
+            .expect("Contract storage layout shall be created")
+    };
+
+    let old = layout(&[(1, 10), (2, 20), (3, 30)]);
+    let new = layout(&[(1, 10), (2, 21), (4, 40)]);
+    let diff = ContractStorageDiff::new(&old, &new);
+
+    let changes = diff
+        .iter()
+        .map(|e| (e.key().cloned(), e.change().clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                Some(Value::UInt(2)),
+                StorageChange::Changed {
+                    old: Value::UInt(20),
+                    new: Value::UInt(21)
+                }
+            ),
+            (
+                Some(Value::UInt(3)),
+                StorageChange::Removed {
+                    old: Value::UInt(30)
+                }
+            ),
+            (
+                Some(Value::UInt(4)),
+                StorageChange::Added {
+                    new: Value::UInt(40)
+                }
+            ),
+        ]
+    );
+    assert!(ContractStorageDiff::new(&old, &old).is_empty());
+}
+
+#[test]
+fn storage_entry_key_works() {
+    #[derive(scale_info::TypeInfo, StorageLayout, Storable)]
+    struct Data {
+        a: Mapping<u8, u8, ManualKey<LAZY_TYPE_ROOT_KEY>>,
+    }
+
+    let Struct(data_layout) = <Data as StorageLayout>::layout(&ROOT_KEY) else {
+        panic!("Layout shall be created");
+    };
+    let storage_layout: Layout = RootLayout::new(
+        LayoutKey::from(ROOT_KEY),
+        data_layout,
+        scale_info::meta_type::<Data>(),
+    )
+    .into();
+
+    let metadata = InkProject::new(storage_layout, contract_default_spec());
+    let decoder = ContractMessageTranscoder::new(metadata);
+
+    let entry_key = StorageEntryKey::new(&decoder, "root.a", Some("4"))
+        .expect("Storage entry key shall be computed");
+    let raw_key = [Encode::encode(&LAZY_TYPE_ROOT_KEY), Encode::encode(&4u8)].concat();
+    assert_eq!(
+        entry_key.storage_key(),
+        [blake2_128(&raw_key).to_vec(), raw_key].concat()
+    );
+
+    let entry = entry_key
+        .decode(Some(encode_storage_value(&8u8)), &decoder)
+        .expect("Storage entry shall be decoded");
+    assert_eq!(entry.path(), "root::Data::a");
+    assert_eq!(entry.key(), Some(&Value::UInt(4)));
+    assert_eq!(entry.value(), Some(&Value::UInt(8)));
+
+    assert!(StorageEntryKey::new(&decoder, "root.a", None).is_err());
+    assert!(StorageEntryKey::new(&decoder, "root.b", Some("4")).is_err());
+    assert!(StorageEntryKey::new(&decoder, "root", Some("4")).is_err());
+}
@@ -15,6 +15,8 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    artifact_registry::defines_event,
+    ArtifactRegistry,
     BalanceVariant,
     TokenMetadata,
 };
@@ -30,7 +32,10 @@ use contract_transcode::{
 
 use anyhow::Result;
 use ink_env::Environment;
-use scale_info::form::PortableForm;
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+};
 use std::{
     fmt::{
         Display,
@@ -41,10 +46,16 @@ use std::{
 use subxt::{
     self,
     blocks::ExtrinsicEvents,
-    events::StaticEvent,
+    events::{
+        EventDetails,
+        Events as BlockEvents,
+        StaticEvent,
+    },
     ext::{
         scale_decode::{
             self,
+            visitor::IgnoreVisitor,
+            DecodeAsType,
             IntoVisitor,
         },
         scale_encode,
@@ -197,16 +208,78 @@ impl DisplayEvents {
         transcoder: Option<&ContractMessageTranscoder>,
         subxt_metadata: &subxt::Metadata,
     ) -> Result<DisplayEvents>
+    where
+        C::AccountId: IntoVisitor,
+    {
+        // display the accounts of the pallet events in the same format as the accounts
+        // of the contract events
+        let registry = match transcoder.and_then(|t| t.ss58_prefix()) {
+            Some(prefix) => ArtifactRegistry::default().with_ss58_prefix(prefix)?,
+            None => ArtifactRegistry::default(),
+        };
+        Self::from_events_with_registry::<C, E>(
+            result,
+            transcoder,
+            &registry,
+            subxt_metadata,
+        )
+    }
+
+    /// Parses events and returns an object which can be serialised.
+    ///
+    /// Contract events are decoded with the `transcoder` of the called contract, or with
+    /// the metadata found in the `registry` for events emitted by other contracts.
+    pub fn from_events_with_registry<C: Config, E: Environment>(
+        result: &ExtrinsicEvents<C>,
+        transcoder: Option<&ContractMessageTranscoder>,
+        registry: &ArtifactRegistry,
+        subxt_metadata: &subxt::Metadata,
+    ) -> Result<DisplayEvents>
+    where
+        C::AccountId: IntoVisitor,
+    {
+        Self::decode_events::<C>(result.iter(), transcoder, registry, subxt_metadata)
+    }
+
+    /// Parses the `Contracts` events of a block matching the `filter` and returns an
+    /// object which can be serialised.
+    pub fn from_block_events<C: Config, E: Environment>(
+        block_events: &BlockEvents<C>,
+        filter: &ContractEventFilter<C::AccountId, C::Hash>,
+        transcoder: Option<&ContractMessageTranscoder>,
+        registry: &ArtifactRegistry,
+        subxt_metadata: &subxt::Metadata,
+    ) -> Result<DisplayEvents>
+    where
+        C::AccountId: IntoVisitor + PartialEq,
+    {
+        let events = block_events.iter().filter(|event| {
+            event
+                .as_ref()
+                .map_or(true, |event| filter.matches(event, subxt_metadata.types()))
+        });
+        Self::decode_events::<C>(events, transcoder, registry, subxt_metadata)
+    }
+
+    fn decode_events<C: Config>(
+        event_details: impl Iterator<Item = Result<EventDetails<C>, subxt::Error>>,
+        transcoder: Option<&ContractMessageTranscoder>,
+        registry: &ArtifactRegistry,
+        subxt_metadata: &subxt::Metadata,
+    ) -> Result<DisplayEvents>
     where
         C::AccountId: IntoVisitor,
     {
         let mut events: Vec<Event> = vec![];
 
-        let events_transcoder = TranscoderBuilder::new(subxt_metadata.types())
-            .with_default_custom_type_transcoders()
-            .done();
+        let mut events_transcoder = TranscoderBuilder::new(subxt_metadata.types())
+            .with_default_custom_type_transcoders();
+        if let Some(prefix) = registry.ss58_prefix() {
+            events_transcoder = events_transcoder.with_ss58_prefix(prefix);
+        }
+        let events_transcoder = events_transcoder.done();
 
-        for event in result.iter() {
+        for event in event_details {
             let event = event?;
             tracing::debug!(
                 "displaying event {}:{}",
@@ -233,6 +306,16 @@ impl DisplayEvents {
                 ) && field_metadata.name == Some("data".to_string())
                 {
                     tracing::debug!("event data: {:?}", hex::encode(&event_data));
+                    // the event might have been emitted by another contract
+                    let transcoder = match event_sig_topic {
+                        Some(topic) => {
+                            transcoder
+                                .filter(|transcoder| defines_event(transcoder, topic))
+                                .or_else(|| registry.event_transcoder(topic))
+                                .or(transcoder)
+                        }
+                        None => transcoder,
+                    };
                     let field = contract_event_data_field::<C>(
                         transcoder,
                         field_metadata,
@@ -270,6 +353,11 @@ impl DisplayEvents {
         Ok(DisplayEvents(events))
     }
 
+    /// Returns whether there are no events to display.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
     /// Displays events in a human readable format
     pub fn display_events<E: Environment>(
         &self,
@@ -362,3 +450,243 @@ fn contract_event_data_field<C: Config>(
         field_metadata.type_name.as_ref().map(|s| s.to_string()),
     ))
 }
+
+/// Selects the `Contracts` pallet events of a block to display: `ContractEmitted`,
+/// `Instantiated`, `Called` and `Terminated`.
+#[derive(Clone, Debug)]
+pub struct ContractEventFilter<AccountId, Hash> {
+    contracts: Vec<AccountId>,
+    signature_topics: Vec<Hash>,
+}
+
+impl<AccountId, Hash> Default for ContractEventFilter<AccountId, Hash> {
+    fn default() -> Self {
+        Self {
+            contracts: Vec::new(),
+            signature_topics: Vec::new(),
+        }
+    }
+}
+
+impl<AccountId, Hash> ContractEventFilter<AccountId, Hash>
+where
+    AccountId: IntoVisitor + PartialEq,
+    Hash: PartialEq,
+{
+    /// Only select the events of these contracts.
+    pub fn contracts(self, contracts: Vec<AccountId>) -> Self {
+        let mut this = self;
+        this.contracts = contracts;
+        this
+    }
+
+    /// Only select the contract events with these signature topics.
+    ///
+    /// Setting any topic excludes all events other than `ContractEmitted`.
+    pub fn signature_topics(self, signature_topics: Vec<Hash>) -> Self {
+        let mut this = self;
+        this.signature_topics = signature_topics;
+        this
+    }
+
+    /// Returns whether the event is selected by the filter.
+    pub fn matches<C>(&self, event: &EventDetails<C>, types: &PortableRegistry) -> bool
+    where
+        C: Config<AccountId = AccountId, Hash = Hash>,
+    {
+        self.matches_event(
+            event.pallet_name(),
+            event.variant_name(),
+            event.topics(),
+            || event_contract::<C>(event, types),
+        )
+    }
+
+    /// Returns whether the event of the pallet with the variant name and topics is
+    /// selected by the filter, where `contract` decodes the contract of the event.
+    fn matches_event(
+        &self,
+        pallet_name: &str,
+        variant_name: &str,
+        topics: &[Hash],
+        contract: impl FnOnce() -> Result<Option<AccountId>>,
+    ) -> bool {
+        const CONTRACT_EVENTS: [&str; 4] =
+            ["ContractEmitted", "Instantiated", "Called", "Terminated"];
+        if pallet_name != "Contracts" || !CONTRACT_EVENTS.contains(&variant_name) {
+            return false
+        }
+        if !self.signature_topics.is_empty() {
+            let is_emitted = <ContractEmitted<AccountId> as StaticEvent>::is_event(
+                pallet_name,
+                variant_name,
+            );
+            let sig_topic = topics.first();
+            if !is_emitted
+                || !sig_topic.is_some_and(|t| self.signature_topics.contains(t))
+            {
+                return false
+            }
+        }
+        if !self.contracts.is_empty() {
+            match contract() {
+                Ok(Some(contract)) => return self.contracts.contains(&contract),
+                Ok(None) => return false,
+                Err(err) => {
+                    tracing::warn!(
+                        "Decoding the contract of event {pallet_name}:{variant_name} \
+                         failed: {err:?}"
+                    );
+                    return false
+                }
+            }
+        }
+        true
+    }
+}
+
+/// Decodes the `contract` field of a `Contracts` pallet event, skipping the fields
+/// before it.
+fn event_contract<C: Config>(
+    event: &EventDetails<C>,
+    types: &PortableRegistry,
+) -> Result<Option<C::AccountId>>
+where
+    C::AccountId: IntoVisitor,
+{
+    let event_data = &mut event.field_bytes();
+    for field in &event.event_metadata().variant.fields {
+        if field.name.as_deref() == Some("contract") {
+            let contract = C::AccountId::decode_as_type(event_data, &field.ty.id, types)?;
+            return Ok(Some(contract))
+        }
+        scale_decode::visitor::decode_with_visitor(
+            event_data,
+            &field.ty.id,
+            types,
+            IgnoreVisitor::<PortableRegistry>::new(),
+        )?;
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subxt::utils::{
+        AccountId32,
+        H256,
+    };
+
+    type Filter = ContractEventFilter<AccountId32, H256>;
+
+    const ALICE: AccountId32 = AccountId32([1; 32]);
+    const BOB: AccountId32 = AccountId32([2; 32]);
+    const TRANSFER: H256 = H256([11; 32]);
+    const APPROVAL: H256 = H256([22; 32]);
+
+    fn matches(
+        filter: &Filter,
+        pallet_name: &str,
+        variant_name: &str,
+        topics: &[H256],
+        contract: Option<AccountId32>,
+    ) -> bool {
+        filter.matches_event(pallet_name, variant_name, topics, || Ok(contract))
+    }
+
+    #[test]
+    fn default_filter_matches_all_contract_events() {
+        let filter = Filter::default();
+
+        for variant in ["ContractEmitted", "Instantiated", "Called", "Terminated"] {
+            assert!(matches(&filter, "Contracts", variant, &[], Some(ALICE)));
+        }
+        assert!(!matches(&filter, "Contracts", "CodeStored", &[], None));
+        assert!(!matches(&filter, "Balances", "Transfer", &[], None));
+    }
+
+    #[test]
+    fn contract_filter_matches_events_of_the_contracts() {
+        let filter = Filter::default().contracts(vec![ALICE]);
+
+        assert!(matches(&filter, "Contracts", "Called", &[], Some(ALICE)));
+        assert!(matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[],
+            Some(ALICE)
+        ));
+        assert!(!matches(&filter, "Contracts", "Called", &[], Some(BOB)));
+        assert!(!matches(&filter, "Contracts", "Called", &[], None));
+        assert!(!filter.matches_event("Contracts", "Called", &[], || {
+            Err(anyhow::anyhow!("undecodable event"))
+        }));
+    }
+
+    #[test]
+    fn topic_filter_matches_emitted_events_by_signature_topic() {
+        let filter = Filter::default().signature_topics(vec![TRANSFER]);
+
+        assert!(matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[TRANSFER, APPROVAL],
+            Some(ALICE)
+        ));
+        // only the first topic is the signature topic
+        assert!(!matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[APPROVAL, TRANSFER],
+            Some(ALICE)
+        ));
+        assert!(!matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[],
+            Some(ALICE)
+        ));
+        // other contract events are excluded once a topic is set
+        assert!(!matches(
+            &filter,
+            "Contracts",
+            "Called",
+            &[TRANSFER],
+            Some(ALICE)
+        ));
+    }
+
+    #[test]
+    fn contract_and_topic_filters_must_both_match() {
+        let filter = Filter::default()
+            .contracts(vec![ALICE])
+            .signature_topics(vec![TRANSFER]);
+
+        assert!(matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[TRANSFER],
+            Some(ALICE)
+        ));
+        assert!(!matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[TRANSFER],
+            Some(BOB)
+        ));
+        assert!(!matches(
+            &filter,
+            "Contracts",
+            "ContractEmitted",
+            &[APPROVAL],
+            Some(ALICE)
+        ));
+    }
+}
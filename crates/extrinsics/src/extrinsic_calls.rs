@@ -15,20 +15,28 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
+    multisig::Timepoint,
     upload::Determinism,
     WasmCode,
 };
 use subxt::{
     ext::{
         codec::Compact,
-        scale_encode::EncodeAsType,
+        scale_decode::DecodeAsType,
+        scale_encode::{
+            self,
+            EncodeAsType,
+            TypeResolver,
+        },
     },
     utils::MultiAddress,
 };
 
-/// Copied from `sp_weight` to additionally implement `scale_encode::EncodeAsType`.
-#[derive(Debug, EncodeAsType)]
+/// Copied from `sp_weight` to additionally implement `scale_encode::EncodeAsType` and
+/// `scale_decode::DecodeAsType`.
+#[derive(Debug, EncodeAsType, DecodeAsType)]
 #[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
 pub(crate) struct Weight {
     #[codec(compact)]
     /// The weight of computational time used based on some reference hardware.
@@ -47,6 +55,12 @@ impl From<sp_weights::Weight> for Weight {
     }
 }
 
+impl From<Weight> for sp_weights::Weight {
+    fn from(weight: Weight) -> Self {
+        Self::from_parts(weight.ref_time, weight.proof_size)
+    }
+}
+
 impl core::fmt::Display for Weight {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         write!(
@@ -214,3 +228,120 @@ impl<AccountId, Balance> Call<AccountId, Balance> {
         subxt::tx::Payload::new("Contracts", "call", self)
     }
 }
+
+/// An already encoded runtime call, passed as an argument to calls which dispatch other
+/// calls.
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedCall(pub Vec<u8>);
+
+impl scale::Encode for EncodedCall {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        dest.write(&self.0)
+    }
+}
+
+impl EncodeAsType for EncodedCall {
+    fn encode_as_type_to<R: TypeResolver>(
+        &self,
+        _type_id: &R::TypeId,
+        _types: &R,
+        out: &mut Vec<u8>,
+    ) -> Result<(), scale_encode::Error> {
+        out.extend_from_slice(&self.0);
+        Ok(())
+    }
+}
+
+/// A raw call to `pallet-multisig`'s `as_multi`.
+#[derive(EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct AsMulti<AccountId> {
+    threshold: u16,
+    other_signatories: Vec<AccountId>,
+    maybe_timepoint: Option<Timepoint>,
+    call: EncodedCall,
+    max_weight: Weight,
+}
+
+impl<AccountId> AsMulti<AccountId> {
+    pub fn new(
+        threshold: u16,
+        other_signatories: Vec<AccountId>,
+        maybe_timepoint: Option<Timepoint>,
+        call: EncodedCall,
+        max_weight: sp_weights::Weight,
+    ) -> Self {
+        Self {
+            threshold,
+            other_signatories,
+            maybe_timepoint,
+            call,
+            max_weight: max_weight.into(),
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Multisig", "as_multi", self)
+    }
+}
+
+/// A raw call to `pallet-multisig`'s `approve_as_multi`.
+#[derive(EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct ApproveAsMulti<AccountId> {
+    threshold: u16,
+    other_signatories: Vec<AccountId>,
+    maybe_timepoint: Option<Timepoint>,
+    call_hash: [u8; 32],
+    max_weight: Weight,
+}
+
+impl<AccountId> ApproveAsMulti<AccountId> {
+    pub fn new(
+        threshold: u16,
+        other_signatories: Vec<AccountId>,
+        maybe_timepoint: Option<Timepoint>,
+        call_hash: [u8; 32],
+        max_weight: sp_weights::Weight,
+    ) -> Self {
+        Self {
+            threshold,
+            other_signatories,
+            maybe_timepoint,
+            call_hash,
+            max_weight: max_weight.into(),
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Multisig", "approve_as_multi", self)
+    }
+}
+
+/// A raw call to `pallet-proxy`'s `proxy`.
+#[derive(EncodeAsType)]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub(crate) struct Proxy<AccountId> {
+    real: MultiAddress<AccountId, ()>,
+    /// Always `None`, so that any proxy type of the signer matches.
+    force_proxy_type: Option<()>,
+    call: EncodedCall,
+}
+
+impl<AccountId> Proxy<AccountId> {
+    pub fn new(real: MultiAddress<AccountId, ()>, call: EncodedCall) -> Self {
+        Self {
+            real,
+            force_proxy_type: None,
+            call,
+        }
+    }
+
+    pub fn build(self) -> subxt::tx::Payload<Self> {
+        subxt::tx::Payload::new("Proxy", "proxy", self)
+    }
+}
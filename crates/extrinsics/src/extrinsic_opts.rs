@@ -16,17 +16,22 @@
 
 use anyhow::Result;
 use contract_build::Verbosity;
+use contract_transcode::{
+    ContractMessageTranscoder,
+    CustomTypes,
+    Renderer,
+};
 use derivative::Derivative;
 use ink_env::Environment;
-use subxt::{
-    tx,
-    Config,
-};
+use subxt::Config;
 use url::Url;
 
 use crate::{
     url_to_string,
     ContractArtifacts,
+    Multisig,
+    NonceTracker,
+    TrySigner,
 };
 use std::{
     marker::PhantomData,
@@ -42,8 +47,18 @@ pub struct ExtrinsicOpts<C: Config, E: Environment, Signer: Clone> {
     manifest_path: Option<PathBuf>,
     url: url::Url,
     signer: Signer,
+    multisig: Option<Multisig<C::AccountId>>,
+    proxy_for: Option<C::AccountId>,
+    nonce: Option<u64>,
+    nonce_tracker: Option<NonceTracker>,
+    tip: Option<u128>,
+    mortality: Option<u64>,
+    fee_asset: Option<C::AssetId>,
     storage_deposit_limit: Option<E::Balance>,
     verbosity: Verbosity,
+    renderer: Option<Renderer>,
+    custom_types: Option<CustomTypes>,
+    ss58_prefix: Option<u16>,
     _marker: PhantomData<C>,
 }
 
@@ -54,7 +69,7 @@ pub struct ExtrinsicOptsBuilder<C: Config, E: Environment, Signer: Clone> {
 
 impl<C: Config, E: Environment, Signer> ExtrinsicOptsBuilder<C, E, Signer>
 where
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Returns a clean builder for [`ExtrinsicOpts`].
     pub fn new(signer: Signer) -> ExtrinsicOptsBuilder<C, E, Signer> {
@@ -64,8 +79,18 @@ where
                 manifest_path: None,
                 url: url::Url::parse("ws://localhost:9944").unwrap(),
                 signer,
+                multisig: None,
+                proxy_for: None,
+                nonce: None,
+                nonce_tracker: None,
+                tip: None,
+                mortality: None,
+                fee_asset: None,
                 storage_deposit_limit: None,
                 verbosity: Verbosity::Default,
+                renderer: None,
+                custom_types: None,
+                ss58_prefix: None,
                 _marker: PhantomData,
             },
         }
@@ -92,6 +117,59 @@ where
         this
     }
 
+    /// Sets the multisig account on behalf of which the extrinsic is submitted.
+    pub fn multisig(self, multisig: Option<Multisig<C::AccountId>>) -> Self {
+        let mut this = self;
+        this.opts.multisig = multisig;
+        this
+    }
+
+    /// Sets the account on behalf of which the extrinsic is submitted via
+    /// `Proxy::proxy`, the signer being a proxy of that account.
+    pub fn proxy_for(self, proxy_for: Option<C::AccountId>) -> Self {
+        let mut this = self;
+        this.opts.proxy_for = proxy_for;
+        this
+    }
+
+    /// Sets the nonce of the extrinsic, instead of querying it from the node.
+    pub fn nonce(self, nonce: Option<u64>) -> Self {
+        let mut this = self;
+        this.opts.nonce = nonce;
+        this
+    }
+
+    /// Sets the tracker handing out the nonces of the extrinsics, to be shared with
+    /// other commands submitting extrinsics concurrently from the same account.
+    pub fn nonce_tracker(self, nonce_tracker: Option<NonceTracker>) -> Self {
+        let mut this = self;
+        this.opts.nonce_tracker = nonce_tracker;
+        this
+    }
+
+    /// Sets the tip paid to the block author to increase the priority of the extrinsic.
+    pub fn tip(self, tip: Option<u128>) -> Self {
+        let mut this = self;
+        this.opts.tip = tip;
+        this
+    }
+
+    /// Sets the number of blocks after which the extrinsic expires, instead of being
+    /// valid forever.
+    pub fn mortality(self, mortality: Option<u64>) -> Self {
+        let mut this = self;
+        this.opts.mortality = mortality;
+        this
+    }
+
+    /// Sets the asset the fees and the tip are paid in via `ChargeAssetTxPayment`,
+    /// instead of the native token.
+    pub fn fee_asset(self, fee_asset: Option<C::AssetId>) -> Self {
+        let mut this = self;
+        this.opts.fee_asset = fee_asset;
+        this
+    }
+
     /// Sets the maximum amount of balance that can be charged from the caller to pay for
     /// storage.
     pub fn storage_deposit_limit(
@@ -110,6 +188,30 @@ where
         this
     }
 
+    /// Sets the renderer of the values decoded with the contract transcoder, e.g. the
+    /// return value of a dry-run and the contract events.
+    pub fn renderer(self, renderer: Option<Renderer>) -> Self {
+        let mut this = self;
+        this.opts.renderer = renderer;
+        this
+    }
+
+    /// Sets the custom types transcoded with the codecs configured for them, see
+    /// [`ContractMessageTranscoder::with_custom_types`].
+    pub fn custom_types(self, custom_types: Option<CustomTypes>) -> Self {
+        let mut this = self;
+        this.opts.custom_types = custom_types;
+        this
+    }
+
+    /// Sets the SS58 address format prefix the accounts are displayed with, instead of
+    /// the generic Substrate prefix.
+    pub fn ss58_prefix(self, ss58_prefix: Option<u16>) -> Self {
+        let mut this = self;
+        this.opts.ss58_prefix = ss58_prefix;
+        this
+    }
+
     pub fn done(self) -> ExtrinsicOpts<C, E, Signer> {
         self.opts
     }
@@ -117,7 +219,7 @@ where
 
 impl<C: Config, E: Environment, Signer> ExtrinsicOpts<C, E, Signer>
 where
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Load contract artifacts.
     pub fn contract_artifacts(&self) -> Result<ContractArtifacts> {
@@ -127,6 +229,25 @@ where
         )
     }
 
+    /// Load the transcoder of the contract artifacts, with the SS58 prefix, the custom
+    /// types and the renderer if set.
+    pub fn contract_transcoder(
+        &self,
+        artifacts: &ContractArtifacts,
+    ) -> Result<ContractMessageTranscoder> {
+        let mut transcoder = artifacts.contract_transcoder()?;
+        if let Some(prefix) = self.ss58_prefix {
+            transcoder = transcoder.with_ss58_prefix(prefix)?;
+        }
+        if let Some(ref custom_types) = self.custom_types {
+            transcoder = transcoder.with_custom_types(custom_types)?;
+        }
+        Ok(match self.renderer {
+            Some(ref renderer) => transcoder.with_renderer(renderer.clone()),
+            None => transcoder,
+        })
+    }
+
     /// Return the file path of the contract artifact.
     pub fn file(&self) -> Option<&PathBuf> {
         self.file.as_ref()
@@ -142,11 +263,65 @@ where
         url_to_string(&self.url)
     }
 
+    /// Return the SS58 address format prefix the accounts are displayed with, if set.
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        self.ss58_prefix
+    }
+
     /// Return the signer.
     pub fn signer(&self) -> &Signer {
         &self.signer
     }
 
+    /// Return the multisig account on behalf of which the extrinsic is submitted.
+    pub fn multisig(&self) -> Option<&Multisig<C::AccountId>> {
+        self.multisig.as_ref()
+    }
+
+    /// Return the account on behalf of which the extrinsic is submitted via
+    /// `Proxy::proxy`.
+    pub fn proxy_for(&self) -> Option<&C::AccountId> {
+        self.proxy_for.as_ref()
+    }
+
+    /// Return the nonce of the extrinsic, if set.
+    pub fn nonce(&self) -> Option<u64> {
+        self.nonce
+    }
+
+    /// Return the tracker handing out the nonces of the extrinsics, if set.
+    pub fn nonce_tracker(&self) -> Option<&NonceTracker> {
+        self.nonce_tracker.as_ref()
+    }
+
+    /// Return the tip paid to the block author.
+    pub fn tip(&self) -> Option<u128> {
+        self.tip
+    }
+
+    /// Return the number of blocks after which the extrinsic expires.
+    pub fn mortality(&self) -> Option<u64> {
+        self.mortality
+    }
+
+    /// Return the asset the fees and the tip are paid in.
+    pub fn fee_asset(&self) -> Option<&C::AssetId> {
+        self.fee_asset.as_ref()
+    }
+
+    /// Return the account the contract extrinsics are dispatched from: the proxied
+    /// account if set, otherwise the multisig account if set, otherwise the account of
+    /// the signer.
+    pub fn origin(&self) -> C::AccountId {
+        if let Some(real) = &self.proxy_for {
+            return real.clone()
+        }
+        match &self.multisig {
+            Some(multisig) => multisig.account_id().clone(),
+            None => self.signer.account_id(),
+        }
+    }
+
     /// Return the storage deposit limit.
     pub fn storage_deposit_limit(&self) -> Option<E::Balance> {
         self.storage_deposit_limit
@@ -15,6 +15,7 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    estimate_fee,
     events::{
         CodeStored,
         ContractInstantiated,
@@ -24,9 +25,11 @@ use super::{
         StorageDeposit,
     },
     state_call,
-    submit_extrinsic,
+    submit_call,
+    ArgsFormat,
     ContractMessageTranscoder,
     ErrorVariant,
+    TrySigner,
 };
 use crate::{
     check_env_types,
@@ -35,6 +38,7 @@ use crate::{
         InstantiateWithCode,
     },
     extrinsic_opts::ExtrinsicOpts,
+    ss58::format_ss58,
 };
 use anyhow::{
     anyhow,
@@ -66,7 +70,6 @@ use subxt::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
     Config,
     OnlineClient,
 };
@@ -75,6 +78,10 @@ use subxt::{
 pub struct InstantiateCommandBuilder<C: Config, E: Environment, Signer: Clone> {
     constructor: String,
     args: Vec<String>,
+    args_format: ArgsFormat,
+    args_document: Option<String>,
+    named_args: Vec<(String, String)>,
+    call_data: Option<Vec<u8>>,
     extrinsic_opts: ExtrinsicOpts<C, E, Signer>,
     value: E::Balance,
     gas_limit: Option<u64>,
@@ -85,7 +92,7 @@ pub struct InstantiateCommandBuilder<C: Config, E: Environment, Signer: Clone> {
 impl<C: Config, E: Environment, Signer> InstantiateCommandBuilder<C, E, Signer>
 where
     E::Balance: Default,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
     C::Hash: From<[u8; 32]>,
 {
     /// Returns a clean builder for [`InstantiateExec`].
@@ -95,6 +102,10 @@ where
         InstantiateCommandBuilder {
             constructor: String::from("new"),
             args: Vec::new(),
+            args_format: ArgsFormat::default(),
+            args_document: None,
+            named_args: Vec::new(),
+            call_data: None,
             extrinsic_opts,
             value: Default::default(),
             gas_limit: None,
@@ -117,6 +128,38 @@ where
         this
     }
 
+    /// Sets the format of the arguments, SCON by default.
+    pub fn args_format(self, args_format: ArgsFormat) -> Self {
+        let mut this = self;
+        this.args_format = args_format;
+        this
+    }
+
+    /// Sets a document holding all the arguments instead of `args`, e.g. the contents
+    /// of an args file, see [`ContractMessageTranscoder::encode_args_document`].
+    pub fn args_document(self, args_document: Option<String>) -> Self {
+        let mut this = self;
+        this.args_document = args_document;
+        this
+    }
+
+    /// Sets the arguments keyed by their labels instead of `args`, see
+    /// [`ContractMessageTranscoder::encode_named`].
+    pub fn named_args(self, named_args: Vec<(String, String)>) -> Self {
+        let mut this = self;
+        this.named_args = named_args;
+        this
+    }
+
+    /// Sets the already encoded call data of the constructor, i.e. the selector followed
+    /// by the arguments, instead of encoding the `args`, e.g. from a generated
+    /// client.
+    pub fn call_data(self, call_data: Vec<u8>) -> Self {
+        let mut this = self;
+        this.call_data = Some(call_data);
+        this
+    }
+
     /// Sets the initial balance to transfer to the instantiated contract.
     pub fn value(self, value: E::Balance) -> Self {
         let mut this = self;
@@ -156,8 +199,30 @@ where
     /// instantiation, or an error in case of failure.
     pub async fn done(self) -> Result<InstantiateExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
-        let data = transcoder.encode(&self.constructor, &self.args)?;
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
+        let data = if let Some(call_data) = self.call_data {
+            call_data
+        } else if let Some(ref document) = self.args_document {
+            transcoder.encode_args_document(
+                &self.constructor,
+                document,
+                self.args_format,
+            )?
+        } else if !self.named_args.is_empty() {
+            transcoder.encode_named(
+                &self.constructor,
+                self.named_args
+                    .iter()
+                    .map(|(label, arg)| (label.as_str(), arg)),
+                self.args_format,
+            )?
+        } else {
+            transcoder.encode_with_format(
+                &self.constructor,
+                &self.args,
+                self.args_format,
+            )?
+        };
         let url = self.extrinsic_opts.url();
         let code = if let Some(code) = artifacts.code {
             Code::Upload(code.0)
@@ -266,9 +331,9 @@ where
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
     C::Hash: IntoVisitor + EncodeAsType,
-    C::AccountId: IntoVisitor + Display,
+    C::AccountId: IntoVisitor + EncodeAsType + Display + Serialize,
     E::Balance: Serialize + EncodeAsType,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Decodes the result of a simulated contract instantiation.
     ///
@@ -294,7 +359,7 @@ where
                     .context(format!("Failed to decode return value {:?}", &ret_val))?;
                 let dry_run_result = InstantiateDryRunResult {
                     result: value,
-                    contract: ret_val.account_id.to_string(),
+                    contract: format_ss58(&ret_val.account_id, self.opts.ss58_prefix()),
                     reverted: ret_val.result.did_revert(),
                     gas_consumed: result.gas_consumed,
                     gas_required: result.gas_required,
@@ -322,7 +387,7 @@ where
     ) -> Result<ContractInstantiateResult<C::AccountId, E::Balance>> {
         let storage_deposit_limit = self.args.storage_deposit_limit;
         let call_request = InstantiateRequest::<C, E> {
-            origin: self.opts.signer().account_id(),
+            origin: self.opts.origin(),
             value: self.args.value,
             gas_limit: None,
             storage_deposit_limit,
@@ -338,18 +403,9 @@ where
         code: Vec<u8>,
         gas_limit: Weight,
     ) -> Result<InstantiateExecResult<C>, ErrorVariant> {
-        let call = InstantiateWithCode::new(
-            self.args.value,
-            gas_limit,
-            self.args.storage_deposit_limit,
-            code,
-            self.args.data.clone(),
-            self.args.salt.clone(),
-        )
-        .build();
+        let call = self.instantiate_with_code_call(code, gas_limit);
 
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        let events = submit_call(&self.client, &self.rpc, &self.opts, &call).await?;
 
         // The CodeStored event is only raised if the contract has not already been
         // uploaded.
@@ -357,14 +413,14 @@ where
             .find_first::<CodeStored<C::Hash>>()?
             .map(|code_stored| code_stored.code_hash);
 
-        let instantiated = events
-            .find_last::<ContractInstantiated<C::AccountId>>()?
-            .ok_or_else(|| anyhow!("Failed to find Instantiated event"))?;
+        let contract_address = self.instantiated_contract(
+            events.find_last::<ContractInstantiated<C::AccountId>>()?,
+        )?;
 
         Ok(InstantiateExecResult {
             events,
             code_hash,
-            contract_address: instantiated.contract,
+            contract_address,
         })
     }
 
@@ -373,28 +429,64 @@ where
         code_hash: C::Hash,
         gas_limit: Weight,
     ) -> Result<InstantiateExecResult<C>, ErrorVariant> {
-        let call = Instantiate::<C::Hash, E::Balance>::new(
+        let call = self.instantiate_call(code_hash, gas_limit);
+
+        let events = submit_call(&self.client, &self.rpc, &self.opts, &call).await?;
+
+        let contract_address = self.instantiated_contract(
+            events.find_first::<ContractInstantiated<C::AccountId>>()?,
+        )?;
+
+        Ok(InstantiateExecResult {
+            events,
+            code_hash: None,
+            contract_address,
+        })
+    }
+
+    fn instantiate_with_code_call(
+        &self,
+        code: Vec<u8>,
+        gas_limit: Weight,
+    ) -> subxt::tx::Payload<InstantiateWithCode<E::Balance>> {
+        InstantiateWithCode::new(
             self.args.value,
             gas_limit,
             self.args.storage_deposit_limit,
-            code_hash,
+            code,
             self.args.data.clone(),
             self.args.salt.clone(),
         )
-        .build();
-
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        .build()
+    }
 
-        let instantiated = events
-            .find_first::<ContractInstantiated<C::AccountId>>()?
-            .ok_or_else(|| anyhow!("Failed to find Instantiated event"))?;
+    fn instantiate_call(
+        &self,
+        code_hash: C::Hash,
+        gas_limit: Weight,
+    ) -> subxt::tx::Payload<Instantiate<C::Hash, E::Balance>> {
+        Instantiate::new(
+            self.args.value,
+            gas_limit,
+            self.args.storage_deposit_limit,
+            code_hash,
+            self.args.data.clone(),
+            self.args.salt.clone(),
+        )
+        .build()
+    }
 
-        Ok(InstantiateExecResult {
-            events,
-            code_hash: None,
-            contract_address: instantiated.contract,
-        })
+    /// Returns the address of the instantiated contract, or `None` if the instantiation
+    /// is pending further multisig approvals.
+    fn instantiated_contract(
+        &self,
+        instantiated: Option<ContractInstantiated<C::AccountId>>,
+    ) -> Result<Option<C::AccountId>, ErrorVariant> {
+        match instantiated {
+            Some(instantiated) => Ok(Some(instantiated.contract)),
+            None if self.opts.multisig().is_some() => Ok(None),
+            None => Err(anyhow!("Failed to find Instantiated event").into()),
+        }
     }
 
     /// Initiates the deployment of a smart contract on the blockchain.
@@ -424,6 +516,21 @@ where
         }
     }
 
+    /// Estimates the fee of the instantiation extrinsic with the given gas limit, less
+    /// any tip, without modifying the blockchain.
+    pub async fn estimate_fee(&self, gas_limit: Weight) -> Result<u128, ErrorVariant> {
+        match self.args.code.clone() {
+            Code::Upload(code) => {
+                let call = self.instantiate_with_code_call(code, gas_limit);
+                estimate_fee(&self.client, &self.rpc, &self.opts, &call).await
+            }
+            Code::Existing(code_hash) => {
+                let call = self.instantiate_call(code_hash, gas_limit);
+                estimate_fee(&self.client, &self.rpc, &self.opts, &call).await
+            }
+        }
+    }
+
     /// Estimates the gas required for the contract instantiation process without
     /// modifying the blockchain.
     ///
@@ -488,7 +595,9 @@ where
 pub struct InstantiateExecResult<C: Config> {
     pub events: ExtrinsicEvents<C>,
     pub code_hash: Option<C::Hash>,
-    pub contract_address: C::AccountId,
+    /// The address of the instantiated contract, `None` if the instantiation is pending
+    /// further multisig approvals.
+    pub contract_address: Option<C::AccountId>,
 }
 
 /// Result of the contract call
@@ -497,14 +497,18 @@ async fn api_build_upload_instantiate_call() {
     assert!(instantiate_result.is_ok(), "instantiate code failed");
     let instantiate_result: InstantiateExecResult<DefaultConfig> =
         instantiate_result.unwrap();
-    let contract_account = instantiate_result.contract_address.to_string();
+    let contract_account = instantiate_result
+        .contract_address
+        .clone()
+        .unwrap()
+        .to_string();
     assert_eq!(48, contract_account.len(), "{contract_account:?}");
 
     // call the contract
     // the value should be true
     let call: CallExec<DefaultConfig, DefaultEnvironment, Keypair> =
         CallCommandBuilder::new(
-            instantiate_result.contract_address.clone(),
+            instantiate_result.contract_address.clone().unwrap(),
             "get",
             opts.clone(),
         )
@@ -533,7 +537,7 @@ async fn api_build_upload_instantiate_call() {
     // flip the value
     let call: CallExec<DefaultConfig, DefaultEnvironment, Keypair> =
         CallCommandBuilder::new(
-            instantiate_result.contract_address.clone(),
+            instantiate_result.contract_address.clone().unwrap(),
             "flip",
             opts.clone(),
         )
@@ -557,7 +561,7 @@ async fn api_build_upload_instantiate_call() {
     // make sure the value has been flipped
     let call: CallExec<DefaultConfig, DefaultEnvironment, Keypair> =
         CallCommandBuilder::new(
-            instantiate_result.contract_address.clone(),
+            instantiate_result.contract_address.clone().unwrap(),
             "get",
             opts.clone(),
         )
@@ -14,10 +14,13 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+mod artifact_registry;
 mod balance;
 mod call;
 mod contract_artifacts;
+mod contract_extrinsic;
 mod contract_info;
+mod contract_snapshot;
 mod contract_storage;
 mod env_check;
 mod error;
@@ -25,9 +28,14 @@ mod events;
 mod extrinsic_calls;
 mod extrinsic_opts;
 mod instantiate;
+mod multisig;
+mod nonce;
 pub mod pallet_contracts_primitives;
+mod proxy;
 mod remove;
 mod rpc;
+mod signer;
+mod ss58;
 mod upload;
 
 #[cfg(test)]
@@ -38,17 +46,24 @@ mod contract_storage_tests;
 mod integration_tests;
 
 use env_check::compare_node_env_with_contract;
+use extrinsic_opts::ExtrinsicOpts;
 
-use anyhow::Result;
+use anyhow::{
+    Context,
+    Result,
+};
 use contract_build::{
     CrateMetadata,
     Verbosity,
     DEFAULT_KEY_COL_WIDTH,
 };
+use extrinsic_calls::EncodedCall;
 use scale::{
     Decode,
     Encode,
 };
+use serde::Serialize;
+use sp_weights::Weight;
 use subxt::{
     backend::legacy::LegacyRpcMethods,
     blocks,
@@ -57,11 +72,16 @@ use subxt::{
         DefaultExtrinsicParamsBuilder,
         ExtrinsicParams,
     },
+    ext::{
+        scale_decode::IntoVisitor,
+        scale_encode::EncodeAsType,
+    },
     tx,
     Config,
     OnlineClient,
 };
 
+pub use artifact_registry::ArtifactRegistry;
 pub use balance::{
     BalanceVariant,
     TokenMetadata,
@@ -71,26 +91,57 @@ pub use call::{
     CallExec,
 };
 pub use contract_artifacts::ContractArtifacts;
+pub use contract_extrinsic::{
+    fetch_extrinsic,
+    ContractCall,
+    ContractExtrinsic,
+};
+#[allow(deprecated)]
 pub use contract_info::{
     fetch_all_contracts,
+    fetch_all_contracts_at,
+    fetch_code_info_at,
     fetch_contract_info,
+    fetch_contract_info_at,
+    fetch_contract_instantiation,
+    fetch_contracts_page_at,
     fetch_wasm_code,
+    fetch_wasm_code_at,
+    CodeInfo,
     ContractInfo,
+    ContractInstantiation,
     TrieId,
 };
 use contract_metadata::ContractMetadata;
+pub use contract_snapshot::ContractSnapshot;
 pub use contract_storage::{
+    CellKind,
     ContractStorage,
     ContractStorageCell,
+    ContractStorageData,
+    ContractStorageDiff,
+    ContractStorageEntry,
     ContractStorageLayout,
     ContractStorageRpc,
+    RootKeyEntry,
+    StorageChange,
+    StorageDiffEntry,
+    StorageEntryKey,
+};
+pub use contract_transcode::{
+    ArgsFormat,
+    ContractMessageTranscoder,
+    CustomTypes,
+    Renderer,
 };
-pub use contract_transcode::ContractMessageTranscoder;
 pub use error::{
     ErrorVariant,
     GenericError,
 };
-pub use events::DisplayEvents;
+pub use events::{
+    ContractEventFilter,
+    DisplayEvents,
+};
 pub use extrinsic_opts::ExtrinsicOptsBuilder;
 pub use instantiate::{
     Code,
@@ -100,11 +151,24 @@ pub use instantiate::{
     InstantiateExec,
     InstantiateExecResult,
 };
+pub use multisig::{
+    Multisig,
+    MultisigStatus,
+    Timepoint,
+};
+pub use nonce::NonceTracker;
 pub use remove::{
     RemoveCommandBuilder,
     RemoveExec,
     RemoveResult,
 };
+pub use signer::TrySigner;
+pub use ss58::{
+    fetch_ss58_prefix,
+    format_ss58,
+    query_ss58_prefix,
+    SUBSTRATE_SS58_PREFIX,
+};
 
 pub use upload::{
     UploadCommandBuilder,
@@ -128,6 +192,192 @@ impl WasmCode {
     }
 }
 
+/// The call submitted for a contracts call: the call itself, or the call wrapped into a
+/// proxy call and a multisig operation if configured in the options.
+enum WrappedCall<'a, Call, AccountId> {
+    Call(&'a Call),
+    Proxy(tx::Payload<extrinsic_calls::Proxy<AccountId>>),
+    Multisig(multisig::MultisigCall<AccountId>),
+}
+
+impl<Call, AccountId> tx::TxPayload for WrappedCall<'_, Call, AccountId>
+where
+    Call: tx::TxPayload,
+    AccountId: EncodeAsType,
+{
+    fn encode_call_data_to(
+        &self,
+        metadata: &subxt::Metadata,
+        out: &mut Vec<u8>,
+    ) -> core::result::Result<(), subxt::Error> {
+        match self {
+            WrappedCall::Call(call) => call.encode_call_data_to(metadata, out),
+            WrappedCall::Proxy(call) => call.encode_call_data_to(metadata, out),
+            WrappedCall::Multisig(call) => call.encode_call_data_to(metadata, out),
+        }
+    }
+}
+
+/// Wraps the call into a proxy call and a multisig operation if configured in the
+/// options.
+async fn wrap_call<'a, C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+    call: &'a Call,
+) -> core::result::Result<WrappedCall<'a, Call, C::AccountId>, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: EncodeAsType + IntoVisitor,
+    E: ink_env::Environment,
+    Call: tx::TxPayload,
+    Signer: TrySigner<C> + Clone,
+{
+    let proxy_call = opts
+        .proxy_for()
+        .map(|real| proxy::wrap_call(client, real, call))
+        .transpose()?;
+    let wrapped = match (opts.multisig(), proxy_call) {
+        (Some(multisig), Some(proxy_call)) => {
+            WrappedCall::Multisig(multisig.wrap_call(client, rpc, &proxy_call).await?)
+        }
+        (Some(multisig), None) => {
+            WrappedCall::Multisig(multisig.wrap_call(client, rpc, call).await?)
+        }
+        (None, Some(proxy_call)) => WrappedCall::Proxy(proxy_call),
+        (None, None) => WrappedCall::Call(call),
+    };
+    Ok(wrapped)
+}
+
+/// Returns the extrinsic params for the nonce and the tip, mortality and fee payment
+/// configured in the options.
+async fn extrinsic_params<C, E, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+    nonce: u64,
+) -> core::result::Result<<C::ExtrinsicParams as ExtrinsicParams<C>>::Params, subxt::Error>
+where
+    C: Config,
+    E: ink_env::Environment,
+    Signer: TrySigner<C> + Clone,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
+        From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
+{
+    let mut params = DefaultExtrinsicParamsBuilder::new().nonce(nonce);
+    if let Some(for_n_blocks) = opts.mortality() {
+        let best_block = get_best_block(rpc).await?;
+        let header = rpc
+            .chain_get_header(Some(best_block))
+            .await?
+            .ok_or(subxt::Error::Other("Best block header not found".into()))?;
+        params = params.mortal(&header, for_n_blocks);
+    }
+    let tip = opts.tip().unwrap_or_default();
+    params = match opts.fee_asset() {
+        Some(asset_id) => {
+            check_fee_asset_supported(client)?;
+            params.tip_of(tip, asset_id.clone())
+        }
+        None => params.tip(tip),
+    };
+    Ok(params.build().into())
+}
+
+/// Returns an error if the chain doesn't support paying fees in an asset. Without the
+/// `ChargeAssetTxPayment` extension the asset would be silently ignored and the fees
+/// paid in the native token.
+fn check_fee_asset_supported<C: Config>(
+    client: &OnlineClient<C>,
+) -> core::result::Result<(), subxt::Error> {
+    let metadata = client.metadata();
+    let charges_asset = metadata
+        .extrinsic()
+        .signed_extensions()
+        .iter()
+        .any(|ext| ext.identifier() == "ChargeAssetTxPayment");
+    if !charges_asset {
+        return Err(subxt::Error::Other(
+            "Paying fees in an asset requires the `ChargeAssetTxPayment` signed \
+             extension, which the chain does not support"
+                .into(),
+        ))
+    }
+    Ok(())
+}
+
+/// The maximum size of the parts of a signed extrinsic besides the call and the address
+/// of the signer, used to estimate its length before it is signed.
+const SIGNED_EXTRINSIC_OVERHEAD: usize = 5 // compact length prefix
+    + 1 // version
+    + 66 // `MultiSignature`, an ECDSA signature being the largest
+    + 2 // mortality
+    + 9 // compact nonce
+    + 17 // compact tip
+    + 10; // asset id of `ChargeAssetTxPayment`
+
+/// Returns an estimate of the fee for the call, wrapped into a proxy call and a multisig
+/// operation if configured in the options, less any tip.
+///
+/// The fee is queried for the encoded call and an estimate of the length of the signed
+/// extrinsic, so that the signer is not asked to sign a transaction which is not
+/// submitted.
+async fn estimate_fee<C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+    call: &Call,
+) -> core::result::Result<u128, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: EncodeAsType + IntoVisitor,
+    E: ink_env::Environment,
+    Call: tx::TxPayload,
+    Signer: TrySigner<C> + Clone,
+{
+    if opts.fee_asset().is_some() {
+        check_fee_asset_supported(client)?;
+    }
+    let call = wrap_call(client, rpc, opts, call).await?;
+    let call_data = client.tx().call_data(&call)?;
+    let len = call_data.len()
+        + opts.signer().address().encode().len()
+        + SIGNED_EXTRINSIC_OVERHEAD;
+    let (_weight, _class, partial_fee): (Weight, u8, u128) = state_call(
+        rpc,
+        "TransactionPaymentCallApi_query_call_info",
+        (EncodedCall(call_data), len as u32),
+    )
+    .await?;
+    Ok(partial_fee)
+}
+
+/// Submit the call, wrapped into a proxy call and a multisig operation if configured in
+/// the options, and wait for the transaction to be included successfully into a block.
+async fn submit_call<C, E, Call, Signer>(
+    client: &OnlineClient<C>,
+    rpc: &LegacyRpcMethods<C>,
+    opts: &ExtrinsicOpts<C, E, Signer>,
+    call: &Call,
+) -> core::result::Result<blocks::ExtrinsicEvents<C>, ErrorVariant>
+where
+    C: Config,
+    C::AccountId: EncodeAsType + IntoVisitor + Serialize,
+    E: ink_env::Environment,
+    Call: tx::TxPayload,
+    Signer: TrySigner<C> + Clone,
+    <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
+        From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
+{
+    let call = wrap_call(client, rpc, opts, call).await?;
+    let events = submit_extrinsic(client, rpc, &call, opts).await?;
+    if opts.proxy_for().is_some() {
+        proxy::check_executed(client, &events)?;
+    }
+    Ok(events)
+}
+
 /// Wait for the transaction to be included successfully into a block.
 ///
 /// # Errors
@@ -140,30 +390,56 @@ impl WasmCode {
 ///
 /// Currently this will report success once the transaction is included in a block. In the
 /// future there could be a flag to wait for finality before reporting success.
-async fn submit_extrinsic<C, Call, Signer>(
+async fn submit_extrinsic<C, E, Call, Signer>(
     client: &OnlineClient<C>,
     rpc: &LegacyRpcMethods<C>,
     call: &Call,
-    signer: &Signer,
+    opts: &ExtrinsicOpts<C, E, Signer>,
 ) -> core::result::Result<blocks::ExtrinsicEvents<C>, subxt::Error>
 where
     C: Config,
+    C::AccountId: Serialize,
+    E: ink_env::Environment,
     Call: tx::TxPayload,
-    Signer: tx::Signer<C>,
+    Signer: TrySigner<C> + Clone,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
 {
-    let account_id = Signer::account_id(signer);
-    let account_nonce = get_account_nonce(client, rpc, &account_id).await?;
-
-    let params = DefaultExtrinsicParamsBuilder::new()
-        .nonce(account_nonce)
-        .build();
-    let mut tx = client
-        .tx()
-        .create_signed_offline(call, signer, params.into())?
-        .submit_and_watch()
-        .await?;
+    let account_id = opts.signer().account_id();
+    let account_nonce = match (opts.nonce(), opts.nonce_tracker()) {
+        (Some(nonce), _) => nonce,
+        (None, Some(tracker)) => tracker.next_nonce(rpc, &account_id).await?,
+        (None, None) => get_account_nonce(client, rpc, &account_id).await?,
+    };
+
+    let submitted = match extrinsic_params(client, rpc, opts, account_nonce).await {
+        Ok(params) => {
+            match client
+                .tx()
+                .create_partial_signed_offline(call, params)
+                .and_then(|partial| signer::sign_extrinsic(&partial, opts.signer()))
+            {
+                Ok(tx) => tx.submit_and_watch().await,
+                Err(err) => Err(err),
+            }
+        }
+        Err(err) => Err(err),
+    };
+
+    // The nonce of an extrinsic which does not make it into a block can be reused.
+    let release_nonce = || {
+        if let (None, Some(tracker)) = (opts.nonce(), opts.nonce_tracker()) {
+            tracker.release(&account_id, account_nonce);
+        }
+    };
+
+    let mut tx = match submitted {
+        Ok(tx) => tx,
+        Err(err) => {
+            release_nonce();
+            return Err(err)
+        }
+    };
 
     // Below we use the low level API to replicate the `wait_for_in_block` behaviour which
     // was removed in subxt 0.33.0. See https://github.com/paritytech/subxt/pull/1237.
@@ -188,9 +464,11 @@ where
                 return Err(TransactionError::Error(message).into())
             }
             TxStatus::Invalid { message } => {
+                release_nonce();
                 return Err(TransactionError::Invalid(message).into())
             }
             TxStatus::Dropped { message } => {
+                release_nonce();
                 return Err(TransactionError::Dropped(message).into())
             }
             _ => continue,
@@ -226,11 +504,23 @@ async fn state_call<C, A: Encode, R: Decode>(
     func: &str,
     args: A,
 ) -> Result<R>
+where
+    C: Config,
+{
+    state_call_at(rpc, func, args, None).await
+}
+
+async fn state_call_at<C, A: Encode, R: Decode>(
+    rpc: &LegacyRpcMethods<C>,
+    func: &str,
+    args: A,
+    block_hash: Option<C::Hash>,
+) -> Result<R>
 where
     C: Config,
 {
     let params = args.encode();
-    let bytes = rpc.state_call(func, Some(&params), None).await?;
+    let bytes = rpc.state_call(func, Some(&params), block_hash).await?;
     Ok(R::decode(&mut bytes.as_ref())?)
 }
 
@@ -246,6 +536,14 @@ where
         .ok_or(subxt::Error::Other("Best block not found".into()))
 }
 
+/// Returns the hash of the parent block of the `header`.
+fn parent_hash<C: Config>(header: &C::Header) -> Result<C::Hash> {
+    // the parent hash is the first field of the header, which the `Header` trait
+    // doesn't provide
+    C::Hash::decode(&mut &header.encode()[..])
+        .context("Failed to decode the parent hash of the block header")
+}
+
 fn check_env_types<C>(
     client: &OnlineClient<C>,
     transcoder: &ContractMessageTranscoder,
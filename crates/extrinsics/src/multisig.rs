@@ -0,0 +1,424 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{
+    get_best_block,
+    parent_hash,
+};
+use crate::extrinsic_calls::{
+    ApproveAsMulti,
+    AsMulti,
+    EncodedCall,
+};
+use anyhow::{
+    anyhow,
+    Context,
+    Result,
+};
+use contract_metadata::byte_str::serialize_as_byte_str;
+use scale::{
+    Decode,
+    Encode,
+};
+use sp_core::hashing::blake2_256;
+use sp_weights::Weight;
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    blocks::ExtrinsicEvents,
+    events::StaticEvent,
+    ext::{
+        scale_decode::{
+            DecodeAsType,
+            IntoVisitor,
+        },
+        scale_encode::EncodeAsType,
+        scale_value::Value,
+    },
+    tx::{
+        Payload,
+        TxPayload,
+    },
+    Config,
+    Metadata,
+    OnlineClient,
+};
+
+/// The prefix used by `pallet-multisig` to derive the multisig account id.
+const MULTI_ACCOUNT_PREFIX: &[u8; 16] = b"modlpy/utilisuba";
+
+/// The signatories and the threshold of a `pallet-multisig` account, on behalf of which
+/// contract extrinsics are submitted.
+#[derive(Clone, Debug)]
+pub struct Multisig<AccountId> {
+    threshold: u16,
+    signatories: Vec<AccountId>,
+    other_signatories: Vec<AccountId>,
+    account_id: AccountId,
+}
+
+impl<AccountId> Multisig<AccountId>
+where
+    AccountId: Encode + Decode + Ord + Clone,
+{
+    /// Creates a new multisig for the given `signatories`, which are required to
+    /// include the `signer`.
+    pub fn new(
+        threshold: u16,
+        signatories: Vec<AccountId>,
+        signer: &AccountId,
+    ) -> Result<Self> {
+        let mut signatories = signatories;
+        signatories.sort();
+        signatories.dedup();
+        if !signatories.contains(signer) {
+            anyhow::bail!("The signer must be one of the multisig signatories")
+        }
+        if signatories.len() < 2 {
+            anyhow::bail!("A multisig requires at least two signatories")
+        }
+        if threshold < 2 || threshold as usize > signatories.len() {
+            anyhow::bail!(
+                "The multisig threshold must be between 2 and the number of signatories ({})",
+                signatories.len()
+            )
+        }
+        let other_signatories = signatories
+            .iter()
+            .filter(|s| *s != signer)
+            .cloned()
+            .collect();
+        let entropy =
+            (MULTI_ACCOUNT_PREFIX, &signatories, threshold).using_encoded(blake2_256);
+        let account_id = AccountId::decode(&mut &entropy[..])
+            .context("Failed to decode the multisig account id")?;
+        Ok(Self {
+            threshold,
+            signatories,
+            other_signatories,
+            account_id,
+        })
+    }
+}
+
+impl<AccountId> Multisig<AccountId> {
+    /// Returns the number of approvals required to execute a call.
+    pub fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Returns all signatories, sorted.
+    pub fn signatories(&self) -> &[AccountId] {
+        &self.signatories
+    }
+
+    /// Returns the multisig account, which is the origin of the wrapped calls.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+}
+
+impl<AccountId> Multisig<AccountId>
+where
+    AccountId: Encode + Clone + EncodeAsType + IntoVisitor,
+{
+    /// Wraps the call into `as_multi` if it collects the final approval, or into
+    /// `approve_as_multi` otherwise.
+    pub(crate) async fn wrap_call<C, Call>(
+        &self,
+        client: &OnlineClient<C>,
+        rpc: &LegacyRpcMethods<C>,
+        call: &Call,
+    ) -> Result<MultisigCall<AccountId>>
+    where
+        C: Config,
+        Call: TxPayload,
+    {
+        let call_data = client.tx().call_data(call)?;
+        let call_hash = blake2_256(&call_data);
+        let best_block = get_best_block(rpc).await?;
+        let pending = self
+            .pending_operation(client, best_block, &call_hash)
+            .await?;
+        let max_weight = query_call_weight(rpc, &call_data).await?;
+
+        let approvals = pending.as_ref().map_or(0, |op| op.approvals.len());
+        let maybe_timepoint = pending.map(|op| op.when);
+        let call = if approvals + 1 >= self.threshold as usize {
+            MultisigCall::AsMulti(
+                AsMulti::new(
+                    self.threshold,
+                    self.other_signatories.clone(),
+                    maybe_timepoint,
+                    EncodedCall(call_data),
+                    max_weight,
+                )
+                .build(),
+            )
+        } else {
+            MultisigCall::ApproveAsMulti(
+                ApproveAsMulti::new(
+                    self.threshold,
+                    self.other_signatories.clone(),
+                    maybe_timepoint,
+                    call_hash,
+                    max_weight,
+                )
+                .build(),
+            )
+        };
+        Ok(call)
+    }
+
+    /// Returns the state of the multisig operation submitted with `events`.
+    pub async fn status<C>(
+        &self,
+        client: &OnlineClient<C>,
+        events: &ExtrinsicEvents<C>,
+    ) -> Result<MultisigStatus<AccountId>>
+    where
+        C: Config,
+        AccountId: PartialEq,
+    {
+        if let Some(executed) = events.find_first::<MultisigExecuted<AccountId>>()? {
+            // the operation is removed once executed, so read the approvals collected
+            // before the inclusion block and add the one which executed it
+            let block = client.blocks().at(events.block_hash()).await?;
+            let parent_hash = parent_hash::<C>(block.header())?;
+            let mut approvals = self
+                .pending_operation(client, parent_hash, &executed.call_hash)
+                .await?
+                .map(|op| op.approvals)
+                .unwrap_or_default();
+            approvals.push(executed.approving);
+            return Ok(MultisigStatus {
+                multisig_account: self.account_id.clone(),
+                call_hash: executed.call_hash,
+                executed: true,
+                timepoint: Some(executed.timepoint),
+                approvals,
+                pending: Vec::new(),
+            })
+        }
+        let call_hash = match events.find_first::<NewMultisig>()? {
+            Some(new) => new.call_hash,
+            None => {
+                events
+                    .find_first::<MultisigApproval>()?
+                    .ok_or_else(|| anyhow!("No multisig event found"))?
+                    .call_hash
+            }
+        };
+        let operation = self
+            .pending_operation(client, events.block_hash(), &call_hash)
+            .await?
+            .ok_or_else(|| {
+                anyhow!("Multisig operation 0x{} not found", hex::encode(call_hash))
+            })?;
+        let pending = self
+            .signatories
+            .iter()
+            .filter(|s| !operation.approvals.contains(s))
+            .cloned()
+            .collect();
+        Ok(MultisigStatus {
+            multisig_account: self.account_id.clone(),
+            call_hash,
+            executed: false,
+            timepoint: Some(operation.when),
+            approvals: operation.approvals,
+            pending,
+        })
+    }
+
+    /// Fetches the pending multisig operation for the call hash, if any.
+    async fn pending_operation<C: Config>(
+        &self,
+        client: &OnlineClient<C>,
+        block_hash: C::Hash,
+        call_hash: &[u8; 32],
+    ) -> Result<Option<MultisigOperation<AccountId>>> {
+        let storage_query = subxt::dynamic::storage(
+            "Multisig",
+            "Multisigs",
+            vec![
+                Value::from_bytes(self.account_id.encode()),
+                Value::from_bytes(call_hash),
+            ],
+        );
+        let operation = client
+            .storage()
+            .at(block_hash)
+            .fetch(&storage_query)
+            .await?
+            .map(|op| op.as_type::<MultisigOperation<AccountId>>())
+            .transpose()?;
+        Ok(operation)
+    }
+}
+
+/// Queries the dispatch weight of the encoded call, used as `max_weight` for the
+/// multisig execution.
+async fn query_call_weight<C: Config>(
+    rpc: &LegacyRpcMethods<C>,
+    call_data: &[u8],
+) -> Result<Weight> {
+    let params = (EncodedCall(call_data.to_vec()), call_data.len() as u32).encode();
+    let bytes = rpc
+        .state_call(
+            "TransactionPaymentCallApi_query_call_info",
+            Some(&params),
+            None,
+        )
+        .await?;
+    // The `weight` is the first field of the returned `RuntimeDispatchInfo`.
+    Ok(Weight::decode(&mut bytes.as_ref())?)
+}
+
+/// The state of a multisig operation.
+#[derive(Debug, serde::Serialize)]
+pub struct MultisigStatus<AccountId> {
+    /// The multisig account on behalf of which the call is dispatched.
+    pub multisig_account: AccountId,
+    /// The hash of the wrapped call.
+    #[serde(serialize_with = "serialize_as_byte_str")]
+    pub call_hash: [u8; 32],
+    /// Whether the call has been executed.
+    pub executed: bool,
+    /// The block number and extrinsic index of the first approval.
+    pub timepoint: Option<Timepoint>,
+    /// The signatories which have approved the call.
+    pub approvals: Vec<AccountId>,
+    /// The signatories whose approvals are still pending.
+    pub pending: Vec<AccountId>,
+}
+
+/// A point in time of the chain: the block number and the extrinsic index in that block.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, DecodeAsType, EncodeAsType, serde::Serialize,
+)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+#[encode_as_type(crate_path = "subxt::ext::scale_encode")]
+pub struct Timepoint {
+    pub height: u64,
+    pub index: u32,
+}
+
+/// A pending multisig operation as stored in `Multisig::Multisigs`.
+#[derive(DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct MultisigOperation<AccountId> {
+    when: Timepoint,
+    approvals: Vec<AccountId>,
+}
+
+/// A new multisig operation has begun.
+#[derive(Debug, DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct NewMultisig {
+    call_hash: [u8; 32],
+}
+
+impl StaticEvent for NewMultisig {
+    const PALLET: &'static str = "Multisig";
+    const EVENT: &'static str = "NewMultisig";
+}
+
+/// A multisig operation has been approved by someone.
+#[derive(Debug, DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct MultisigApproval {
+    call_hash: [u8; 32],
+}
+
+impl StaticEvent for MultisigApproval {
+    const PALLET: &'static str = "Multisig";
+    const EVENT: &'static str = "MultisigApproval";
+}
+
+/// A multisig operation has been executed.
+#[derive(Debug, DecodeAsType)]
+#[decode_as_type(crate_path = "subxt::ext::scale_decode")]
+struct MultisigExecuted<AccountId> {
+    approving: AccountId,
+    timepoint: Timepoint,
+    call_hash: [u8; 32],
+}
+
+impl<AccountId: IntoVisitor> StaticEvent for MultisigExecuted<AccountId> {
+    const PALLET: &'static str = "Multisig";
+    const EVENT: &'static str = "MultisigExecuted";
+}
+
+/// A contracts call wrapped into one of the `pallet-multisig` calls.
+pub(crate) enum MultisigCall<AccountId> {
+    AsMulti(Payload<AsMulti<AccountId>>),
+    ApproveAsMulti(Payload<ApproveAsMulti<AccountId>>),
+}
+
+impl<AccountId: EncodeAsType> TxPayload for MultisigCall<AccountId> {
+    fn encode_call_data_to(
+        &self,
+        metadata: &Metadata,
+        out: &mut Vec<u8>,
+    ) -> Result<(), subxt::Error> {
+        match self {
+            MultisigCall::AsMulti(call) => call.encode_call_data_to(metadata, out),
+            MultisigCall::ApproveAsMulti(call) => call.encode_call_data_to(metadata, out),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subxt::utils::AccountId32;
+
+    fn account(seed: u8) -> AccountId32 {
+        AccountId32([seed; 32])
+    }
+
+    #[test]
+    fn multisig_excludes_signer_from_other_signatories() {
+        let multisig =
+            Multisig::new(2, vec![account(3), account(1), account(2)], &account(2))
+                .unwrap();
+
+        assert_eq!(
+            multisig.signatories(),
+            &[account(1), account(2), account(3)]
+        );
+        assert_eq!(multisig.other_signatories, vec![account(1), account(3)]);
+    }
+
+    #[test]
+    fn multisig_account_id_does_not_depend_on_signer_or_order() {
+        let a = Multisig::new(2, vec![account(1), account(2)], &account(1)).unwrap();
+        let b = Multisig::new(2, vec![account(2), account(1)], &account(2)).unwrap();
+
+        assert_eq!(a.account_id(), b.account_id());
+    }
+
+    #[test]
+    fn multisig_with_invalid_threshold_fails() {
+        assert!(Multisig::new(1, vec![account(1), account(2)], &account(1)).is_err());
+        assert!(Multisig::new(3, vec![account(1), account(2)], &account(1)).is_err());
+    }
+
+    #[test]
+    fn multisig_without_signer_fails() {
+        assert!(Multisig::new(2, vec![account(1), account(2)], &account(3)).is_err());
+    }
+}
@@ -0,0 +1,124 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use scale::Encode;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        Mutex,
+    },
+};
+use subxt::{
+    backend::legacy::LegacyRpcMethods,
+    Config,
+};
+
+/// Hands out consecutive nonces to extrinsics submitted from the same account before
+/// the previous ones are included in a block.
+///
+/// Clones of the tracker share the handed out nonces, so a single tracker can be set in
+/// the [`crate::ExtrinsicOpts`] of several `UploadExec`, `InstantiateExec`, `CallExec`
+/// and `RemoveExec` which submit extrinsics concurrently.
+#[derive(Clone, Debug, Default)]
+pub struct NonceTracker {
+    /// The next nonce to hand out, by encoded account id.
+    next: Arc<Mutex<HashMap<Vec<u8>, u64>>>,
+}
+
+impl NonceTracker {
+    /// Creates a new tracker without any handed out nonces.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the nonce for the next extrinsic of the account.
+    ///
+    /// This is the next nonce reported by the node, which accounts for the transactions
+    /// of the account in the pool, unless a higher nonce is still pending from a previous
+    /// call.
+    pub async fn next_nonce<C>(
+        &self,
+        rpc: &LegacyRpcMethods<C>,
+        account_id: &C::AccountId,
+    ) -> Result<u64, subxt::Error>
+    where
+        C: Config,
+        C::AccountId: Serialize,
+    {
+        let pool_nonce = rpc.system_account_next_index(account_id).await?;
+        Ok(self.reserve(account_id.encode(), pool_nonce))
+    }
+
+    /// Releases the nonce of an extrinsic which failed to be submitted or was dropped
+    /// from the transaction pool.
+    ///
+    /// The nonce is handed out again if it was the last one. Otherwise the nonces handed
+    /// out after it would leave a gap, so the account is resynced with the nonce reported
+    /// by the node on the next call to [`Self::next_nonce`].
+    pub fn release<AccountId: Encode>(&self, account_id: &AccountId, nonce: u64) {
+        let mut next = self.next.lock().expect("nonce tracker lock poisoned");
+        let account = account_id.encode();
+        match next.get(&account) {
+            Some(next_nonce) if *next_nonce == nonce + 1 => {
+                next.insert(account, nonce);
+            }
+            Some(next_nonce) if *next_nonce > nonce => {
+                next.remove(&account);
+            }
+            _ => (),
+        }
+    }
+
+    fn reserve(&self, account: Vec<u8>, pool_nonce: u64) -> u64 {
+        let mut next = self.next.lock().expect("nonce tracker lock poisoned");
+        let next_nonce = next.entry(account).or_insert(pool_nonce);
+        let nonce = (*next_nonce).max(pool_nonce);
+        *next_nonce = nonce + 1;
+        nonce
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserve_hands_out_consecutive_nonces() {
+        let tracker = NonceTracker::new();
+        let shared = tracker.clone();
+
+        assert_eq!(tracker.reserve(vec![1], 5), 5);
+        assert_eq!(shared.reserve(vec![1], 5), 6);
+        assert_eq!(tracker.reserve(vec![2], 0), 0);
+        // the pool caught up with transactions submitted elsewhere
+        assert_eq!(tracker.reserve(vec![1], 10), 10);
+    }
+
+    #[test]
+    fn release_hands_out_last_nonce_again() {
+        let tracker = NonceTracker::new();
+        let first = tracker.reserve(1u8.encode(), 3);
+        let second = tracker.reserve(1u8.encode(), 3);
+
+        tracker.release(&1u8, second);
+        assert_eq!(tracker.reserve(1u8.encode(), 3), second);
+        // releasing an earlier nonce resyncs with the pool to fill the gap
+        tracker.release(&1u8, first);
+        assert_eq!(tracker.reserve(1u8.encode(), first), first);
+    }
+}
@@ -0,0 +1,69 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::ErrorVariant;
+use crate::extrinsic_calls::{
+    EncodedCall,
+    Proxy,
+};
+use scale::Decode;
+use sp_runtime::DispatchError;
+use subxt::{
+    blocks::ExtrinsicEvents,
+    tx::{
+        Payload,
+        TxPayload,
+    },
+    utils::MultiAddress,
+    Config,
+    OnlineClient,
+};
+
+/// Wraps the call into `Proxy::proxy`, dispatching it on behalf of the `real` account.
+pub(crate) fn wrap_call<C, Call>(
+    client: &OnlineClient<C>,
+    real: &C::AccountId,
+    call: &Call,
+) -> Result<Payload<Proxy<C::AccountId>>, subxt::Error>
+where
+    C: Config,
+    Call: TxPayload,
+{
+    let call_data = client.tx().call_data(call)?;
+    Ok(Proxy::new(MultiAddress::Id(real.clone()), EncodedCall(call_data)).build())
+}
+
+/// Returns the error of the proxied call.
+///
+/// The `proxy` extrinsic itself succeeds even if the proxied call fails, the result of
+/// the proxied call is only reported by the `ProxyExecuted` event.
+pub(crate) fn check_executed<C: Config>(
+    client: &OnlineClient<C>,
+    events: &ExtrinsicEvents<C>,
+) -> Result<(), ErrorVariant> {
+    for event in events.iter() {
+        let event = event?;
+        if event.pallet_name() != "Proxy" || event.variant_name() != "ProxyExecuted" {
+            continue
+        }
+        let result = Result::<(), DispatchError>::decode(&mut event.field_bytes())
+            .map_err(|e| anyhow::anyhow!("Failed to decode ProxyExecuted event: {e}"))?;
+        if let Err(err) = result {
+            return Err(ErrorVariant::from_dispatch_error(&err, &client.metadata())?)
+        }
+    }
+    Ok(())
+}
@@ -16,9 +16,10 @@
 
 use super::{
     events::CodeRemoved,
-    submit_extrinsic,
+    submit_call,
     ContractMessageTranscoder,
     ErrorVariant,
+    TrySigner,
 };
 use crate::{
     extrinsic_calls::RemoveCode,
@@ -27,6 +28,7 @@ use crate::{
 
 use anyhow::Result;
 use ink_env::Environment;
+use serde::Serialize;
 use subxt::{
     backend::{
         legacy::LegacyRpcMethods,
@@ -41,7 +43,6 @@ use subxt::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
     Config,
     OnlineClient,
 };
@@ -54,7 +55,7 @@ pub struct RemoveCommandBuilder<C: Config, E: Environment, Signer: Clone> {
 
 impl<C: Config, E: Environment, Signer> RemoveCommandBuilder<C, E, Signer>
 where
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Returns a clean builder for [`RemoveExec`].
     pub fn new(
@@ -77,7 +78,7 @@ where
 impl<C: Config, E: Environment, Signer> RemoveCommandBuilder<C, E, Signer>
 where
     C::Hash: From<[u8; 32]>,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Preprocesses contract artifacts and options for subsequent removal of contract
     /// code.
@@ -91,7 +92,7 @@ where
     /// removal, or an error in case of failure.
     pub async fn done(self) -> Result<RemoveExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
 
         let artifacts_path = artifacts.artifact_path().to_path_buf();
 
@@ -132,10 +133,10 @@ pub struct RemoveExec<C: Config, E: Environment, Signer: Clone> {
 impl<C: Config, E: Environment, Signer> RemoveExec<C, E, Signer>
 where
     C::Hash: IntoVisitor + EncodeAsType,
-    C::AccountId: IntoVisitor,
+    C::AccountId: IntoVisitor + EncodeAsType + Serialize,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Removes a contract code from the blockchain.
     ///
@@ -154,8 +155,7 @@ where
 
         let call = RemoveCode::new(code_hash).build();
 
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        let events = submit_call(&self.client, &self.rpc, &self.opts, &call).await?;
 
         let code_removed =
             events.find_first::<CodeRemoved<C::Hash, C::AccountId, E::Balance>>()?;
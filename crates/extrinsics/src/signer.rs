@@ -0,0 +1,70 @@
+// Copyright (C) Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use anyhow::Result;
+use subxt::{
+    tx::{
+        self,
+        PartialExtrinsic,
+        SubmittableExtrinsic,
+    },
+    Config,
+    OnlineClient,
+};
+
+/// Signs extrinsics on behalf of an account, where signing may fail.
+///
+/// [`tx::Signer::sign`] is infallible, which does not fit signers delegating to e.g. an
+/// external program. Every [`tx::Signer`] implements this trait.
+pub trait TrySigner<C: Config> {
+    /// Returns the account id of the signer.
+    fn account_id(&self) -> C::AccountId;
+
+    /// Returns the address of the signer, which is part of the signed extrinsic.
+    fn address(&self) -> C::Address;
+
+    /// Signs the payload of an extrinsic.
+    fn try_sign(&self, signer_payload: &[u8]) -> Result<C::Signature>;
+}
+
+impl<C: Config, S: tx::Signer<C>> TrySigner<C> for S {
+    fn account_id(&self) -> C::AccountId {
+        tx::Signer::account_id(self)
+    }
+
+    fn address(&self) -> C::Address {
+        tx::Signer::address(self)
+    }
+
+    fn try_sign(&self, signer_payload: &[u8]) -> Result<C::Signature> {
+        Ok(tx::Signer::sign(self, signer_payload))
+    }
+}
+
+/// Signs the partial extrinsic, returning the signing error instead of panicking.
+pub(crate) fn sign_extrinsic<C, Signer>(
+    extrinsic: &PartialExtrinsic<C, OnlineClient<C>>,
+    signer: &Signer,
+) -> Result<SubmittableExtrinsic<C, OnlineClient<C>>, subxt::Error>
+where
+    C: Config,
+    Signer: TrySigner<C>,
+{
+    let signature = signer.try_sign(&extrinsic.signer_payload()).map_err(|e| {
+        subxt::Error::Other(format!("Failed to sign the extrinsic: {e:#}"))
+    })?;
+    Ok(extrinsic.sign_with_address_and_signature(&signer.address(), &signature))
+}
@@ -0,0 +1,96 @@
+// Copyright 2018-2020 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::url_to_string;
+use anyhow::{
+    Context,
+    Result,
+};
+use contract_transcode::AccountId32;
+use std::{
+    fmt::Display,
+    str::FromStr,
+};
+use subxt::{
+    backend::{
+        legacy::LegacyRpcMethods,
+        rpc::RpcClient,
+    },
+    Config,
+};
+use url::Url;
+
+/// The SS58 address format prefix of the generic Substrate chains.
+pub const SUBSTRATE_SS58_PREFIX: u16 = 42;
+
+/// Query the SS58 address format prefix of the chain from the `ss58Format` of the
+/// node's system properties, falling back to the generic Substrate prefix.
+pub async fn query_ss58_prefix<C: Config>(url: &Url) -> Result<u16> {
+    let rpc_cli = RpcClient::from_url(url_to_string(url)).await?;
+    let rpc = LegacyRpcMethods::<C>::new(rpc_cli);
+    fetch_ss58_prefix(&rpc).await
+}
+
+/// Fetch the SS58 address format prefix of the chain like [query_ss58_prefix], with an
+/// existing RPC client.
+pub async fn fetch_ss58_prefix<C: Config>(rpc: &LegacyRpcMethods<C>) -> Result<u16> {
+    let sys_props = rpc.system_properties().await?;
+
+    match sys_props.get("ss58Format") {
+        Some(ss58_format) => {
+            ss58_format
+                .as_u64()
+                .and_then(|prefix| u16::try_from(prefix).ok())
+                .context("error converting ss58Format to u16")
+        }
+        None => Ok(SUBSTRATE_SS58_PREFIX),
+    }
+}
+
+/// Format an account displayed as an SS58 address with the given address format
+/// prefix. Accounts which are not displayed as an SS58 address, e.g. 20 byte accounts,
+/// are formatted as they are displayed.
+pub fn format_ss58<AccountId: Display>(
+    account: &AccountId,
+    prefix: Option<u16>,
+) -> String {
+    let address = account.to_string();
+    match (prefix, AccountId32::from_str(&address)) {
+        (Some(prefix), Ok(account_id)) => account_id.to_ss58check_with_prefix(prefix),
+        _ => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use subxt::utils::AccountId32 as SubxtAccountId32;
+
+    #[test]
+    fn format_ss58_with_prefix() {
+        let alice = SubxtAccountId32::from_str(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+        )
+        .unwrap();
+
+        assert_eq!(
+            format_ss58(&alice, Some(0)),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(format_ss58(&alice, None), alice.to_string());
+        assert_eq!(format_ss58(&"0x1234", Some(0)), "0x1234");
+    }
+}
@@ -18,8 +18,9 @@ use super::{
     events::CodeStored,
     pallet_contracts_primitives::CodeUploadResult,
     state_call,
-    submit_extrinsic,
+    submit_call,
     ErrorVariant,
+    TrySigner,
     WasmCode,
 };
 use crate::{
@@ -31,6 +32,7 @@ use anyhow::Result;
 use contract_transcode::ContractMessageTranscoder;
 use ink_env::Environment;
 use scale::Encode;
+use serde::Serialize;
 use subxt::{
     backend::{
         legacy::LegacyRpcMethods,
@@ -45,7 +47,6 @@ use subxt::{
         scale_decode::IntoVisitor,
         scale_encode::EncodeAsType,
     },
-    tx,
     Config,
     OnlineClient,
 };
@@ -57,7 +58,7 @@ pub struct UploadCommandBuilder<C: Config, E: Environment, Signer: Clone> {
 
 impl<C: Config, E: Environment, Signer> UploadCommandBuilder<C, E, Signer>
 where
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Returns a clean builder for [`UploadExec`].
     pub fn new(
@@ -77,7 +78,7 @@ where
     /// execution.
     pub async fn done(self) -> Result<UploadExec<C, E, Signer>> {
         let artifacts = self.extrinsic_opts.contract_artifacts()?;
-        let transcoder = artifacts.contract_transcoder()?;
+        let transcoder = self.extrinsic_opts.contract_transcoder(&artifacts)?;
 
         let artifacts_path = artifacts.artifact_path().to_path_buf();
         let code = artifacts.code.ok_or_else(|| {
@@ -114,11 +115,11 @@ pub struct UploadExec<C: Config, E: Environment, Signer: Clone> {
 impl<C: Config, E: Environment, Signer> UploadExec<C, E, Signer>
 where
     C::Hash: IntoVisitor,
-    C::AccountId: IntoVisitor,
+    C::AccountId: IntoVisitor + EncodeAsType + Serialize,
     E::Balance: EncodeAsType,
     <C::ExtrinsicParams as ExtrinsicParams<C>>::Params:
         From<<DefaultExtrinsicParams<C> as ExtrinsicParams<C>>::Params>,
-    Signer: tx::Signer<C> + Clone,
+    Signer: TrySigner<C> + Clone,
 {
     /// Uploads contract code to a specified URL using a JSON-RPC call.
     ///
@@ -129,7 +130,7 @@ where
     pub async fn upload_code_rpc(&self) -> Result<CodeUploadResult<C::Hash, E::Balance>> {
         let storage_deposit_limit = self.opts.storage_deposit_limit();
         let call_request = CodeUploadRequest {
-            origin: self.opts.signer().account_id(),
+            origin: self.opts.origin(),
             code: self.code.0.clone(),
             storage_deposit_limit,
             determinism: Determinism::Enforced,
@@ -153,8 +154,7 @@ where
         )
         .build();
 
-        let events =
-            submit_extrinsic(&self.client, &self.rpc, &call, self.opts.signer()).await?;
+        let events = submit_call(&self.client, &self.rpc, &self.opts, &call).await?;
 
         let code_stored = events.find_first::<CodeStored<C::Hash>>()?;
         Ok(UploadResult {
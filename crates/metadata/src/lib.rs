@@ -332,7 +332,7 @@ impl FromStr for SourceLanguage {
 }
 
 /// The language in which the smart contract is written.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum Language {
     Ink,
     Solidity,
@@ -99,9 +99,26 @@ impl AccountId32 {
         // substrate prefix (since we have no way to otherwise pick one). It
         // doesn't really matter, since when it's deserialized back in
         // system_accountNextIndex, we ignore this (so long as it's valid).
-        const SUBSTRATE_SS58_PREFIX: u8 = 42;
-        // prefix <= 63 just take up one byte at the start:
-        let mut v = vec![SUBSTRATE_SS58_PREFIX];
+        const SUBSTRATE_SS58_PREFIX: u16 = 42;
+        self.to_ss58check_with_prefix(SUBSTRATE_SS58_PREFIX)
+    }
+
+    /// Return the ss58-check string for this key with the given address format prefix.
+    /// Adapted from `sp_core::crypto`.
+    pub fn to_ss58check_with_prefix(&self, prefix: u16) -> String {
+        // we mask out the upper two bits of the prefix.
+        let ident = prefix & 0b0011_1111_1111_1111;
+        let mut v = match ident {
+            // prefix <= 63 just take up one byte at the start:
+            0..=63 => vec![ident as u8],
+            // prefix <= 16_383 take up two bytes at the start:
+            _ => {
+                let first = ((ident & 0b0000_0000_1111_1100) as u8) >> 2;
+                let second =
+                    ((ident >> 8) as u8) | ((ident & 0b0000_0000_0000_0011) as u8) << 6;
+                vec![first | 0b0100_0000, second]
+            }
+        };
         // then push the account ID bytes.
         v.extend(self.0);
         // then push a 2 byte checksum of what we have so far.
@@ -116,6 +133,12 @@ impl AccountId32 {
     // we also implement the logic needed to decode an AccountId32 from an SS58
     // encoded string. This is exposed via a `FromStr` impl.
     fn from_ss58check(s: &str) -> Result<Self, FromSs58Error> {
+        Self::from_ss58check_with_prefix(s).map(|(account_id, _)| account_id)
+    }
+
+    /// Decode an AccountId32 from an SS58 encoded string, returning it along with the
+    /// address format prefix of the string.
+    pub fn from_ss58check_with_prefix(s: &str) -> Result<(Self, u16), FromSs58Error> {
         const CHECKSUM_LEN: usize = 2;
         let body_len = 32;
 
@@ -124,9 +147,15 @@ impl AccountId32 {
         if data.len() < 2 {
             return Err(FromSs58Error::BadLength)
         }
-        let prefix_len = match data[0] {
-            0..=63 => 1,
-            64..=127 => 2,
+        let (prefix_len, prefix) = match data[0] {
+            0..=63 => (1, data[0] as u16),
+            64..=127 => {
+                // weird bit manipulation owing to the combination of LE encoding and
+                // missing two bits from the left.
+                let lower = (data[0] << 2) | (data[1] >> 6);
+                let upper = data[1] & 0b0011_1111;
+                (2, (lower as u16) | ((upper as u16) << 8))
+            }
             _ => return Err(FromSs58Error::InvalidPrefix),
         };
         if data.len() != prefix_len + body_len + CHECKSUM_LEN {
@@ -143,7 +172,7 @@ impl AccountId32 {
         let result = data[prefix_len..body_len + prefix_len]
             .try_into()
             .map_err(|_| FromSs58Error::BadLength)?;
-        Ok(AccountId32(result))
+        Ok((AccountId32(result), prefix))
     }
 }
 
@@ -243,4 +272,23 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn ss58_with_prefix_is_compatible_with_substrate_impl() {
+        let substrate_account = AccountKeyring::Alice.to_account_id();
+        let local_account = AccountId32(substrate_account.clone().into());
+
+        for prefix in [0, 2, 63, 64, 1284, 16_383] {
+            let format = sp_core::crypto::Ss58AddressFormat::custom(prefix);
+            let substrate_ss58 = substrate_account.to_ss58check_with_version(format);
+            assert_eq!(
+                substrate_ss58,
+                local_account.to_ss58check_with_prefix(prefix)
+            );
+            assert_eq!(
+                AccountId32::from_ss58check_with_prefix(&substrate_ss58).unwrap(),
+                (local_account.clone(), prefix)
+            );
+        }
+    }
 }
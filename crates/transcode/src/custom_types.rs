@@ -0,0 +1,589 @@
+// Copyright 2018-2020 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Custom transcoders for the types of chains with custom environments, configured by
+//! mapping the path of a type to one of the built-in codecs:
+//!
+//! ```toml
+//! [types."ink_primitives::types::AccountId"]
+//! codec = "ss58"
+//! prefix = 0
+//!
+//! [types."my_contract::Price"]
+//! codec = "fixed-point"
+//! decimals = 6
+//!
+//! [types."my_contract::Status"]
+//! codec = "enum"
+//! names = { 0 = "Pending", 1 = "Active" }
+//! ```
+
+use super::{
+    env_types::{
+        CustomTypeDecoder,
+        CustomTypeEncoder,
+        PathKey,
+    },
+    render::decimal,
+    scon::Value,
+    AccountId32,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use serde::Deserialize;
+use sha3::{
+    Digest,
+    Keccak256,
+};
+use std::{
+    collections::BTreeMap,
+    str::FromStr,
+};
+
+/// The custom transcoders of types, by the path of the type e.g.
+/// `sp_core::crypto::AccountId32`.
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomTypes {
+    #[serde(default)]
+    types: BTreeMap<String, Codec>,
+}
+
+/// A built-in codec for a custom type.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "codec", rename_all = "kebab-case", deny_unknown_fields)]
+pub enum Codec {
+    /// A 32 byte account id as an SS58 address with the given address format prefix.
+    Ss58 { prefix: u16 },
+    /// A 20 byte address as an EIP-55 checksummed hex string.
+    H160,
+    /// An integer as a decimal number with the given number of decimal places.
+    FixedPoint { decimals: u32 },
+    /// An integer as the name it is given in the table.
+    Enum { names: BTreeMap<String, String> },
+}
+
+impl CustomTypes {
+    /// Return `true` if no custom types are configured.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+
+    /// Construct the custom transcoders for the types in the registry with one of the
+    /// configured paths.
+    ///
+    /// # Errors
+    ///
+    /// - If the codec configured for a type is not applicable to it.
+    pub(crate) fn transcoders(
+        &self,
+        registry: &PortableRegistry,
+    ) -> Result<Vec<(u32, CustomTypeTranscoder)>> {
+        let mut transcoders = Vec::new();
+        for (path, codec) in &self.types {
+            let path_key = PathKey::from(path.as_str());
+            let mut matched = false;
+            for ty in &registry.types {
+                if PathKey::from(&ty.ty.path) != path_key {
+                    continue
+                }
+                let transcoder = CustomTypeTranscoder::new(registry, ty.id, codec)
+                    .with_context(|| format!("Invalid codec for custom type `{path}`"))?;
+                transcoders.push((ty.id, transcoder));
+                matched = true;
+            }
+            if !matched {
+                // a configuration may be shared between contracts which don't all use
+                // every type.
+                tracing::debug!("No matching type in registry for path {:?}.", path);
+            }
+        }
+        Ok(transcoders)
+    }
+}
+
+/// A [`Codec`] resolved for the SCALE encoding of a type in the registry.
+#[derive(Clone)]
+pub(crate) enum CustomTypeTranscoder {
+    Ss58 {
+        prefix: u16,
+    },
+    H160,
+    FixedPoint {
+        int: Int,
+        decimals: u32,
+    },
+    Enum {
+        int: Int,
+        names: BTreeMap<i128, String>,
+    },
+}
+
+impl CustomTypeTranscoder {
+    fn new(registry: &PortableRegistry, type_id: u32, codec: &Codec) -> Result<Self> {
+        Ok(match codec {
+            Codec::Ss58 { prefix } => {
+                byte_array(registry, type_id, 32)?;
+                anyhow::ensure!(
+                    *prefix < 16_384,
+                    "SS58 prefix {prefix} is out of range, expected at most 16383"
+                );
+                Self::Ss58 { prefix: *prefix }
+            }
+            Codec::H160 => {
+                byte_array(registry, type_id, 20)?;
+                Self::H160
+            }
+            Codec::FixedPoint { decimals } => {
+                anyhow::ensure!(
+                    *decimals <= 38,
+                    "{decimals} decimal places exceed the 38 digits of a `u128`"
+                );
+                Self::FixedPoint {
+                    int: Int::resolve(registry, type_id)?,
+                    decimals: *decimals,
+                }
+            }
+            Codec::Enum { names } => {
+                let names = names
+                    .iter()
+                    .map(|(value, name)| {
+                        let value = value.parse::<i128>().with_context(|| {
+                            format!("Expected an integer for the value of `{name}`")
+                        })?;
+                        Ok((value, name.clone()))
+                    })
+                    .collect::<Result<_>>()?;
+                Self::Enum {
+                    int: Int::resolve(registry, type_id)?,
+                    names,
+                }
+            }
+        })
+    }
+}
+
+impl CustomTypeEncoder for CustomTypeTranscoder {
+    fn encode_value(&self, value: &Value) -> Result<Vec<u8>> {
+        match self {
+            Self::Ss58 { .. } => {
+                let account_id = match value {
+                    Value::Literal(s) | Value::String(s) => {
+                        AccountId32::from_str(s).map_err(|e| {
+                            anyhow::anyhow!("Error parsing SS58 address `{s}`: {e}")
+                        })?
+                    }
+                    Value::Hex(hex) => {
+                        AccountId32::try_from(hex.bytes()).map_err(|_| {
+                            anyhow::anyhow!("Expected 32 bytes for an SS58 address")
+                        })?
+                    }
+                    _ => anyhow::bail!("Expected a string or hex for an SS58 address"),
+                };
+                Ok(account_id.encode())
+            }
+            Self::H160 => {
+                let address = match value {
+                    Value::Hex(hex) => hex.as_str().to_string(),
+                    Value::Literal(s) | Value::String(s) => {
+                        s.strip_prefix("0x").unwrap_or(s).to_string()
+                    }
+                    _ => anyhow::bail!("Expected a hex string for an H160 address"),
+                };
+                let bytes = hex::decode(&address)
+                    .with_context(|| format!("Error parsing H160 address `{address}`"))?;
+                anyhow::ensure!(
+                    bytes.len() == 20,
+                    "Expected 20 bytes for an H160 address"
+                );
+                let mixed_case = address.chars().any(|c| c.is_ascii_lowercase())
+                    && address.chars().any(|c| c.is_ascii_uppercase());
+                if mixed_case && checksummed(&bytes)[2..] != address {
+                    anyhow::bail!("Invalid checksum of H160 address `0x{address}`")
+                }
+                Ok(bytes)
+            }
+            Self::FixedPoint { int, decimals } => {
+                let n = match value {
+                    Value::Literal(s) | Value::String(s) => parse_decimal(s, *decimals)?,
+                    Value::UInt(n) => {
+                        10i128
+                            .checked_pow(*decimals)
+                            .zip(i128::try_from(*n).ok())
+                            .and_then(|(unit, n)| n.checked_mul(unit))
+                            .context("Number out of range")?
+                    }
+                    Value::Int(n) => {
+                        10i128
+                            .checked_pow(*decimals)
+                            .and_then(|unit| n.checked_mul(unit))
+                            .context("Number out of range")?
+                    }
+                    _ => anyhow::bail!("Expected a decimal number as a string"),
+                };
+                int.encode(n)
+            }
+            Self::Enum { int, names } => {
+                let name = match value {
+                    Value::Literal(s) | Value::String(s) => Some(s.clone()),
+                    Value::Tuple(tuple) if tuple.values().next().is_none() => {
+                        tuple.ident()
+                    }
+                    Value::UInt(n) => {
+                        return int.encode(i128::try_from(*n).context("Out of range")?)
+                    }
+                    Value::Int(n) => return int.encode(*n),
+                    _ => None,
+                };
+                let name = name.context("Expected a name or an integer")?;
+                let value = names
+                    .iter()
+                    .find_map(|(value, n)| (*n == name).then_some(*value))
+                    .with_context(|| {
+                        let expected = names.values().cloned().collect::<Vec<_>>();
+                        format!("Unknown name `{name}`, expected one of {expected:?}")
+                    })?;
+                int.encode(value)
+            }
+        }
+    }
+}
+
+impl CustomTypeDecoder for CustomTypeTranscoder {
+    fn decode_value(&self, input: &mut &[u8]) -> Result<Value> {
+        match self {
+            Self::Ss58 { prefix } => {
+                let account_id = AccountId32::decode(input)?;
+                Ok(Value::Literal(account_id.to_ss58check_with_prefix(*prefix)))
+            }
+            Self::H160 => {
+                let bytes = <[u8; 20]>::decode(input)?;
+                Ok(Value::Literal(checksummed(&bytes)))
+            }
+            Self::FixedPoint { int, decimals } => {
+                let n = int.decode(input)?;
+                let decimal = decimal(n.unsigned_abs(), *decimals);
+                Ok(Value::Literal(
+                    if n < 0 {
+                        format!("-{decimal}")
+                    } else {
+                        decimal
+                    },
+                ))
+            }
+            Self::Enum { int, names } => {
+                let n = int.decode(input)?;
+                Ok(match names.get(&n) {
+                    Some(name) => Value::Literal(name.clone()),
+                    None if int.signed => Value::Int(n),
+                    None => Value::UInt(n as u128),
+                })
+            }
+        }
+    }
+}
+
+/// The SCALE encoding of a primitive integer type, or of a composite wrapping one.
+///
+/// The values are handled as `i128`, so a `u128` greater than `i128::MAX` is rejected.
+#[derive(Clone, Copy)]
+pub(crate) struct Int {
+    bytes: usize,
+    signed: bool,
+}
+
+impl Int {
+    fn resolve(registry: &PortableRegistry, type_id: u32) -> Result<Self> {
+        let (bytes, signed) = match &inner_type(registry, type_id)?.type_def {
+            TypeDef::Primitive(TypeDefPrimitive::U8) => (1, false),
+            TypeDef::Primitive(TypeDefPrimitive::U16) => (2, false),
+            TypeDef::Primitive(TypeDefPrimitive::U32) => (4, false),
+            TypeDef::Primitive(TypeDefPrimitive::U64) => (8, false),
+            TypeDef::Primitive(TypeDefPrimitive::U128) => (16, false),
+            TypeDef::Primitive(TypeDefPrimitive::I8) => (1, true),
+            TypeDef::Primitive(TypeDefPrimitive::I16) => (2, true),
+            TypeDef::Primitive(TypeDefPrimitive::I32) => (4, true),
+            TypeDef::Primitive(TypeDefPrimitive::I64) => (8, true),
+            TypeDef::Primitive(TypeDefPrimitive::I128) => (16, true),
+            _ => anyhow::bail!("Expected an integer type"),
+        };
+        Ok(Self { bytes, signed })
+    }
+
+    fn encode(&self, n: i128) -> Result<Vec<u8>> {
+        let bits = 8 * self.bytes as u32;
+        let (min, max) = match (self.signed, bits) {
+            (true, _) => (i128::MIN >> (128 - bits), i128::MAX >> (128 - bits)),
+            (false, 128) => (0, i128::MAX),
+            (false, _) => (0, (1 << bits) - 1),
+        };
+        anyhow::ensure!(
+            (min..=max).contains(&n),
+            "{n} is out of range of an {}{bits} integer",
+            if self.signed { "i" } else { "u" }
+        );
+        Ok(n.to_le_bytes()[..self.bytes].to_vec())
+    }
+
+    fn decode(&self, input: &mut &[u8]) -> Result<i128> {
+        anyhow::ensure!(input.len() >= self.bytes, "Not enough data for an integer");
+        let (bytes, rest) = input.split_at(self.bytes);
+        *input = rest;
+        let fill = if self.signed && bytes[self.bytes - 1] & 0x80 != 0 {
+            0xff
+        } else {
+            0
+        };
+        let mut le = [fill; 16];
+        le[..self.bytes].copy_from_slice(bytes);
+        let n = i128::from_le_bytes(le);
+        anyhow::ensure!(
+            self.signed || n >= 0,
+            "Unsigned integer out of range of an `i128`"
+        );
+        Ok(n)
+    }
+}
+
+/// Resolve the type with the given id, unwrapping composites with a single field.
+fn inner_type(registry: &PortableRegistry, type_id: u32) -> Result<&Type<PortableForm>> {
+    let ty = registry
+        .resolve(type_id)
+        .with_context(|| format!("Failed to resolve type with id `{type_id}`"))?;
+    match &ty.type_def {
+        TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+            inner_type(registry, composite.fields[0].ty.id)
+        }
+        _ => Ok(ty),
+    }
+}
+
+/// Check that the type with the given id is encoded as `[u8; len]`.
+fn byte_array(registry: &PortableRegistry, type_id: u32, len: u32) -> Result<()> {
+    if let TypeDef::Array(array) = &inner_type(registry, type_id)?.type_def {
+        let elem = inner_type(registry, array.type_param.id)?;
+        if array.len == len
+            && matches!(elem.type_def, TypeDef::Primitive(TypeDefPrimitive::U8))
+        {
+            return Ok(())
+        }
+    }
+    anyhow::bail!("Expected a type encoded as `[u8; {len}]`")
+}
+
+/// Parse a decimal number, e.g. `-12.5`, as an integer of `10^-decimals` units.
+fn parse_decimal(s: &str, decimals: u32) -> Result<i128> {
+    let invalid = || anyhow::anyhow!("Invalid decimal number `{s}`");
+    let (negative, digits) = match s.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, s),
+    };
+    let digits = digits.replace('_', "");
+    let (integral, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if integral.is_empty() || !is_digits(integral) || !is_digits(fraction) {
+        return Err(invalid())
+    }
+    anyhow::ensure!(
+        fraction.len() <= decimals as usize,
+        "`{s}` has more than {decimals} decimal places"
+    );
+    let units = format!("{integral}{fraction:0<width$}", width = decimals as usize);
+    let n = units.parse::<i128>().map_err(|_| invalid())?;
+    Ok(if negative { -n } else { n })
+}
+
+/// Format a 20 byte address as an EIP-55 checksummed hex string.
+fn checksummed(address: &[u8]) -> String {
+    let hex = hex::encode(address);
+    let hash = Keccak256::digest(hex.as_bytes());
+    let checksummed = hex
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 {
+                hash[i / 2] >> 4
+            } else {
+                hash[i / 2] & 0x0f
+            };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect::<String>();
+    format!("0x{checksummed}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        scon,
+        TranscoderBuilder,
+    };
+    use scale_info::{
+        MetaType,
+        Registry,
+        TypeInfo,
+    };
+
+    #[derive(Encode, TypeInfo)]
+    struct Account {
+        owner: AccountId32,
+        address: Address,
+        price: Price,
+        status: Status,
+    }
+
+    #[derive(Encode, TypeInfo)]
+    struct Address([u8; 20]);
+
+    #[derive(Encode, TypeInfo)]
+    struct Price(i64);
+
+    #[derive(Encode, TypeInfo)]
+    struct Status(u8);
+
+    fn custom_types() -> CustomTypes {
+        let path = |ident| format!("{}::{ident}", module_path!());
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "sp_core::crypto::AccountId32": { "codec": "ss58", "prefix": 0 },
+                path("Address"): { "codec": "h160" },
+                path("Price"): { "codec": "fixed-point", "decimals": 4 },
+                path("Status"): {
+                    "codec": "enum",
+                    "names": { "0": "Pending", "1": "Active" }
+                },
+            }
+        }))
+        .unwrap()
+    }
+
+    fn registry() -> (PortableRegistry, u32) {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&MetaType::new::<Account>());
+        (registry.into(), type_id.id)
+    }
+
+    #[test]
+    fn transcode_custom_types() -> Result<()> {
+        let (registry, type_id) = registry();
+        let transcoder = TranscoderBuilder::new(&registry)
+            .with_default_custom_type_transcoders()
+            .with_custom_types(&registry, &custom_types())?
+            .done();
+        let account = Account {
+            owner: AccountId32([1; 32]),
+            address: Address(
+                hex::decode("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").unwrap()[..]
+                    .try_into()
+                    .unwrap(),
+            ),
+            price: Price(-12_500),
+            status: Status(1),
+        };
+
+        let decoded =
+            transcoder.decode(&registry, type_id, &mut &account.encode()[..])?;
+        let expected = format!(
+            "Account {{ owner: {}, address: {}, price: {}, status: {} }}",
+            AccountId32([1; 32]).to_ss58check_with_prefix(0),
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "-1.25",
+            "Active",
+        );
+        assert_eq!(decoded.to_string(), expected);
+
+        let input = format!(
+            r#"Account {{
+                owner: {},
+                address: 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed,
+                price: "-1.25",
+                status: Active
+            }}"#,
+            AccountId32([1; 32]).to_ss58check_with_prefix(0),
+        );
+        let mut encoded = Vec::new();
+        let value = scon::parse_value(&input)?;
+        transcoder.encode(&registry, type_id, &value, &mut encoded)?;
+        assert_eq!(encoded, account.encode());
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_custom_values() -> Result<()> {
+        let (registry, type_id) = registry();
+        let transcoder = TranscoderBuilder::new(&registry)
+            .with_custom_types(&registry, &custom_types())?
+            .done();
+        let encode = |address: &str, price: &str, status: &str| {
+            let input = format!(
+                r#"Account {{
+                    owner: 0x{},
+                    address: {address},
+                    price: {price},
+                    status: {status}
+                }}"#,
+                hex::encode([1; 32])
+            );
+            let value = scon::parse_value(&input).unwrap();
+            transcoder.encode(&registry, type_id, &value, &mut Vec::new())
+        };
+        let address = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+        assert!(encode(address, r#""1.25""#, "Pending").is_ok());
+        // invalid checksum
+        assert!(encode(&address.replace('a', "A"), r#""1.25""#, "Pending").is_err());
+        // repeated prefix
+        assert!(encode(&format!("0x{address}"), r#""1.25""#, "Pending").is_err());
+        // too many decimal places
+        assert!(encode(address, r#""1.23456""#, "Pending").is_err());
+        // unknown name
+        assert!(encode(address, r#""1.25""#, "Closed").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn reject_inapplicable_codec() {
+        let (registry, _) = registry();
+        let custom_types: CustomTypes = serde_json::from_value(serde_json::json!({
+            "types": {
+                format!("{}::Price", module_path!()): { "codec": "h160" },
+            }
+        }))
+        .unwrap();
+
+        assert!(TranscoderBuilder::new(&registry)
+            .with_custom_types(&registry, &custom_types)
+            .is_err());
+    }
+}
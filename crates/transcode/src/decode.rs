@@ -20,13 +20,16 @@ use super::{
         Map,
         Tuple,
         Value,
+        I256,
     },
+    util,
     CompositeTypeFields,
 };
 use anyhow::{
     Context,
     Result,
 };
+use primitive_types::U256;
 use scale::{
     Compact,
     Decode,
@@ -41,6 +44,7 @@ use scale_info::{
     PortableRegistry,
     Type,
     TypeDef,
+    TypeDefBitSequence,
     TypeDefCompact,
     TypeDefPrimitive,
     TypeDefVariant,
@@ -131,9 +135,7 @@ impl<'a> Decoder<'a> {
             }
             TypeDef::Primitive(primitive) => self.decode_primitive(primitive, input),
             TypeDef::Compact(compact) => self.decode_compact(compact, input),
-            TypeDef::BitSequence(_) => {
-                Err(anyhow::anyhow!("bitvec decoding not yet supported"))
-            }
+            TypeDef::BitSequence(bit_seq) => self.decode_bit_sequence(bit_seq, input),
         }
         .context(format!("Error decoding type {}: {}", id, ty.path))
     }
@@ -226,20 +228,34 @@ impl<'a> Decoder<'a> {
             TypeDefPrimitive::U32 => decode_uint::<u32>(input),
             TypeDefPrimitive::U64 => decode_uint::<u64>(input),
             TypeDefPrimitive::U128 => decode_uint::<u128>(input),
-            TypeDefPrimitive::U256 => {
-                Err(anyhow::anyhow!("U256 currently not supported"))
-            }
+            TypeDefPrimitive::U256 => Ok(Value::BigUInt(U256::decode(input)?)),
             TypeDefPrimitive::I8 => decode_int::<i8>(input),
             TypeDefPrimitive::I16 => decode_int::<i16>(input),
             TypeDefPrimitive::I32 => decode_int::<i32>(input),
             TypeDefPrimitive::I64 => decode_int::<i64>(input),
             TypeDefPrimitive::I128 => decode_int::<i128>(input),
             TypeDefPrimitive::I256 => {
-                Err(anyhow::anyhow!("I256 currently not supported"))
+                let bytes = <[u8; 32]>::decode(input)?;
+                Ok(Value::BigInt(I256::from_le_bytes(bytes)))
             }
         }
     }
 
+    fn decode_bit_sequence(
+        &self,
+        bit_seq: &TypeDefBitSequence<PortableForm>,
+        input: &mut &[u8],
+    ) -> Result<Value> {
+        let format = util::bit_sequence_format(bit_seq, self.registry)?;
+        let decoder = scale_bits::scale::decode_using_format_from(input, format)?;
+        let encoded_size = decoder.encoded_size();
+        let bits = decoder.collect::<Result<Vec<_>, _>>()?;
+        *input = input
+            .get(encoded_size..)
+            .ok_or_else(|| anyhow::anyhow!("Not enough data to decode a bit sequence"))?;
+        Ok(Value::Bits(bits.into()))
+    }
+
     fn decode_compact(
         &self,
         compact: &TypeDefCompact<PortableForm>,
@@ -16,11 +16,17 @@
 
 use super::{
     env_types::EnvTypesTranscoder,
-    scon::Value,
+    scon::{
+        parse_u256,
+        Value,
+        I256,
+    },
+    util,
     CompositeTypeFields,
 };
 use anyhow::Result;
 use itertools::Itertools;
+use primitive_types::U256;
 use scale::{
     Compact,
     Encode,
@@ -34,6 +40,7 @@ use scale_info::{
     Field,
     PortableRegistry,
     TypeDef,
+    TypeDefBitSequence,
     TypeDefCompact,
     TypeDefPrimitive,
     TypeDefTuple,
@@ -98,8 +105,8 @@ impl<'a> Encoder<'a> {
                     self.encode_primitive(primitive, value, output)
                 }
                 TypeDef::Compact(compact) => self.encode_compact(compact, value, output),
-                TypeDef::BitSequence(_) => {
-                    Err(anyhow::anyhow!("bitvec encoding not yet supported"))
+                TypeDef::BitSequence(bit_seq) => {
+                    self.encode_bit_sequence(bit_seq, value, output)
                 }
             }?;
         }
@@ -295,7 +302,19 @@ impl<'a> Encoder<'a> {
             TypeDefPrimitive::U64 => encode_uint::<u64, O>(value, "u64", output),
             TypeDefPrimitive::U128 => encode_uint::<u128, O>(value, "u128", output),
             TypeDefPrimitive::U256 => {
-                Err(anyhow::anyhow!("U256 currently not supported"))
+                let uint = match value {
+                    Value::UInt(uint) => U256::from(*uint),
+                    Value::BigUInt(uint) => *uint,
+                    Value::String(s) => parse_u256(&s.replace(&['_', ','][..], ""))?,
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "Expected a u256 or a String value, got {}",
+                            value
+                        ))
+                    }
+                };
+                uint.encode_to(output);
+                Ok(())
             }
             TypeDefPrimitive::I8 => encode_int::<i8, O>(value, "i8", output),
             TypeDefPrimitive::I16 => encode_int::<i16, O>(value, "i16", output),
@@ -303,11 +322,63 @@ impl<'a> Encoder<'a> {
             TypeDefPrimitive::I64 => encode_int::<i64, O>(value, "i64", output),
             TypeDefPrimitive::I128 => encode_int::<i128, O>(value, "i128", output),
             TypeDefPrimitive::I256 => {
-                Err(anyhow::anyhow!("I256 currently not supported"))
+                let int = match value {
+                    Value::Int(int) => I256::from(*int),
+                    Value::UInt(uint) => I256::from(*uint),
+                    Value::BigInt(int) => *int,
+                    Value::BigUInt(uint) => I256::try_from(*uint)?,
+                    Value::String(s) => I256::from_str(&s.replace(&['_', ','][..], ""))?,
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "Expected a i256 or a String value, got {}",
+                            value
+                        ))
+                    }
+                };
+                output.write(&int.to_le_bytes());
+                Ok(())
             }
         }
     }
 
+    fn encode_bit_sequence<O: Output + Debug>(
+        &self,
+        bit_seq: &TypeDefBitSequence<PortableForm>,
+        value: &Value,
+        output: &mut O,
+    ) -> Result<()> {
+        let bits = match value {
+            Value::Bits(bits) => bits.as_slice().to_vec(),
+            Value::Seq(seq) => {
+                seq.elems()
+                    .iter()
+                    .map(|elem| {
+                        match elem {
+                            Value::Bool(bit) => Ok(*bit),
+                            elem => {
+                                Err(anyhow::anyhow!(
+                                    "Expected a bool in the bit sequence, got {}",
+                                    elem
+                                ))
+                            }
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?
+            }
+            value => {
+                return Err(anyhow::anyhow!(
+                    "{} cannot be encoded as a bit sequence, expected e.g. `0b1011`",
+                    value
+                ))
+            }
+        };
+        let format = util::bit_sequence_format(bit_seq, self.registry)?;
+        let mut encoded = Vec::new();
+        scale_bits::scale::encode_using_format_to(bits.into_iter(), format, &mut encoded);
+        output.write(&encoded);
+        Ok(())
+    }
+
     fn encode_compact<O: Output + Debug>(
         &self,
         compact: &TypeDefCompact<PortableForm>,
@@ -127,6 +127,13 @@ impl From<&Path<PortableForm>> for PathKey {
     }
 }
 
+impl From<&str> for PathKey {
+    /// Parse a path with `::` separated segments, e.g. `sp_core::crypto::AccountId32`.
+    fn from(path: &str) -> Self {
+        PathKey(path.split("::").map(ToString::to_string).collect())
+    }
+}
+
 pub type TypesByPath = HashMap<PathKey, u32>;
 
 /// Implement this trait to define custom encoding for a type in a `scale-info` type
@@ -0,0 +1,577 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Conversion between JSON and [`Value`], guided by the type in the registry.
+//!
+//! - composites with named fields are objects, with unnamed fields arrays, and a
+//!   composite with a single unnamed field is represented by the field itself.
+//! - enum variants are objects with the variant name as the only key, e.g. `{ "Some": 42
+//!   }`. A variant without fields may also be written as its name.
+//! - byte arrays and sequences are hex strings, e.g. `"0xdeadbeef"`.
+//! - integers of up to 32 bits are numbers, larger integers are decimal strings. Both
+//!   numbers and strings are accepted as input.
+//! - bit sequences are arrays of booleans.
+
+use super::{
+    scon::{
+        Bits,
+        Hex,
+        Map,
+        Seq,
+        Tuple,
+        Value,
+    },
+    CompositeTypeFields,
+};
+use anyhow::{
+    Context,
+    Result,
+};
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+    Variant,
+};
+use serde_json::Value as Json;
+use std::str::FromStr;
+
+/// Convert a JSON value to a [`Value`] which can be encoded as the type with the given
+/// id.
+pub fn json_to_value(
+    registry: &PortableRegistry,
+    type_id: u32,
+    json: &Json,
+) -> Result<Value> {
+    let ty = resolve(registry, type_id)?;
+    match (&ty.type_def, json) {
+        (TypeDef::Composite(composite), Json::String(s)) => {
+            // e.g. an `AccountId` or a `Hash`, encoded by a custom type transcoder or via
+            // the single field of the composite.
+            match &composite.fields[..] {
+                [field] => {
+                    json_to_value(registry, field.ty.id, json)
+                        .or_else(|_| string_to_value(s))
+                }
+                _ => string_to_value(s),
+            }
+        }
+        (TypeDef::Composite(composite), json) => {
+            fields_to_value(registry, ident(ty), &composite.fields, json)
+        }
+        (TypeDef::Variant(variant), Json::String(name)) => {
+            let variant = find_variant(&variant.variants, name)?;
+            fields_to_value(registry, Some(&variant.name), &variant.fields, &Json::Null)
+        }
+        (TypeDef::Variant(variant), Json::Object(object)) if object.len() == 1 => {
+            let (name, fields) = object.iter().next().expect("object has one entry");
+            let variant = find_variant(&variant.variants, name)?;
+            fields_to_value(registry, Some(&variant.name), &variant.fields, fields)
+                .context(format!("Invalid fields of variant `{name}`"))
+        }
+        (TypeDef::Variant(_), json) => {
+            Err(anyhow::anyhow!(
+                "Expected an object with the variant name as the only key, got {json}"
+            ))
+        }
+        (TypeDef::Array(array), json) => {
+            seq_to_value(registry, array.type_param.id, json)
+        }
+        (TypeDef::Sequence(seq), json) => seq_to_value(registry, seq.type_param.id, json),
+        (TypeDef::Tuple(tuple), Json::Null) if tuple.fields.is_empty() => {
+            Ok(Value::Tuple(Tuple::new(None, Vec::new())))
+        }
+        (TypeDef::Tuple(tuple), Json::Array(elems)) => {
+            anyhow::ensure!(
+                elems.len() == tuple.fields.len(),
+                "Expected a tuple of {} elements, got {}",
+                tuple.fields.len(),
+                elems.len()
+            );
+            let values = tuple
+                .fields
+                .iter()
+                .zip(elems)
+                .map(|(field, elem)| json_to_value(registry, field.id, elem))
+                .collect::<Result<_>>()?;
+            Ok(Value::Tuple(Tuple::new(None, values)))
+        }
+        (TypeDef::Tuple(_), json) => {
+            Err(anyhow::anyhow!("Expected an array for a tuple, got {json}"))
+        }
+        (TypeDef::Primitive(primitive), json) => primitive_to_value(primitive, json),
+        (TypeDef::Compact(compact), json) => {
+            json_to_value(registry, compact.type_param.id, json)
+        }
+        (TypeDef::BitSequence(_), Json::String(s)) => Ok(Value::Bits(Bits::from_str(s)?)),
+        (TypeDef::BitSequence(_), Json::Array(elems)) => {
+            let bits = elems
+                .iter()
+                .map(|elem| {
+                    elem.as_bool().ok_or_else(|| {
+                        anyhow::anyhow!("Expected a bool in a bit sequence, got {elem}")
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Value::Bits(bits.into()))
+        }
+        (TypeDef::BitSequence(_), json) => {
+            Err(anyhow::anyhow!(
+                "Expected an array of bools for a bit sequence, got {json}"
+            ))
+        }
+    }
+}
+
+/// Convert a decoded [`Value`] of the type with the given id to JSON.
+pub fn value_to_json(
+    registry: &PortableRegistry,
+    type_id: u32,
+    value: &Value,
+) -> Result<Json> {
+    let ty = resolve(registry, type_id)?;
+    match (&ty.type_def, value) {
+        // Produced by the custom type decoders, e.g. for an `AccountId` or a `Hash`.
+        (_, Value::Literal(literal)) => Ok(Json::String(literal.clone())),
+        (_, Value::Hex(hex)) => Ok(Json::String(format!("{hex:#x}"))),
+        (TypeDef::Composite(composite), value) => {
+            fields_to_json(registry, &composite.fields, value)
+        }
+        (TypeDef::Variant(variant), Value::Map(_) | Value::Tuple(_)) => {
+            let name = match value {
+                Value::Map(map) => map.ident(),
+                Value::Tuple(tuple) => tuple.ident(),
+                _ => None,
+            }
+            .ok_or_else(|| anyhow::anyhow!("Missing the name of the enum variant"))?;
+            let variant = find_variant(&variant.variants, &name)?;
+            let fields = fields_to_json(registry, &variant.fields, value)?;
+            Ok(Json::Object([(name, fields)].into_iter().collect()))
+        }
+        (TypeDef::Array(array), Value::Seq(seq)) => {
+            seq_to_json(registry, array.type_param.id, seq)
+        }
+        (TypeDef::Sequence(sequence), Value::Seq(seq)) => {
+            seq_to_json(registry, sequence.type_param.id, seq)
+        }
+        (TypeDef::Tuple(tuple), Value::Tuple(values)) => {
+            if tuple.fields.is_empty() {
+                return Ok(Json::Null)
+            }
+            let elems = tuple
+                .fields
+                .iter()
+                .zip(values.values())
+                .map(|(field, value)| value_to_json(registry, field.id, value))
+                .collect::<Result<_>>()?;
+            Ok(Json::Array(elems))
+        }
+        (TypeDef::Primitive(primitive), value) => primitive_to_json(primitive, value),
+        (TypeDef::Compact(compact), value) => {
+            value_to_json(registry, compact.type_param.id, value)
+        }
+        (TypeDef::BitSequence(_), Value::Bits(bits)) => {
+            Ok(Json::Array(
+                bits.as_slice().iter().copied().map(Json::Bool).collect(),
+            ))
+        }
+        (type_def, value) => {
+            Err(anyhow::anyhow!(
+                "Unexpected value {value} for type definition {type_def:?}"
+            ))
+        }
+    }
+}
+
+fn resolve(registry: &PortableRegistry, type_id: u32) -> Result<&Type<PortableForm>> {
+    registry
+        .resolve(type_id)
+        .ok_or_else(|| anyhow::anyhow!("Failed to resolve type with id `{type_id}`"))
+}
+
+fn ident(ty: &Type<PortableForm>) -> Option<&str> {
+    ty.path.segments.last().map(|s| s.as_str())
+}
+
+fn find_variant<'a>(
+    variants: &'a [Variant<PortableForm>],
+    name: &str,
+) -> Result<&'a Variant<PortableForm>> {
+    variants
+        .iter()
+        .find(|variant| variant.name == name)
+        .ok_or_else(|| anyhow::anyhow!("No variant `{name}` found"))
+}
+
+fn string_to_value(s: &str) -> Result<Value> {
+    if s.starts_with("0x") {
+        Ok(Value::Hex(Hex::from_str(s)?))
+    } else {
+        Ok(Value::Literal(s.to_string()))
+    }
+}
+
+fn fields_to_value(
+    registry: &PortableRegistry,
+    ident: Option<&str>,
+    fields: &[Field<PortableForm>],
+    json: &Json,
+) -> Result<Value> {
+    match (CompositeTypeFields::from_fields(fields)?, json) {
+        (CompositeTypeFields::NoFields, Json::Null) => {
+            Ok(Value::Tuple(Tuple::new(ident, Vec::new())))
+        }
+        (CompositeTypeFields::NoFields, Json::Array(elems)) if elems.is_empty() => {
+            Ok(Value::Tuple(Tuple::new(ident, Vec::new())))
+        }
+        (CompositeTypeFields::Named(named_fields), Json::Object(object)) => {
+            if let Some(unknown) = object
+                .keys()
+                .find(|key| !named_fields.iter().any(|field| field.name() == *key))
+            {
+                anyhow::bail!("Unknown field `{unknown}`")
+            }
+            let map = named_fields
+                .iter()
+                .map(|field| {
+                    let json = object.get(field.name()).ok_or_else(|| {
+                        anyhow::anyhow!("Missing a field named `{}`", field.name())
+                    })?;
+                    let value = json_to_value(registry, field.field().ty.id, json)
+                        .context(format!("Invalid field `{}`", field.name()))?;
+                    Ok((Value::String(field.name().to_string()), value))
+                })
+                .collect::<Result<_>>()?;
+            Ok(Value::Map(Map::new(ident, map)))
+        }
+        (CompositeTypeFields::Unnamed(fields), Json::Array(elems))
+            if fields.len() > 1 || elems.len() == fields.len() =>
+        {
+            anyhow::ensure!(
+                elems.len() == fields.len(),
+                "Expected {} fields, got {}",
+                fields.len(),
+                elems.len()
+            );
+            let values = fields
+                .iter()
+                .zip(elems)
+                .map(|(field, elem)| json_to_value(registry, field.ty.id, elem))
+                .collect::<Result<_>>()?;
+            Ok(Value::Tuple(Tuple::new(ident, values)))
+        }
+        (CompositeTypeFields::Unnamed(fields), json) if fields.len() == 1 => {
+            let value = json_to_value(registry, fields[0].ty.id, json)?;
+            Ok(Value::Tuple(Tuple::new(ident, vec![value])))
+        }
+        (_, json) => {
+            Err(anyhow::anyhow!(
+                "Expected {} for the fields of `{}`, got {json}",
+                match fields.first() {
+                    None => "null",
+                    Some(field) if field.name.is_some() => "an object",
+                    Some(_) => "an array",
+                },
+                ident.unwrap_or_default()
+            ))
+        }
+    }
+}
+
+fn fields_to_json(
+    registry: &PortableRegistry,
+    fields: &[Field<PortableForm>],
+    value: &Value,
+) -> Result<Json> {
+    match (CompositeTypeFields::from_fields(fields)?, value) {
+        (CompositeTypeFields::NoFields, _) => Ok(Json::Null),
+        (CompositeTypeFields::Named(named_fields), Value::Map(map)) => {
+            let object = named_fields
+                .iter()
+                .map(|field| {
+                    let value = map.get_by_str(field.name()).ok_or_else(|| {
+                        anyhow::anyhow!("Missing a field named `{}`", field.name())
+                    })?;
+                    let json = value_to_json(registry, field.field().ty.id, value)?;
+                    Ok((field.name().to_string(), json))
+                })
+                .collect::<Result<_>>()?;
+            Ok(Json::Object(object))
+        }
+        (CompositeTypeFields::Unnamed(fields), Value::Tuple(tuple)) => {
+            let mut elems = fields
+                .iter()
+                .zip(tuple.values())
+                .map(|(field, value)| value_to_json(registry, field.ty.id, value))
+                .collect::<Result<Vec<_>>>()?;
+            if elems.len() == 1 {
+                Ok(elems.remove(0))
+            } else {
+                Ok(Json::Array(elems))
+            }
+        }
+        (CompositeTypeFields::Unnamed(fields), value) if fields.len() == 1 => {
+            value_to_json(registry, fields[0].ty.id, value)
+        }
+        (_, value) => Err(anyhow::anyhow!("Unexpected value {value} for fields")),
+    }
+}
+
+fn is_byte(registry: &PortableRegistry, type_id: u32) -> Result<bool> {
+    Ok(matches!(
+        resolve(registry, type_id)?.type_def,
+        TypeDef::Primitive(TypeDefPrimitive::U8)
+    ))
+}
+
+fn seq_to_value(
+    registry: &PortableRegistry,
+    elem_type_id: u32,
+    json: &Json,
+) -> Result<Value> {
+    match json {
+        Json::String(s) if is_byte(registry, elem_type_id)? => {
+            Ok(Value::Hex(Hex::from_str(s)?))
+        }
+        Json::Array(elems) => {
+            let values = elems
+                .iter()
+                .map(|elem| json_to_value(registry, elem_type_id, elem))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Value::Seq(Seq::new(values)))
+        }
+        json => Err(anyhow::anyhow!("Expected an array, got {json}")),
+    }
+}
+
+fn seq_to_json(
+    registry: &PortableRegistry,
+    elem_type_id: u32,
+    seq: &Seq,
+) -> Result<Json> {
+    if is_byte(registry, elem_type_id)? {
+        let bytes = seq
+            .elems()
+            .iter()
+            .map(|elem| {
+                match elem {
+                    Value::UInt(byte) => Ok(u8::try_from(*byte)?),
+                    elem => Err(anyhow::anyhow!("Expected a byte, got {elem}")),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        return Ok(Json::String(format!("0x{}", hex::encode(bytes))))
+    }
+    let elems = seq
+        .elems()
+        .iter()
+        .map(|elem| value_to_json(registry, elem_type_id, elem))
+        .collect::<Result<_>>()?;
+    Ok(Json::Array(elems))
+}
+
+fn primitive_to_value(primitive: &TypeDefPrimitive, json: &Json) -> Result<Value> {
+    match (primitive, json) {
+        (TypeDefPrimitive::Bool, Json::Bool(b)) => Ok(Value::Bool(*b)),
+        (TypeDefPrimitive::Str, Json::String(s)) => Ok(Value::String(s.clone())),
+        (TypeDefPrimitive::Char, Json::String(s)) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Ok(Value::Char(c)),
+                _ => Err(anyhow::anyhow!("Expected a single character, got {s:?}")),
+            }
+        }
+        // Integers are encoded from strings, e.g. "1_000_000", by the encoder.
+        (_, Json::String(s)) if is_integer(primitive) => Ok(Value::String(s.clone())),
+        (_, Json::Number(n)) if is_integer(primitive) => {
+            n.as_u64()
+                .map(|uint| Value::UInt(uint.into()))
+                .or_else(|| n.as_i64().map(|int| Value::Int(int.into())))
+                .ok_or_else(|| anyhow::anyhow!("Expected an integer, got {n}"))
+        }
+        (primitive, json) => {
+            Err(anyhow::anyhow!(
+                "Expected a {} value, got {json}",
+                format!("{primitive:?}").to_lowercase()
+            ))
+        }
+    }
+}
+
+fn primitive_to_json(primitive: &TypeDefPrimitive, value: &Value) -> Result<Json> {
+    let is_number = matches!(
+        primitive,
+        TypeDefPrimitive::U8
+            | TypeDefPrimitive::U16
+            | TypeDefPrimitive::U32
+            | TypeDefPrimitive::I8
+            | TypeDefPrimitive::I16
+            | TypeDefPrimitive::I32
+    );
+    match value {
+        Value::Bool(b) => Ok(Json::Bool(*b)),
+        Value::Char(c) => Ok(Json::String(c.to_string())),
+        Value::String(s) => Ok(Json::String(s.clone())),
+        Value::UInt(uint) if is_number => Ok(Json::from(u32::try_from(*uint)?)),
+        Value::Int(int) if is_number => Ok(Json::from(i32::try_from(*int)?)),
+        Value::UInt(_) | Value::Int(_) | Value::BigUInt(_) | Value::BigInt(_) => {
+            Ok(Json::String(value.to_string()))
+        }
+        value => {
+            Err(anyhow::anyhow!(
+                "Unexpected value {value} for primitive {primitive:?}"
+            ))
+        }
+    }
+}
+
+fn is_integer(primitive: &TypeDefPrimitive) -> bool {
+    !matches!(
+        primitive,
+        TypeDefPrimitive::Bool | TypeDefPrimitive::Char | TypeDefPrimitive::Str
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        Transcoder,
+        TranscoderBuilder,
+    };
+    use scale::Encode;
+    use scale_info::{
+        MetaType,
+        Registry,
+        TypeInfo,
+    };
+    use serde_json::json;
+
+    #[allow(dead_code)]
+    #[derive(Encode, TypeInfo)]
+    struct Point {
+        x: i32,
+        y: u64,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Encode, TypeInfo)]
+    struct Wrapper([u8; 4]);
+
+    #[allow(dead_code)]
+    #[derive(Encode, TypeInfo)]
+    enum Shape {
+        Empty,
+        Circle(u32),
+        Rect { corner: Point, sides: (u8, u8) },
+    }
+
+    fn registry_with_type<T: TypeInfo + 'static>() -> (PortableRegistry, u32) {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&MetaType::new::<T>());
+        (registry.into(), type_id.id)
+    }
+
+    /// Encode the JSON as `T`, check against the SCALE encoding of `expected` and decode
+    /// it back to JSON.
+    fn json_roundtrip<T: TypeInfo + Encode + 'static>(
+        input: Json,
+        expected: T,
+        output: Json,
+    ) -> Result<()> {
+        let (registry, ty) = registry_with_type::<T>();
+        let transcoder: Transcoder = TranscoderBuilder::new(&registry)
+            .with_default_custom_type_transcoders()
+            .done();
+
+        let value = json_to_value(&registry, ty, &input)?;
+        let mut encoded = Vec::new();
+        transcoder.encode(&registry, ty, &value, &mut encoded)?;
+        assert_eq!(encoded, expected.encode(), "encoding");
+
+        let decoded = transcoder.decode(&registry, ty, &mut &encoded[..])?;
+        assert_eq!(value_to_json(&registry, ty, &decoded)?, output, "decoding");
+        Ok(())
+    }
+
+    #[test]
+    fn json_struct() -> Result<()> {
+        json_roundtrip(
+            json!({ "x": -1, "y": 2 }),
+            Point { x: -1, y: 2 },
+            json!({ "x": -1, "y": "2" }),
+        )?;
+        json_roundtrip(
+            json!({ "x": 1, "y": "18_446_744_073_709_551_615" }),
+            Point { x: 1, y: u64::MAX },
+            json!({ "x": 1, "y": "18446744073709551615" }),
+        )
+    }
+
+    #[test]
+    fn json_bytes() -> Result<()> {
+        json_roundtrip(
+            json!("0xdeadbeef"),
+            Wrapper([0xde, 0xad, 0xbe, 0xef]),
+            json!("0xdeadbeef"),
+        )?;
+        json_roundtrip(
+            json!([1, 2, 3, 4]),
+            Wrapper([1, 2, 3, 4]),
+            json!("0x01020304"),
+        )?;
+        json_roundtrip(json!("0x0102"), vec![1u8, 2], json!("0x0102"))
+    }
+
+    #[test]
+    fn json_enum() -> Result<()> {
+        json_roundtrip(json!("Empty"), Shape::Empty, json!({ "Empty": null }))?;
+        json_roundtrip(
+            json!({ "Circle": 5 }),
+            Shape::Circle(5),
+            json!({ "Circle": 5 }),
+        )?;
+        json_roundtrip(
+            json!({ "Rect": { "corner": { "x": 0, "y": 1 }, "sides": [2, 3] } }),
+            Shape::Rect {
+                corner: Point { x: 0, y: 1 },
+                sides: (2, 3),
+            },
+            json!({ "Rect": { "corner": { "x": 0, "y": "1" }, "sides": [2, 3] } }),
+        )?;
+        json_roundtrip(json!({ "Some": true }), Some(true), json!({ "Some": true }))?;
+        json_roundtrip(json!("None"), None::<bool>, json!({ "None": null }))
+    }
+
+    #[test]
+    fn json_invalid_input() {
+        let (registry, ty) = registry_with_type::<Point>();
+        assert!(json_to_value(&registry, ty, &json!({ "x": 1 })).is_err());
+        assert!(
+            json_to_value(&registry, ty, &json!({ "x": 1, "y": 2, "z": 3 })).is_err()
+        );
+        assert!(json_to_value(&registry, ty, &json!([1, 2])).is_err());
+
+        let (registry, ty) = registry_with_type::<Shape>();
+        assert!(json_to_value(&registry, ty, &json!({ "Square": 1 })).is_err());
+        assert!(
+            json_to_value(&registry, ty, &json!({ "Empty": 1, "Circle": 2 })).is_err()
+        );
+    }
+}
@@ -99,20 +99,31 @@
 //! ```
 
 mod account_id;
+mod custom_types;
 mod decode;
 mod encode;
 pub mod env_types;
+mod json;
+mod render;
 mod scon;
 mod transcoder;
 mod util;
 
 pub use self::{
     account_id::AccountId32,
+    custom_types::{
+        Codec,
+        CustomTypes,
+    },
+    render::Renderer,
     scon::{
+        Bits,
         Hex,
         Map,
+        Seq,
         Tuple,
         Value,
+        I256,
     },
     transcoder::{
         Transcoder,
@@ -124,13 +135,17 @@ use anyhow::{
     Context,
     Result,
 };
+use indexmap::IndexMap;
 pub use ink_metadata;
 use ink_metadata::{
     ConstructorSpec,
     InkProject,
+    MessageParamSpec,
     MessageSpec,
+    Selector,
 };
 use itertools::Itertools;
+pub use scale;
 use scale::{
     Compact,
     Decode,
@@ -145,15 +160,53 @@ use scale_info::{
 };
 use std::{
     cmp::Ordering,
-    fmt::Debug,
+    fmt::{
+        Debug,
+        Display,
+    },
     path::Path,
+    str::FromStr,
 };
 
+/// The format of the arguments of a contract call.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ArgsFormat {
+    /// SCALE Object Notation, e.g. `Some(42)` or `{ a: 1 }`.
+    #[default]
+    Scon,
+    /// JSON, e.g. `{ "Some": 42 }` or `{ "a": 1 }`.
+    Json,
+}
+
+impl FromStr for ArgsFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "scon" => Ok(Self::Scon),
+            "json" => Ok(Self::Json),
+            _ => anyhow::bail!("Invalid args format `{s}`, expected `scon` or `json`"),
+        }
+    }
+}
+
+impl Display for ArgsFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Scon => write!(f, "scon"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
 /// Encode strings to SCALE encoded smart contract calls.
 /// Decode SCALE encoded smart contract events and return values into `Value` objects.
 pub struct ContractMessageTranscoder {
     metadata: InkProject,
     transcoder: Transcoder,
+    renderer: Option<Renderer>,
+    ss58_prefix: Option<u16>,
+    custom_types: Option<CustomTypes>,
 }
 
 /// Find strings from an iterable of `possible_values` similar to a given value `v`
@@ -174,15 +227,90 @@ where
     candidates.into_iter().map(|(_, pv)| pv).collect()
 }
 
+/// Check that there is one of the `args` for each of the `spec_args`.
+fn args_by_position<T>(
+    spec_args: &[MessageParamSpec<PortableForm>],
+    args: Vec<T>,
+) -> Result<Vec<T>> {
+    if spec_args.len() != args.len() {
+        anyhow::bail!(
+            "Invalid number of input arguments: expected {}, {} provided",
+            spec_args.len(),
+            args.len()
+        )
+    }
+    Ok(args)
+}
+
 impl ContractMessageTranscoder {
     pub fn new(metadata: InkProject) -> Self {
-        let transcoder = TranscoderBuilder::new(metadata.registry())
-            .register_custom_type_transcoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::AccountId, _>(env_types::AccountId)
-            .register_custom_type_decoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::Hash, _>(env_types::Hash)
-            .done();
+        let transcoder = Self::transcoder_builder(&metadata).done();
         Self {
             metadata,
             transcoder,
+            renderer: None,
+            ss58_prefix: None,
+            custom_types: None,
+        }
+    }
+
+    /// Transcode the types configured in the [`CustomTypes`] with their custom codecs,
+    /// which take precedence over the built-in transcoders of `AccountId` and `Hash`.
+    ///
+    /// # Errors
+    ///
+    /// - If the codec configured for a type is not applicable to it.
+    pub fn with_custom_types(self, custom_types: &CustomTypes) -> Result<Self> {
+        let mut this = self;
+        this.custom_types = Some(custom_types.clone());
+        this.transcoder = this.build_transcoder()?;
+        Ok(this)
+    }
+
+    /// Display the `AccountId` as an SS58 address with the given address format
+    /// prefix, instead of the generic Substrate prefix `42`. Addresses with any prefix
+    /// are accepted as arguments.
+    ///
+    /// # Errors
+    ///
+    /// - If the codec configured for a custom type is not applicable to it.
+    pub fn with_ss58_prefix(self, prefix: u16) -> Result<Self> {
+        let mut this = self;
+        this.ss58_prefix = Some(prefix);
+        this.transcoder = this.build_transcoder()?;
+        Ok(this)
+    }
+
+    /// Return the SS58 address format prefix the `AccountId` is displayed with, if it
+    /// is not the generic Substrate prefix.
+    pub fn ss58_prefix(&self) -> Option<u16> {
+        self.ss58_prefix
+    }
+
+    fn build_transcoder(&self) -> Result<Transcoder> {
+        let mut builder = Self::transcoder_builder(&self.metadata);
+        if let Some(prefix) = self.ss58_prefix {
+            builder = builder.with_ss58_prefix(prefix);
+        }
+        if let Some(ref custom_types) = self.custom_types {
+            builder =
+                builder.with_custom_types(self.metadata.registry(), custom_types)?;
+        }
+        Ok(builder.done())
+    }
+
+    fn transcoder_builder(metadata: &InkProject) -> TranscoderBuilder {
+        TranscoderBuilder::new(metadata.registry())
+            .register_custom_type_transcoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::AccountId, _>(env_types::AccountId)
+            .register_custom_type_decoder::<<ink_env::DefaultEnvironment as ink_env::Environment>::Hash, _>(env_types::Hash)
+    }
+
+    /// Render the decoded values of well-known types in a human-readable form, see
+    /// [`Renderer`]. The values decoded to JSON are never rendered.
+    pub fn with_renderer(self, renderer: Renderer) -> Self {
+        Self {
+            renderer: Some(renderer),
+            ..self
         }
     }
 
@@ -211,14 +339,260 @@ impl ContractMessageTranscoder {
         I: IntoIterator<Item = S>,
         S: AsRef<str> + Debug,
     {
-        let (selector, spec_args) = match (
+        self.encode_with_format(name, args, ArgsFormat::Scon)
+    }
+
+    /// Encode a call to the constructor or message with the given name, with each of
+    /// the `args` given as JSON, see [`ContractMessageTranscoder::decode_json`].
+    pub fn encode_json<I, S>(&self, name: &str, args: I) -> Result<Vec<u8>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str> + Debug,
+    {
+        self.encode_with_format(name, args, ArgsFormat::Json)
+    }
+
+    /// Encode a call to the constructor or message with the given name, with the
+    /// `args` in the given format.
+    pub fn encode_with_format<I, S>(
+        &self,
+        name: &str,
+        args: I,
+        format: ArgsFormat,
+    ) -> Result<Vec<u8>>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str> + Debug,
+    {
+        let (selector, spec_args) = self.find_spec(name)?;
+        let args: Vec<_> = args.into_iter().collect();
+        let values = args_by_position(spec_args, args)?
+            .into_iter()
+            .zip(spec_args)
+            .map(|(arg, spec)| self.parse_arg(spec.ty().ty().id, arg.as_ref(), format))
+            .collect::<Result<_>>()?;
+        self.encode_values(selector, spec_args, values)
+    }
+
+    /// Encode a call to the constructor or message with the given name, with the
+    /// `args` in the given format keyed by the argument labels, in any order.
+    ///
+    /// Arguments of type `Option` may be left out, and default to `None`.
+    pub fn encode_named<I, L, S>(
+        &self,
+        name: &str,
+        args: I,
+        format: ArgsFormat,
+    ) -> Result<Vec<u8>>
+    where
+        I: IntoIterator<Item = (L, S)>,
+        L: Into<String>,
+        S: AsRef<str>,
+    {
+        let (selector, spec_args) = self.find_spec(name)?;
+        let args = args
+            .into_iter()
+            .map(|(label, arg)| (label.into(), arg))
+            .collect();
+        let values = self.args_by_label(spec_args, args, |type_id, arg| {
+            self.parse_arg(type_id, arg.as_ref(), format)
+        })?;
+        self.encode_values(selector, spec_args, values)
+    }
+
+    /// Encode a call to the constructor or message with the given name, with all the
+    /// arguments given in a single `document` in the given format, e.g. read from a
+    /// file. The document is either a sequence with one value per argument, e.g. `[1,
+    /// [2, 3]]`, or a map keyed by the argument labels, e.g. `{ "a": 1, "b": [2, 3] }`.
+    pub fn encode_args_document(
+        &self,
+        name: &str,
+        document: &str,
+        format: ArgsFormat,
+    ) -> Result<Vec<u8>> {
+        let (selector, spec_args) = self.find_spec(name)?;
+        let registry = self.metadata.registry();
+        let values = match format {
+            ArgsFormat::Scon => {
+                match scon::parse_value(document)? {
+                    Value::Seq(seq) => args_by_position(spec_args, seq.elems().to_vec())?,
+                    Value::Tuple(tuple) if tuple.ident().is_none() => {
+                        args_by_position(spec_args, tuple.values().cloned().collect())?
+                    }
+                    Value::Map(map) if map.ident().is_none() => {
+                        let args = map
+                            .iter()
+                            .map(|(label, value)| {
+                                match label {
+                                    Value::String(label) => {
+                                        Ok((label.clone(), value.clone()))
+                                    }
+                                    label => {
+                                        Err(anyhow::anyhow!(
+                                            "Expected an argument label, got {label}"
+                                        ))
+                                    }
+                                }
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        self.args_by_label(spec_args, args, |_, value| Ok(value))?
+                    }
+                    value => {
+                        anyhow::bail!(
+                            "Expected a sequence of arguments or a map keyed by argument \
+                             label, got {value}"
+                        )
+                    }
+                }
+            }
+            ArgsFormat::Json => {
+                let document: serde_json::Value = serde_json::from_str(document)
+                    .context("Failed to parse the arguments as JSON")?;
+                let convert =
+                    |type_id, json| json::json_to_value(registry, type_id, &json);
+                match document {
+                    serde_json::Value::Array(args) => {
+                        args_by_position(spec_args, args)?
+                            .into_iter()
+                            .zip(spec_args)
+                            .map(|(arg, spec)| {
+                                convert(spec.ty().ty().id, arg).context(format!(
+                                    "Invalid argument `{}`",
+                                    spec.label()
+                                ))
+                            })
+                            .collect::<Result<_>>()?
+                    }
+                    serde_json::Value::Object(args) => {
+                        self.args_by_label(
+                            spec_args,
+                            args.into_iter().collect(),
+                            convert,
+                        )?
+                    }
+                    json => {
+                        anyhow::bail!(
+                            "Expected an array of arguments or an object keyed by \
+                             argument label, got {json}"
+                        )
+                    }
+                }
+            }
+        };
+        self.encode_values(selector, spec_args, values)
+    }
+
+    fn parse_arg(&self, type_id: u32, arg: &str, format: ArgsFormat) -> Result<Value> {
+        match format {
+            ArgsFormat::Scon => scon::parse_value(arg),
+            ArgsFormat::Json => {
+                let json = serde_json::from_str(arg)
+                    .context(format!("Failed to parse `{arg}` as JSON"))?;
+                json::json_to_value(self.metadata.registry(), type_id, &json)
+            }
+        }
+    }
+
+    /// Order the `args` keyed by label as the `spec_args`, converting each with
+    /// `convert`. Reports unknown and missing labels, missing `Option` arguments
+    /// default to `None`.
+    fn args_by_label<T, F>(
+        &self,
+        spec_args: &[MessageParamSpec<PortableForm>],
+        args: Vec<(String, T)>,
+        convert: F,
+    ) -> Result<Vec<Value>>
+    where
+        F: Fn(u32, T) -> Result<Value>,
+    {
+        let mut args: IndexMap<_, _> =
+            args.into_iter()
+                .try_fold(IndexMap::new(), |mut args, (label, arg)| {
+                    if args.insert(label.clone(), arg).is_some() {
+                        anyhow::bail!("Argument `{label}` given more than once")
+                    }
+                    Ok(args)
+                })?;
+        let labels = || spec_args.iter().map(|spec| spec.label()).join(", ");
+        if let Some(unknown) = args
+            .keys()
+            .find(|label| !spec_args.iter().any(|spec| spec.label() == *label))
+        {
+            let help_txt =
+                did_you_mean(unknown, spec_args.iter().map(|spec| spec.label()))
+                    .pop()
+                    .map(|suggestion| format!("Did you mean '{suggestion}'?"))
+                    .unwrap_or_else(|| format!("Should be one of: {}", labels()));
+            anyhow::bail!("Unknown argument `{unknown}`.\n{help_txt}")
+        }
+        let missing: Vec<_> = spec_args
+            .iter()
+            .filter(|spec| {
+                !args.contains_key(spec.label()) && !self.is_option(spec.ty().ty().id)
+            })
+            .map(|spec| format!("`{}`", spec.label()))
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "Missing argument(s) {}, expected: {}",
+                missing.join(", "),
+                labels()
+            )
+        }
+        spec_args
+            .iter()
+            .map(|spec| {
+                match args.swap_remove(spec.label()) {
+                    Some(arg) => {
+                        convert(spec.ty().ty().id, arg)
+                            .context(format!("Invalid argument `{}`", spec.label()))
+                    }
+                    None => Ok(Value::Tuple(Tuple::new(Some("None"), Vec::new()))),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns `true` if the type with the given id is an `Option`.
+    fn is_option(&self, type_id: u32) -> bool {
+        self.metadata.registry().resolve(type_id).is_some_and(|ty| {
+            ty.path.segments == ["Option"]
+                && matches!(ty.type_def, scale_info::TypeDef::Variant(_))
+        })
+    }
+
+    fn encode_values(
+        &self,
+        selector: &Selector,
+        spec_args: &[MessageParamSpec<PortableForm>],
+        values: Vec<Value>,
+    ) -> Result<Vec<u8>> {
+        let mut encoded = selector.to_bytes().to_vec();
+        for (spec, value) in spec_args.iter().zip(values) {
+            self.transcoder.encode(
+                self.metadata.registry(),
+                spec.ty().ty().id,
+                &value,
+                &mut encoded,
+            )?;
+        }
+        Ok(encoded)
+    }
+
+    /// Find the selector and the arguments of the constructor or message with the
+    /// given name.
+    fn find_spec(
+        &self,
+        name: &str,
+    ) -> Result<(&Selector, &[MessageParamSpec<PortableForm>])> {
+        match (
             self.find_constructor_spec(name),
             self.find_message_spec(name),
         ) {
-            (Some(c), None) => (c.selector(), c.args()),
-            (None, Some(m)) => (m.selector(), m.args()),
+            (Some(c), None) => Ok((c.selector(), c.args())),
+            (None, Some(m)) => Ok((m.selector(), m.args())),
             (Some(_), Some(_)) => {
-                return Err(anyhow::anyhow!(
+                Err(anyhow::anyhow!(
                 "Invalid metadata: both a constructor and message found with name '{}'",
                 name
             ))
@@ -234,37 +608,89 @@ impl ContractMessageTranscoder {
                         format!("Should be one of: {}", possible_values.iter().join(", "))
                     });
 
-                return Err(anyhow::anyhow!(
+                Err(anyhow::anyhow!(
                     "No constructor or message with the name '{name}' found.\n{help_txt}",
                 ))
             }
-        };
-
-        let args: Vec<_> = args.into_iter().collect();
-        if spec_args.len() != args.len() {
-            anyhow::bail!(
-                "Invalid number of input arguments: expected {}, {} provided",
-                spec_args.len(),
-                args.len()
-            )
         }
+    }
 
-        let mut encoded = selector.to_bytes().to_vec();
-        for (spec, arg) in spec_args.iter().zip(args) {
-            let value = scon::parse_value(arg.as_ref())?;
-            self.transcoder.encode(
-                self.metadata.registry(),
-                spec.ty().ty().id,
-                &value,
-                &mut encoded,
-            )?;
-        }
+    /// Encode a single SCON value of the type with the given id, e.g. a storage key.
+    pub fn encode_value(&self, type_id: u32, value: &str) -> Result<Vec<u8>> {
+        self.encode_value_with_format(type_id, value, ArgsFormat::Scon)
+    }
+
+    /// Encode a single value in the given format of the type with the given id, e.g. to
+    /// validate a single argument.
+    pub fn encode_value_with_format(
+        &self,
+        type_id: u32,
+        value: &str,
+        format: ArgsFormat,
+    ) -> Result<Vec<u8>> {
+        let value = self.parse_arg(type_id, value, format)?;
+        let mut encoded = Vec::new();
+        self.transcoder.encode(
+            self.metadata.registry(),
+            type_id,
+            &value,
+            &mut encoded,
+        )?;
         Ok(encoded)
     }
 
+    /// Returns the name of the type with the given id as it would be written in Rust,
+    /// e.g. `Option<String>`.
+    pub fn type_name(&self, type_id: u32) -> String {
+        util::type_name(self.metadata.registry(), type_id)
+    }
+
     pub fn decode(&self, type_id: u32, input: &mut &[u8]) -> Result<Value> {
-        self.transcoder
-            .decode(self.metadata.registry(), type_id, input)
+        self.decode_as(type_id, None, input)
+    }
+
+    /// Decode a value of the type with the given id, rendered according to the
+    /// `type_name` it is referred to with, e.g. `Balance` for a `u128`.
+    fn decode_as(
+        &self,
+        type_id: u32,
+        type_name: Option<&str>,
+        input: &mut &[u8],
+    ) -> Result<Value> {
+        let registry = self.metadata.registry();
+        let value = self.transcoder.decode(registry, type_id, input)?;
+        Ok(match self.renderer {
+            Some(ref renderer) => renderer.render(registry, type_id, type_name, &value),
+            None => value,
+        })
+    }
+
+    /// Decode a value of the type with the given id to JSON: objects for structs,
+    /// `{ "Variant": fields }` for enums, hex strings for byte arrays and strings for
+    /// integers larger than 32 bits.
+    pub fn decode_json(
+        &self,
+        type_id: u32,
+        input: &mut &[u8],
+    ) -> Result<serde_json::Value> {
+        let value = self
+            .transcoder
+            .decode(self.metadata.registry(), type_id, input)?;
+        json::value_to_json(self.metadata.registry(), type_id, &value)
+    }
+
+    /// Decode the return value of the message with the given name to JSON, see
+    /// [`ContractMessageTranscoder::decode_json`].
+    pub fn decode_message_return_json(
+        &self,
+        name: &str,
+        data: &mut &[u8],
+    ) -> Result<serde_json::Value> {
+        let msg_spec = self.find_message_spec(name).ok_or_else(|| {
+            anyhow::anyhow!("Failed to find message spec with name '{}'", name)
+        })?;
+        let return_ty = msg_spec.return_type().ret_type();
+        self.decode_json(return_ty.ty().id, data)
     }
 
     pub fn metadata(&self) -> &InkProject {
@@ -326,7 +752,11 @@ impl ContractMessageTranscoder {
         let mut args = Vec::new();
         for arg in event_spec.args() {
             let name = arg.label().to_string();
-            let value = self.decode(arg.ty().ty().id, data)?;
+            let value = self.decode_as(
+                arg.ty().ty().id,
+                arg.ty().display_name().segments.last().map(String::as_str),
+                data,
+            )?;
             args.push((Value::String(name), value));
         }
 
@@ -355,7 +785,11 @@ impl ContractMessageTranscoder {
         let mut args = Vec::new();
         for arg in msg_spec.args() {
             let name = arg.label().to_string();
-            let value = self.decode(arg.ty().ty().id, data)?;
+            let value = self.decode_as(
+                arg.ty().ty().id,
+                arg.ty().display_name().segments.last().map(String::as_str),
+                data,
+            )?;
             args.push((Value::String(name), value));
         }
 
@@ -384,7 +818,11 @@ impl ContractMessageTranscoder {
         let mut args = Vec::new();
         for arg in msg_spec.args() {
             let name = arg.label().to_string();
-            let value = self.decode(arg.ty().ty().id, data)?;
+            let value = self.decode_as(
+                arg.ty().ty().id,
+                arg.ty().display_name().segments.last().map(String::as_str),
+                data,
+            )?;
             args.push((Value::String(name), value));
         }
 
@@ -405,7 +843,8 @@ impl ContractMessageTranscoder {
             anyhow::anyhow!("Failed to find constructor spec with name '{}'", name)
         })?;
         let return_ty = ctor_spec.return_type().ret_type();
-        self.decode(return_ty.ty().id, data)
+        let type_name = return_ty.display_name().segments.last();
+        self.decode_as(return_ty.ty().id, type_name.map(String::as_str), data)
     }
 
     pub fn decode_message_return(&self, name: &str, data: &mut &[u8]) -> Result<Value> {
@@ -413,7 +852,8 @@ impl ContractMessageTranscoder {
             anyhow::anyhow!("Failed to find message spec with name '{}'", name)
         })?;
         let return_ty = msg_spec.return_type().ret_type();
-        self.decode(return_ty.ty().id, data)
+        let type_name = return_ty.display_name().segments.last();
+        self.decode_as(return_ty.ty().id, type_name.map(String::as_str), data)
     }
 
     /// Checks if buffer empty, otherwise returns am error
@@ -580,6 +1020,11 @@ mod tests {
             pub fn uint_array_args(&self, arr: [u8; 4]) {
                 let _ = arr;
             }
+
+            #[ink(message)]
+            pub fn transfer(&self, to: AccountId, amount: u128, memo: Option<String>) {
+                let _ = (to, amount, memo);
+            }
         }
     }
 
@@ -654,6 +1099,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn transcode_account_id_with_ss58_prefix() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata).with_ss58_prefix(0)?;
+        let alice =
+            AccountId32::from_str("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap();
+        let polkadot_alice = alice.to_ss58check_with_prefix(0);
+
+        // addresses are accepted with any prefix
+        let encoded = transcoder.encode("set_account_id", [alice.to_string()])?;
+        assert_eq!(
+            transcoder.encode("set_account_id", [&polkadot_alice])?,
+            encoded
+        );
+
+        let decoded = transcoder.decode_contract_message(&mut &encoded[..])?;
+        assert!(decoded.to_string().contains(&polkadot_alice));
+        Ok(())
+    }
+
     #[test]
     fn encode_account_ids_vec_args() -> Result<()> {
         let metadata = generate_metadata();
@@ -677,6 +1143,244 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn encode_json_args() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let encoded = transcoder.encode_json(
+            "set_account_ids_vec",
+            [r#"["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"]"#],
+        )?;
+        let expected = vec![
+            AccountId32::from_str("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap(),
+            AccountId32::from_str("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY")
+                .unwrap(),
+        ];
+        assert_eq!(expected.encode(), &encoded[4..]);
+
+        let encoded = transcoder.encode_with_format(
+            "uint_args",
+            [
+                "1",
+                "2",
+                "3",
+                r#""4""#,
+                r#""340282366920938463463374607431768211455""#,
+            ],
+            ArgsFormat::Json,
+        )?;
+        assert_eq!((1u8, 2u16, 3u32, 4u64, u128::MAX).encode(), &encoded[4..]);
+        assert!(transcoder.encode_json("new", ["True"]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn encode_args_document() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let expected = transcoder.encode("uint_args", ["1", "2", "3", "4", "5"])?;
+
+        let documents = [
+            ("[1, 2, 3, 4, 5]", ArgsFormat::Scon),
+            ("(1, 2, 3, 4, 5)", ArgsFormat::Scon),
+            (
+                "{ _u128: 5, _u64: 4, _u32: 3, _u16: 2, _u8: 1 }",
+                ArgsFormat::Scon,
+            ),
+            (r#"[1, 2, 3, "4", "5"]"#, ArgsFormat::Json),
+            (
+                r#"{ "_u8": 1, "_u16": 2, "_u32": 3, "_u64": "4", "_u128": 5 }"#,
+                ArgsFormat::Json,
+            ),
+        ];
+        for (document, format) in documents {
+            let encoded =
+                transcoder.encode_args_document("uint_args", document, format)?;
+            assert_eq!(expected, encoded, "{document}");
+        }
+
+        let encoded = transcoder.encode_args_document(
+            "set_account_ids_vec",
+            "[[5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY]]",
+            ArgsFormat::Scon,
+        )?;
+        let expected = vec![AccountId32::from_str(
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY",
+        )
+        .unwrap()];
+        assert_eq!(expected.encode(), &encoded[4..]);
+        Ok(())
+    }
+
+    #[test]
+    fn encode_args_document_reports_invalid_labels() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let error = transcoder
+            .encode_args_document(
+                "uint_args",
+                "{ _u8: 1, _u16: 2, _u32: 3, _u64: 4, _u129: 5 }",
+                ArgsFormat::Scon,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown argument `_u129`.\nDid you mean '_u128'?"
+        );
+
+        let error = transcoder
+            .encode_args_document(
+                "uint_args",
+                r#"{ "_u8": 1, "_u16": 2 }"#,
+                ArgsFormat::Json,
+            )
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Missing argument(s) `_u32`, `_u64`, `_u128`, expected: _u8, _u16, _u32, _u64, _u128"
+        );
+
+        assert!(transcoder
+            .encode_args_document("uint_args", "[1, 2]", ArgsFormat::Scon)
+            .is_err());
+        assert!(transcoder
+            .encode_args_document("new", "true", ArgsFormat::Json)
+            .is_err());
+    }
+
+    #[test]
+    fn encode_named_args() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let account_id = AccountId32::from_str(alice).unwrap();
+
+        let encoded = transcoder.encode_named(
+            "transfer",
+            [
+                ("amount", "100"),
+                ("memo", r#"Some("rent")"#),
+                ("to", alice),
+            ],
+            ArgsFormat::Scon,
+        )?;
+        let expected =
+            transcoder.encode("transfer", [alice, "100", r#"Some("rent")"#])?;
+        assert_eq!(expected, encoded);
+        assert_eq!(
+            (account_id.clone(), 100u128, Some("rent".to_string())).encode(),
+            &encoded[4..]
+        );
+
+        let encoded = transcoder.encode_named(
+            "transfer",
+            [
+                ("to", format!(r#""{alice}""#)),
+                ("amount", r#""100""#.into()),
+            ],
+            ArgsFormat::Json,
+        )?;
+        assert_eq!(
+            (account_id, 100u128, None::<String>).encode(),
+            &encoded[4..]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn encode_named_args_reports_invalid_labels() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let error = transcoder
+            .encode_named("transfer", [("amount", "100")], ArgsFormat::Scon)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Missing argument(s) `to`, expected: to, amount, memo"
+        );
+
+        let error = transcoder
+            .encode_named(
+                "transfer",
+                [("to", "0x00"), ("amount", "1"), ("amount", "2")],
+                ArgsFormat::Scon,
+            )
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Argument `amount` given more than once");
+
+        let error = transcoder
+            .encode_named("transfer", [("too", "0x00")], ArgsFormat::Scon)
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown argument `too`.\nDid you mean 'to'?"
+        );
+    }
+
+    #[test]
+    fn resolve_argument_type_names() {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let type_names = |name| {
+            transcoder
+                .find_message_spec(name)
+                .unwrap()
+                .args()
+                .iter()
+                .map(|arg| transcoder.type_name(arg.ty().ty().id))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            type_names("transfer"),
+            ["AccountId", "u128", "Option<String>"]
+        );
+        assert_eq!(type_names("set_account_ids_vec"), ["Vec<AccountId>"]);
+        assert_eq!(type_names("uint_array_args"), ["[u8; 4]"]);
+    }
+
+    #[test]
+    fn encode_single_value_with_format() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+        let memo = &transcoder.find_message_spec("transfer").unwrap().args()[2];
+        let type_id = memo.ty().ty().id;
+
+        let scon = transcoder.encode_value_with_format(
+            type_id,
+            "Some(\"ink!\")",
+            ArgsFormat::Scon,
+        )?;
+        let json = transcoder.encode_value_with_format(
+            type_id,
+            r#"{ "Some": "ink!" }"#,
+            ArgsFormat::Json,
+        )?;
+
+        assert_eq!(scon, Some("ink!".to_string()).encode());
+        assert_eq!(json, scon);
+        assert!(transcoder
+            .encode_value_with_format(type_id, "42", ArgsFormat::Scon)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn decode_json_return() -> Result<()> {
+        let metadata = generate_metadata();
+        let transcoder = ContractMessageTranscoder::new(metadata);
+
+        let encoded = Result::<bool, ink::primitives::LangError>::Ok(true).encode();
+        let decoded = transcoder.decode_message_return_json("get", &mut &encoded[..])?;
+
+        assert_eq!(decoded, serde_json::json!({ "Ok": true }));
+        Ok(())
+    }
+
     #[test]
     fn encode_primitive_vec_args() -> Result<()> {
         let metadata = generate_metadata();
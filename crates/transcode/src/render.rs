@@ -0,0 +1,457 @@
+// Copyright 2018-2023 Use Ink (UK) Ltd.
+// This file is part of cargo-contract.
+//
+// cargo-contract is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+//
+// cargo-contract is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+//
+// You should have received a copy of the GNU General Public License
+// along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::scon::{
+    Hex,
+    Map,
+    Seq,
+    Tuple,
+    Value,
+};
+use scale_info::{
+    form::PortableForm,
+    Field,
+    PortableRegistry,
+    Type,
+    TypeDef,
+    TypeDefPrimitive,
+};
+use std::{
+    str::FromStr,
+    sync::Arc,
+};
+
+/// Formats a balance, e.g. denominated in the token of the chain.
+type BalanceFormatter = dyn Fn(u128) -> String + Send + Sync;
+
+/// Renders decoded values of well-known types in a human-readable form:
+///
+/// - `Balance` values with the balance formatter, if any.
+/// - `Timestamp` values as ISO-8601 dates.
+/// - `[u8; N]` arrays as hex and `Vec<u8>` as a string if it is printable UTF-8.
+/// - `Percent`, `Permill`, `Perbill` and `Perquintill` as percentages, and `FixedU128`,
+///   `FixedI128`, `FixedU64` and `FixedI64` as decimals.
+///
+/// Values are recognized by the name they are referred to with, e.g. the type name of a
+/// field, so a `u128` field is only rendered as a balance if it is declared as a
+/// `Balance`.
+#[derive(Clone, Default)]
+pub struct Renderer {
+    balance: Option<Arc<BalanceFormatter>>,
+}
+
+impl Renderer {
+    /// Render balances with the given function, otherwise they are displayed as plain
+    /// integers.
+    pub fn balance_formatter<F>(self, formatter: F) -> Self
+    where
+        F: Fn(u128) -> String + Send + Sync + 'static,
+    {
+        Self {
+            balance: Some(Arc::new(formatter)),
+        }
+    }
+
+    /// Render a `value` decoded as the type with the given id, where the type is referred
+    /// to with `type_name`, e.g. `Balance` for a `u128`.
+    pub fn render(
+        &self,
+        registry: &PortableRegistry,
+        type_id: u32,
+        type_name: Option<&str>,
+        value: &Value,
+    ) -> Value {
+        Render {
+            renderer: self,
+            registry,
+        }
+        .render(type_id, type_name, value)
+    }
+}
+
+struct Render<'a> {
+    renderer: &'a Renderer,
+    registry: &'a PortableRegistry,
+}
+
+impl Render<'_> {
+    fn render(&self, type_id: u32, type_name: Option<&str>, value: &Value) -> Value {
+        let Some(ty) = self.registry.resolve(type_id) else {
+            return value.clone()
+        };
+        match &ty.type_def {
+            TypeDef::Primitive(_) => {
+                match (type_name.map(base_name), value) {
+                    (Some("Balance" | "BalanceOf"), Value::UInt(n)) => self.balance(*n),
+                    (Some("Timestamp" | "Moment"), Value::UInt(millis)) => {
+                        iso8601(*millis)
+                            .map(Value::Literal)
+                            .unwrap_or_else(|| value.clone())
+                    }
+                    _ => value.clone(),
+                }
+            }
+            TypeDef::Array(array) if self.is_u8(array.type_param.id) => {
+                match bytes(value) {
+                    Some(bytes) => hex(&bytes),
+                    None => value.clone(),
+                }
+            }
+            TypeDef::Sequence(sequence) if self.is_u8(sequence.type_param.id) => {
+                match bytes(value) {
+                    Some(bytes) => {
+                        match String::from_utf8(bytes) {
+                            Ok(string) if is_printable(&string) => Value::String(string),
+                            Err(err) => hex(err.as_bytes()),
+                            Ok(string) => hex(string.as_bytes()),
+                        }
+                    }
+                    None => value.clone(),
+                }
+            }
+            TypeDef::Array(array) => self.render_seq(array.type_param.id, value),
+            TypeDef::Sequence(sequence) => self.render_seq(sequence.type_param.id, value),
+            TypeDef::Tuple(tuple) => {
+                match value {
+                    Value::Tuple(values) => {
+                        let values = values
+                            .values()
+                            .zip(&tuple.fields)
+                            .map(|(value, field)| self.render(field.id, None, value))
+                            .collect();
+                        Value::Tuple(Tuple::new(None, values))
+                    }
+                    value => value.clone(),
+                }
+            }
+            TypeDef::Composite(composite) => {
+                match fixed_point(ty, value) {
+                    Some(rendered) => Value::Literal(rendered),
+                    None => self.render_fields(ty, &composite.fields, value),
+                }
+            }
+            TypeDef::Variant(variant) => {
+                let ident = match value {
+                    Value::Map(map) => map.ident(),
+                    Value::Tuple(tuple) => tuple.ident(),
+                    _ => None,
+                };
+                match variant
+                    .variants
+                    .iter()
+                    .find(|variant| Some(&variant.name) == ident.as_ref())
+                {
+                    Some(variant) => self.render_fields(ty, &variant.fields, value),
+                    None => value.clone(),
+                }
+            }
+            TypeDef::Compact(_) | TypeDef::BitSequence(_) => value.clone(),
+        }
+    }
+
+    fn render_seq(&self, type_id: u32, value: &Value) -> Value {
+        match value {
+            Value::Seq(seq) => {
+                Value::Seq(Seq::new(
+                    seq.elems()
+                        .iter()
+                        .map(|elem| self.render(type_id, None, elem))
+                        .collect(),
+                ))
+            }
+            value => value.clone(),
+        }
+    }
+
+    /// Render the fields of a struct or enum variant `ty`. The type names of fields
+    /// which are type parameters of `ty`, e.g. the `T` of `Option<T>`, are unknown.
+    fn render_fields(
+        &self,
+        ty: &Type<PortableForm>,
+        fields: &[Field<PortableForm>],
+        value: &Value,
+    ) -> Value {
+        match value {
+            Value::Map(map) => {
+                let entries = map
+                    .iter()
+                    .zip(fields)
+                    .map(|((key, value), field)| {
+                        let type_name = field_type_name(ty, field);
+                        (key.clone(), self.render(field.ty.id, type_name, value))
+                    })
+                    .collect();
+                Value::Map(Map::new(map.ident().as_deref(), entries))
+            }
+            Value::Tuple(tuple) => {
+                let values = tuple
+                    .values()
+                    .zip(fields)
+                    .map(|(value, field)| {
+                        self.render(field.ty.id, field_type_name(ty, field), value)
+                    })
+                    .collect();
+                Value::Tuple(Tuple::new(tuple.ident().as_deref(), values))
+            }
+            value => value.clone(),
+        }
+    }
+
+    fn balance(&self, balance: u128) -> Value {
+        match self.renderer.balance {
+            Some(ref formatter) => Value::Literal(formatter(balance)),
+            None => Value::UInt(balance),
+        }
+    }
+
+    fn is_u8(&self, type_id: u32) -> bool {
+        self.registry.resolve(type_id).is_some_and(|ty| {
+            matches!(ty.type_def, TypeDef::Primitive(TypeDefPrimitive::U8))
+        })
+    }
+}
+
+/// The type name of a field of `ty`, unless it is one of the type parameters of `ty`.
+fn field_type_name<'a>(
+    ty: &Type<PortableForm>,
+    field: &'a Field<PortableForm>,
+) -> Option<&'a str> {
+    field
+        .type_name
+        .as_deref()
+        .filter(|name| !ty.type_params.iter().any(|param| param.name == *name))
+}
+
+/// The last segment of a type name without its generic arguments, e.g. `Balance` for
+/// `ink::primitives::Balance`.
+fn base_name(type_name: &str) -> &str {
+    let name = type_name.split('<').next().unwrap_or(type_name);
+    name.rsplit("::").next().unwrap_or(name).trim()
+}
+
+fn bytes(value: &Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Seq(seq) => {
+            seq.elems()
+                .iter()
+                .map(|elem| {
+                    match elem {
+                        Value::UInt(byte) => u8::try_from(*byte).ok(),
+                        _ => None,
+                    }
+                })
+                .collect()
+        }
+        Value::Hex(hex) => Some(hex.bytes().to_vec()),
+        _ => None,
+    }
+}
+
+fn hex(bytes: &[u8]) -> Value {
+    Value::Hex(Hex::from_str(&hex::encode(bytes)).expect("hex encoded bytes are valid"))
+}
+
+/// Returns `true` for a non-empty string without control characters other than
+/// whitespace.
+fn is_printable(string: &str) -> bool {
+    !string.is_empty()
+        && string
+            .chars()
+            .all(|c| !c.is_control() || c == '\n' || c == '\r' || c == '\t')
+}
+
+/// Render a value of the per-thing and fixed-point types of `sp-arithmetic`: the
+/// per-things as percentages, e.g. `12.5%` for a `Perbill` of `125_000_000`, and the
+/// fixed-point numbers as decimals.
+fn fixed_point(ty: &Type<PortableForm>, value: &Value) -> Option<String> {
+    let inner = match value {
+        Value::Tuple(tuple) if tuple.values().count() == 1 => tuple.values().next()?,
+        Value::Map(map) if map.values().count() == 1 => map.values().next()?,
+        _ => return None,
+    };
+    // the number of decimal places of a percentage of the parts of a per-thing, and of
+    // the inner value of a fixed-point number
+    let (scale, percent) = match ty.path.ident()?.as_str() {
+        "Percent" => (0, true),
+        "Permill" => (4, true),
+        "Perbill" => (7, true),
+        "Perquintill" => (16, true),
+        "FixedU64" | "FixedI64" => (9, false),
+        "FixedU128" | "FixedI128" => (18, false),
+        _ => return None,
+    };
+    let decimal = match inner {
+        Value::UInt(n) => decimal(*n, scale),
+        Value::Int(n) if *n < 0 => format!("-{}", decimal(n.unsigned_abs(), scale)),
+        Value::Int(n) => decimal(n.unsigned_abs(), scale),
+        _ => return None,
+    };
+    Some(
+        if percent {
+            format!("{decimal}%")
+        } else {
+            decimal
+        },
+    )
+}
+
+/// Format `n / 10^scale` as a decimal without trailing zeros.
+pub(crate) fn decimal(n: u128, scale: u32) -> String {
+    let unit = 10u128.pow(scale);
+    let fraction = n % unit;
+    if fraction == 0 {
+        return (n / unit).to_string()
+    }
+    let fraction = format!("{fraction:0width$}", width = scale as usize);
+    format!("{}.{}", n / unit, fraction.trim_end_matches('0'))
+}
+
+/// Format a timestamp in milliseconds since the Unix epoch as an ISO-8601 date, e.g.
+/// `2024-01-31T12:00:00.000Z`. Returns `None` for dates after the year 9999.
+fn iso8601(millis: u128) -> Option<String> {
+    const MAX_SECS: u128 = 253_402_300_799;
+
+    let secs = millis / 1000;
+    if secs > MAX_SECS {
+        return None
+    }
+    let secs = secs as i64;
+    let (days, time) = (secs / 86_400, secs % 86_400);
+    // convert the days since the epoch to a civil date, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524
+        - day_of_era / 146_096)
+        / 365;
+    let day_of_year =
+        day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        time / 3600,
+        time % 3600 / 60,
+        time % 60,
+        millis % 1000
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use scale::Encode;
+    use scale_info::{
+        MetaType,
+        Registry,
+        TypeInfo,
+    };
+
+    type Balance = u128;
+    type Timestamp = u64;
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo, Encode)]
+    struct Perbill(u32);
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo, Encode)]
+    struct FixedI64(i64);
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo, Encode)]
+    struct Account {
+        balance: Balance,
+        count: u128,
+        created: Timestamp,
+        name: Vec<u8>,
+        data: Vec<u8>,
+        code: [u8; 4],
+        fee: Perbill,
+        rate: FixedI64,
+        deposits: Option<Balance>,
+    }
+
+    fn render<T: TypeInfo + Encode + 'static>(
+        renderer: &Renderer,
+        value: T,
+    ) -> anyhow::Result<String> {
+        let mut registry = Registry::new();
+        let type_id = registry.register_type(&MetaType::new::<T>()).id;
+        let registry: PortableRegistry = registry.into();
+        let transcoder = crate::TranscoderBuilder::new(&registry).done();
+        let decoded = transcoder.decode(&registry, type_id, &mut &value.encode()[..])?;
+        Ok(renderer
+            .render(&registry, type_id, None, &decoded)
+            .to_string())
+    }
+
+    fn account() -> Account {
+        Account {
+            balance: 1_500_000_000_000,
+            count: 3,
+            created: 1_706_702_400_123,
+            name: b"ink!".to_vec(),
+            data: vec![0, 159, 146, 150],
+            code: [0xde, 0xad, 0xbe, 0xef],
+            fee: Perbill(125_000_000),
+            rate: FixedI64(-2_500_000_000),
+            deposits: Some(42),
+        }
+    }
+
+    #[test]
+    fn render_well_known_types() -> anyhow::Result<()> {
+        let renderer =
+            Renderer::default().balance_formatter(|balance| format!("{balance} UNIT"));
+
+        assert_eq!(
+            render(&renderer, account())?,
+            "Account { balance: 1500000000000 UNIT, count: 3, \
+            created: 2024-01-31T12:00:00.123Z, name: ink!, data: 0x009f9296, \
+            code: 0xdeadbeef, fee: 12.5%, rate: -2.5, deposits: Some(42) }"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn render_balances_without_formatter_as_integers() -> anyhow::Result<()> {
+        let rendered = render(&Renderer::default(), account())?;
+
+        assert!(rendered.contains("balance: 1500000000000,"), "{rendered}");
+        Ok(())
+    }
+
+    #[test]
+    fn format_decimals_and_dates() {
+        assert_eq!(decimal(125, 1), "12.5");
+        assert_eq!(decimal(1_000_000, 6), "1");
+        assert_eq!(decimal(1_000_001, 6), "1.000001");
+        assert_eq!(iso8601(0).as_deref(), Some("1970-01-01T00:00:00.000Z"));
+        assert_eq!(
+            iso8601(951_782_400_000).as_deref(),
+            Some("2000-02-29T00:00:00.000Z")
+        );
+        assert_eq!(iso8601(u64::MAX.into()), None);
+    }
+}
@@ -15,12 +15,15 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    Bits,
     Hex,
     Map,
     Seq,
     Tuple,
     Value,
+    I256,
 };
+use primitive_types::U256;
 use std::fmt::{
     Debug,
     Display,
@@ -39,11 +42,14 @@ impl<'a> Debug for DisplayValue<'a> {
             Value::Char(character) => <char as Debug>::fmt(character, f),
             Value::UInt(uint) => <u128 as Display>::fmt(uint, f),
             Value::Int(integer) => <i128 as Display>::fmt(integer, f),
+            Value::BigUInt(uint) => <U256 as Display>::fmt(uint, f),
+            Value::BigInt(integer) => <I256 as Display>::fmt(integer, f),
             Value::Map(map) => <DisplayMap as Debug>::fmt(&DisplayMap(map), f),
             Value::Tuple(tuple) => <DisplayTuple as Debug>::fmt(&DisplayTuple(tuple), f),
             Value::String(string) => <String as Display>::fmt(string, f),
             Value::Seq(seq) => <DisplaySeq as Debug>::fmt(&DisplaySeq(seq), f),
             Value::Hex(hex) => <Hex as Debug>::fmt(hex, f),
+            Value::Bits(bits) => <Bits as Display>::fmt(bits, f),
             Value::Literal(literal) => <String as Display>::fmt(literal, f),
             Value::Unit => write!(f, "()"),
         }
@@ -123,6 +129,26 @@ impl LowerHex for Hex {
     }
 }
 
+impl Display for I256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        if self.is_negative() {
+            write!(f, "-{}", self.unsigned_abs())
+        } else {
+            write!(f, "{}", self.unsigned_abs())
+        }
+    }
+}
+
+impl Display for Bits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "0b")?;
+        for bit in self.as_slice() {
+            write!(f, "{}", u8::from(*bit))?;
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -20,6 +20,7 @@ mod display;
 mod parse;
 
 use indexmap::IndexMap;
+use primitive_types::U256;
 
 use crate::util;
 use std::{
@@ -42,6 +43,7 @@ use std::{
 use serde::{
     ser::SerializeMap,
     Serialize,
+    Serializer,
 };
 
 pub use self::parse::parse_value;
@@ -52,11 +54,17 @@ pub enum Value {
     Char(char),
     UInt(u128),
     Int(i128),
+    /// An unsigned integer which does not fit into a `u128`, e.g. a `U256`.
+    #[serde(serialize_with = "serialize_as_str")]
+    BigUInt(U256),
+    /// A signed integer which does not fit into an `i128`, e.g. an `I256`.
+    BigInt(I256),
     Map(Map),
     Tuple(Tuple),
     String(String),
     Seq(Seq),
     Hex(Hex),
+    Bits(Bits),
     Literal(String),
     Unit,
 }
@@ -249,3 +257,179 @@ impl Hex {
         &self.bytes
     }
 }
+
+/// A signed 256-bit integer, stored in two's complement.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct I256(U256);
+
+impl I256 {
+    const SIGN_BIT: U256 = U256([0, 0, 0, 1 << 63]);
+
+    /// Create an `I256` from its little-endian two's complement representation.
+    pub fn from_le_bytes(bytes: [u8; 32]) -> Self {
+        Self(U256::from_little_endian(&bytes))
+    }
+
+    /// Return the little-endian two's complement representation.
+    pub fn to_le_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.0.to_little_endian(&mut bytes);
+        bytes
+    }
+
+    /// Returns `true` if the value is less than zero.
+    pub fn is_negative(&self) -> bool {
+        self.0.bit(255)
+    }
+
+    /// Create an `I256` from a sign and a magnitude, returns `None` if it does not fit.
+    pub fn from_sign_and_magnitude(negative: bool, magnitude: U256) -> Option<Self> {
+        if negative {
+            (magnitude <= Self::SIGN_BIT)
+                .then(|| Self((!magnitude).overflowing_add(U256::one()).0))
+        } else {
+            (magnitude < Self::SIGN_BIT).then_some(Self(magnitude))
+        }
+    }
+
+    /// Returns the absolute value as an unsigned integer.
+    pub fn unsigned_abs(&self) -> U256 {
+        if self.is_negative() {
+            (!self.0).overflowing_add(U256::one()).0
+        } else {
+            self.0
+        }
+    }
+}
+
+impl From<i128> for I256 {
+    fn from(value: i128) -> Self {
+        Self::from_sign_and_magnitude(value < 0, value.unsigned_abs().into())
+            .expect("an i128 always fits into an I256")
+    }
+}
+
+impl From<u128> for I256 {
+    fn from(value: u128) -> Self {
+        Self(value.into())
+    }
+}
+
+impl TryFrom<U256> for I256 {
+    type Error = ParseBigIntError;
+
+    fn try_from(value: U256) -> Result<Self, Self::Error> {
+        Self::from_sign_and_magnitude(false, value)
+            .ok_or_else(|| ParseBigIntError(value.to_string()))
+    }
+}
+
+impl Ord for I256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.0 ^ Self::SIGN_BIT).cmp(&(other.0 ^ Self::SIGN_BIT))
+    }
+}
+
+impl PartialOrd for I256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for I256 {
+    type Err = ParseBigIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let magnitude = parse_u256(digits).map_err(|_| ParseBigIntError(s.into()))?;
+        Self::from_sign_and_magnitude(negative, magnitude)
+            .ok_or_else(|| ParseBigIntError(s.into()))
+    }
+}
+
+impl Serialize for I256 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+/// Error returned when parsing an integer which does not fit into 256 bits.
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid 256-bit integer `{0}`")]
+pub struct ParseBigIntError(String);
+
+/// Parse a decimal `U256`.
+pub fn parse_u256(s: &str) -> Result<U256, ParseBigIntError> {
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(ParseBigIntError(s.into()))
+    }
+    U256::from_dec_str(s).map_err(|_| ParseBigIntError(s.into()))
+}
+
+fn serialize_as_str<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: std::fmt::Display,
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+/// A sequence of bits, e.g. a `BitVec`, written as `0b1011` with the first bit on the
+/// left.
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Bits(Vec<bool>);
+
+impl Bits {
+    pub fn new(bits: Vec<bool>) -> Self {
+        Self(bits)
+    }
+
+    pub fn as_slice(&self) -> &[bool] {
+        &self.0
+    }
+}
+
+impl From<Vec<bool>> for Bits {
+    fn from(bits: Vec<bool>) -> Self {
+        Self(bits)
+    }
+}
+
+impl FromStr for Bits {
+    type Err = ParseBitsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.strip_prefix("0b")
+            .unwrap_or(s)
+            .chars()
+            .map(|c| {
+                match c {
+                    '0' => Ok(false),
+                    '1' => Ok(true),
+                    c => Err(ParseBitsError(c)),
+                }
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+/// Error returned when parsing a bit sequence containing anything but `0` and `1`.
+#[derive(Debug, thiserror::Error)]
+#[error("Invalid bit `{0}`, expected `0` or `1`")]
+pub struct ParseBitsError(char);
+
+impl Serialize for Bits {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
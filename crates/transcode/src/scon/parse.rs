@@ -15,10 +15,13 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    parse_u256,
+    Bits,
     Hex,
     Map,
     Tuple,
     Value,
+    I256,
 };
 use escape8259::unescape;
 use nom::{
@@ -66,6 +69,7 @@ fn scon_value(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
     ws(alt((
         scon_unit,
         scon_hex,
+        scon_bits,
         scon_seq,
         scon_tuple,
         scon_map,
@@ -151,9 +155,15 @@ fn scon_integer(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
             let digits = parts.join("");
             if let Some(sign) = sign {
                 let s = format!("{sign}{digits}");
-                s.parse::<i128>().map(Value::Int)
+                match s.parse::<i128>() {
+                    Ok(int) => Ok(Value::Int(int)),
+                    Err(_) => I256::from_str(&s).map(Value::BigInt),
+                }
             } else {
-                digits.parse::<u128>().map(Value::UInt)
+                match digits.parse::<u128>() {
+                    Ok(uint) => Ok(Value::UInt(uint)),
+                    Err(_) => parse_u256(&digits).map(Value::BigUInt),
+                }
             }
         })
         .parse(input)
@@ -234,14 +244,26 @@ fn scon_hex(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
         .parse(input)
 }
 
+fn scon_bits(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
+    tag("0b")
+        .precedes(take_while1(|c| c == '0' || c == '1'))
+        .map_res::<_, _, super::ParseBitsError>(|bit_str| {
+            let bits = Bits::from_str(bit_str)?;
+            Ok(Value::Bits(bits))
+        })
+        .parse(input)
+}
+
 /// Parse any alphanumeric literal with more than 39 characters (the length of
-/// `u128::MAX`)
+/// `u128::MAX`) which is not a number.
 ///
 /// This is suitable for capturing e.g. Base58 encoded literals for Substrate addresses
 fn scon_literal(input: &str) -> IResult<&str, Value, ErrorTree<&str>> {
     const MAX_UINT_LEN: usize = 39;
     alphanumeric1
-        .verify(|s: &&str| s.len() > MAX_UINT_LEN)
+        .verify(|s: &&str| {
+            s.len() > MAX_UINT_LEN && !s.chars().all(|c| c.is_ascii_digit())
+        })
         .recognize()
         .map(|literal: &str| Value::Literal(literal.to_string()))
         .parse(input)
@@ -313,14 +335,91 @@ mod tests {
             ("", Value::UInt(340282366920938463463374607431768211455))
         );
 
+        // integers larger than 128 bits
+        assert_eq!(
+            scon_integer("340282366920938463463374607431768211456").unwrap(),
+            (
+                "",
+                Value::BigUInt(primitive_types::U256::from(u128::MAX) + 1)
+            )
+        );
+        assert_eq!(
+            scon_integer("-170141183460469231731687303715884105729").unwrap(),
+            (
+                "",
+                Value::BigInt(
+                    I256::from_sign_and_magnitude(
+                        true,
+                        primitive_types::U256::from(i128::MIN.unsigned_abs()) + 1
+                    )
+                    .unwrap()
+                )
+            )
+        );
+
         // too many digits
         assert_matches!(
-            scon_integer("3402823669209384634633746074317682114550"),
+            scon_integer(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+            ),
+            Err(nom::Err::Error(_))
+        );
+        assert_matches!(
+            scon_integer(
+                "-57896044618658097711785492504343953926634992332820282019728792003956564819969"
+            ),
             Err(nom::Err::Error(_))
         );
         assert_matches!(scon_integer("abc123"), Err(nom::Err::Error(_)));
     }
 
+    #[test]
+    fn test_big_integer_display_roundtrip() {
+        let values = [
+            "0",
+            "-1",
+            "340282366920938463463374607431768211456",
+            "57896044618658097711785492504343953926634992332820282019728792003956564819967",
+            "-57896044618658097711785492504343953926634992332820282019728792003956564819968",
+        ];
+        for value in values {
+            let int = I256::from_str(value).unwrap();
+            assert_eq!(int.to_string(), value);
+            assert_eq!(I256::from_le_bytes(int.to_le_bytes()), int);
+        }
+        assert!(I256::from_str("-1").unwrap() < I256::from_str("0").unwrap());
+        assert!(I256::from(i128::MIN) > I256::from_str(values[4]).unwrap());
+    }
+
+    #[test]
+    fn test_bits() {
+        assert_eq!(
+            scon_value("0b1011").unwrap(),
+            ("", Value::Bits(vec![true, false, true, true].into()))
+        );
+        assert_eq!(
+            scon_value("[0b0, 0b01]").unwrap(),
+            (
+                "",
+                Value::Seq(
+                    vec![
+                        Value::Bits(vec![false].into()),
+                        Value::Bits(vec![false, true].into())
+                    ]
+                    .into()
+                )
+            )
+        );
+        assert_eq!(Value::Bits(vec![true, false].into()).to_string(), "0b10");
+        assert_matches!(scon_bits("0b2"), Err(nom::Err::Error(_)));
+        assert_eq!(
+            Bits::from_str("0b10").unwrap(),
+            Bits::from_str("10").unwrap()
+        );
+        // only a single prefix is stripped
+        assert!(Bits::from_str("0b0b10").is_err());
+    }
+
     #[test]
     fn test_string() {
         // Plain Unicode strings with no escaping
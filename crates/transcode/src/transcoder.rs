@@ -15,6 +15,10 @@
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
 use super::{
+    custom_types::{
+        CustomTypeTranscoder,
+        CustomTypes,
+    },
     decode::Decoder,
     encode::Encoder,
     env_types::{
@@ -30,6 +34,10 @@ use super::{
 };
 
 use anyhow::Result;
+use ink_env::{
+    DefaultEnvironment,
+    Environment,
+};
 use scale::Output;
 use scale_info::{
     PortableRegistry,
@@ -102,6 +110,47 @@ impl TranscoderBuilder {
             .register_custom_type_decoder::<primitive_types::H256, _>(env_types::Hash)
     }
 
+    /// Display the `AccountId` as an SS58 address with the given address format prefix,
+    /// instead of the generic Substrate prefix `42`. Addresses with any prefix are
+    /// accepted when encoding.
+    pub fn with_ss58_prefix(self, prefix: u16) -> Self {
+        let mut this = self;
+        let path_keys = [
+            PathKey::from_type::<AccountId32>(),
+            PathKey::from_type::<<DefaultEnvironment as Environment>::AccountId>(),
+        ];
+        for path_key in path_keys {
+            if let Some(type_id) = this.types_by_path.get(&path_key).copied() {
+                let transcoder = CustomTypeTranscoder::Ss58 { prefix };
+                this.encoders.insert(type_id, Box::new(transcoder.clone()));
+                this.decoders.insert(type_id, Box::new(transcoder));
+                tracing::debug!("Registered SS58 prefix {prefix} for type `{type_id:?}`");
+            }
+        }
+        this
+    }
+
+    /// Register the transcoders of the configured [`CustomTypes`] for the types of the
+    /// `registry` the builder was created with, replacing any transcoders registered
+    /// before for the same types.
+    ///
+    /// # Errors
+    ///
+    /// - If the codec configured for a type is not applicable to it.
+    pub fn with_custom_types(
+        self,
+        registry: &PortableRegistry,
+        custom_types: &CustomTypes,
+    ) -> Result<Self> {
+        let mut this = self;
+        for (type_id, transcoder) in custom_types.transcoders(registry)? {
+            this.encoders.insert(type_id, Box::new(transcoder.clone()));
+            this.decoders.insert(type_id, Box::new(transcoder));
+            tracing::debug!("Registered custom transcoder for type `{:?}`", type_id);
+        }
+        Ok(this)
+    }
+
     pub fn register_custom_type_transcoder<T, U>(self, transcoder: U) -> Self
     where
         T: TypeInfo + 'static,
@@ -303,6 +352,100 @@ mod tests {
         )
     }
 
+    /// Stand-ins for types which `scale-info` describes as primitives or bit sequences
+    /// without a `TypeInfo` implementation in the dependencies of this crate.
+    macro_rules! type_info {
+        ($name:ident, $type_def:expr) => {
+            struct $name;
+
+            impl TypeInfo for $name {
+                type Identity = Self;
+
+                fn type_info() -> scale_info::Type {
+                    $type_def.into()
+                }
+            }
+        };
+    }
+
+    type_info!(U256, scale_info::TypeDefPrimitive::U256);
+    type_info!(I256, scale_info::TypeDefPrimitive::I256);
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct Lsb0;
+
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    struct Msb0;
+
+    type_info!(
+        BitVecU8Lsb0,
+        scale_info::TypeDefBitSequence::new::<u8, Lsb0>()
+    );
+    type_info!(
+        BitVecU32Msb0,
+        scale_info::TypeDefBitSequence::new::<u32, Msb0>()
+    );
+
+    #[test]
+    fn transcode_big_integers() -> Result<()> {
+        let u256_max = primitive_types::U256::MAX;
+        transcode_roundtrip::<U256>("0", Value::BigUInt(0.into()))?;
+        transcode_roundtrip::<U256>(&u256_max.to_string(), Value::BigUInt(u256_max))?;
+        transcode_roundtrip::<U256>(
+            "\"340_282_366_920_938_463_463_374_607_431_768_211_456\"",
+            Value::BigUInt(primitive_types::U256::from(u128::MAX) + 1),
+        )?;
+
+        let i256_min = "-57896044618658097711785492504343953926634992332820282019728792003956564819968";
+        let i256_max = "57896044618658097711785492504343953926634992332820282019728792003956564819967";
+        transcode_roundtrip::<I256>("-1", Value::BigInt(scon::I256::from(-1i128)))?;
+        transcode_roundtrip::<I256>(i256_min, Value::BigInt(i256_min.parse()?))?;
+        transcode_roundtrip::<I256>(i256_max, Value::BigInt(i256_max.parse()?))?;
+
+        let (registry, ty) = registry_with_type::<I256>()?;
+        let transcoder = Transcoder::new(Default::default());
+        let mut encoded = Vec::new();
+        transcoder.encode(&registry, ty, &Value::Int(-2), &mut encoded)?;
+        let mut expected = [0xff; 32];
+        expected[0] = 0xfe;
+        assert_eq!(encoded, expected);
+        assert!(transcoder
+            .encode(&registry, ty, &Value::BigUInt(u256_max), &mut Vec::new())
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn transcode_bit_sequence() -> Result<()> {
+        transcode_roundtrip::<BitVecU8Lsb0>(
+            "0b1011",
+            Value::Bits(vec![true, false, true, true].into()),
+        )?;
+        transcode_roundtrip::<BitVecU8Lsb0>(
+            "[true, false]",
+            Value::Bits(vec![true, false].into()),
+        )?;
+        transcode_roundtrip::<BitVecU32Msb0>(
+            "0b101100000000000000000000000000001",
+            Value::Bits(scon::Bits::from_str("0b101100000000000000000000000000001")?),
+        )?;
+
+        let (registry, ty) = registry_with_type::<BitVecU8Lsb0>()?;
+        let transcoder = Transcoder::new(Default::default());
+        let mut encoded = Vec::new();
+        let bits = scon::parse_value("0b1011")?;
+        transcoder.encode(&registry, ty, &bits, &mut encoded)?;
+        assert_eq!(encoded, vec![4 << 2, 0b1101]);
+
+        let (registry, ty) = registry_with_type::<BitVecU32Msb0>()?;
+        let mut encoded = Vec::new();
+        transcoder.encode(&registry, ty, &bits, &mut encoded)?;
+        assert_eq!(encoded, vec![4 << 2, 0, 0, 0, 0b1011_0000]);
+        Ok(())
+    }
+
     #[test]
     fn transcode_byte_array() -> Result<()> {
         transcode_roundtrip::<[u8; 2]>(
@@ -14,7 +14,116 @@
 // You should have received a copy of the GNU General Public License
 // along with cargo-contract.  If not, see <http://www.gnu.org/licenses/>.
 
+use scale_bits::scale::format::{
+    Format,
+    OrderFormat,
+    StoreFormat,
+};
+use scale_info::{
+    form::PortableForm,
+    PortableRegistry,
+    TypeDef,
+    TypeDefBitSequence,
+    TypeDefPrimitive,
+};
+
 /// Decode hex string with or without 0x prefix
 pub fn decode_hex(input: &str) -> Result<Vec<u8>, hex::FromHexError> {
     hex::decode(input.trim_start_matches("0x"))
 }
+
+/// Resolve the SCALE encoding format of a bit sequence, i.e. the store type and the bit
+/// order of the `BitVec`.
+pub fn bit_sequence_format(
+    bit_seq: &TypeDefBitSequence<PortableForm>,
+    registry: &PortableRegistry,
+) -> anyhow::Result<Format> {
+    let resolve = |type_id: u32| {
+        registry.resolve(type_id).ok_or_else(|| {
+            anyhow::anyhow!("Failed to resolve type with id `{:?}`", type_id)
+        })
+    };
+    let store = match resolve(bit_seq.bit_store_type.id)?.type_def {
+        TypeDef::Primitive(TypeDefPrimitive::U8) => StoreFormat::U8,
+        TypeDef::Primitive(TypeDefPrimitive::U16) => StoreFormat::U16,
+        TypeDef::Primitive(TypeDefPrimitive::U32) => StoreFormat::U32,
+        TypeDef::Primitive(TypeDefPrimitive::U64) => StoreFormat::U64,
+        ref store => {
+            anyhow::bail!("Unsupported bit store type {:?}", store)
+        }
+    };
+    let order_type = resolve(bit_seq.bit_order_type.id)?;
+    let order = match order_type.path.ident().as_deref() {
+        Some("Lsb0") => OrderFormat::Lsb0,
+        Some("Msb0") => OrderFormat::Msb0,
+        _ => anyhow::bail!("Unsupported bit order type {}", order_type.path),
+    };
+    Ok(Format::new(store, order))
+}
+
+/// Render the name of the type with the given id as it would be written in Rust, e.g.
+/// `Option<String>`, `[u8; 32]` or `(u32, bool)`.
+pub fn type_name(registry: &PortableRegistry, type_id: u32) -> String {
+    let Some(ty) = registry.resolve(type_id) else {
+        return format!("<unknown type {type_id}>")
+    };
+    let names = |ids: &mut dyn Iterator<Item = u32>| {
+        ids.map(|id| type_name(registry, id)).collect::<Vec<_>>()
+    };
+    match &ty.type_def {
+        TypeDef::Primitive(primitive) => {
+            match primitive {
+                TypeDefPrimitive::Bool => "bool",
+                TypeDefPrimitive::Char => "char",
+                TypeDefPrimitive::Str => "String",
+                TypeDefPrimitive::U8 => "u8",
+                TypeDefPrimitive::U16 => "u16",
+                TypeDefPrimitive::U32 => "u32",
+                TypeDefPrimitive::U64 => "u64",
+                TypeDefPrimitive::U128 => "u128",
+                TypeDefPrimitive::U256 => "u256",
+                TypeDefPrimitive::I8 => "i8",
+                TypeDefPrimitive::I16 => "i16",
+                TypeDefPrimitive::I32 => "i32",
+                TypeDefPrimitive::I64 => "i64",
+                TypeDefPrimitive::I128 => "i128",
+                TypeDefPrimitive::I256 => "i256",
+            }
+            .to_string()
+        }
+        TypeDef::Array(array) => {
+            format!(
+                "[{}; {}]",
+                type_name(registry, array.type_param.id),
+                array.len
+            )
+        }
+        TypeDef::Sequence(sequence) => {
+            format!("Vec<{}>", type_name(registry, sequence.type_param.id))
+        }
+        TypeDef::Tuple(tuple) => {
+            match names(&mut tuple.fields.iter().map(|field| field.id)).as_slice() {
+                [field] => format!("({field},)"),
+                fields => format!("({})", fields.join(", ")),
+            }
+        }
+        TypeDef::Compact(compact) => {
+            format!("Compact<{}>", type_name(registry, compact.type_param.id))
+        }
+        TypeDef::BitSequence(_) => "BitVec".to_string(),
+        TypeDef::Composite(_) | TypeDef::Variant(_) => {
+            let ident = ty.path.ident().unwrap_or_else(|| ty.path.to_string());
+            let params = names(
+                &mut ty
+                    .type_params
+                    .iter()
+                    .filter_map(|param| param.ty.map(|ty| ty.id)),
+            );
+            if params.is_empty() {
+                ident
+            } else {
+                format!("{ident}<{}>", params.join(", "))
+            }
+        }
+    }
+}